@@ -12,8 +12,9 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+use crate::helpers::VanityPattern;
 use snarkvm::console::{
-    account::PrivateKey,
+    account::{Address, PrivateKey, Signature},
     prelude::{Environment, Uniform},
     types::Field,
 };
@@ -21,13 +22,28 @@ use snarkvm::console::{
 use anyhow::{anyhow, bail, Result};
 use clap::Parser;
 use colored::Colorize;
-use core::str::FromStr;
+use core::{
+    str::FromStr,
+    sync::atomic::{AtomicBool, AtomicU64, Ordering},
+    time::Duration,
+};
+use parking_lot::Mutex;
 use rand::SeedableRng;
 use rand_chacha::ChaChaRng;
-use rayon::prelude::*;
+use std::{
+    io::{stdout, Write},
+    path::PathBuf,
+    sync::Arc,
+    time::Instant,
+};
 
 type Network = snarkvm::prelude::Testnet3;
 
+/// The interval at which the vanity search progress is reported.
+const VANITY_PROGRESS_INTERVAL: Duration = Duration::from_secs(1);
+/// The domain separator that is prepended to a message before it is signed or verified.
+const MESSAGE_DOMAIN_SEPARATOR: &[u8] = b"Aleo Signed Message:\n";
+
 /// Commands to manage Aleo accounts.
 #[derive(Debug, Parser)]
 pub enum Account {
@@ -36,88 +52,227 @@ pub enum Account {
         /// Seed the RNG with a numeric value
         #[clap(short = 's', long)]
         seed: Option<String>,
-        /// Try until an address with the vanity string is found
+        /// Try until an address that starts or ends with the vanity string is found
         #[clap(short = 'v', long)]
         vanity: Option<String>,
+        /// Try until an address that starts with the given string is found
+        #[clap(long)]
+        prefix: Option<String>,
+        /// Try until an address that ends with the given string is found
+        #[clap(long)]
+        suffix: Option<String>,
+        /// Try until an address that matches the given regex is found
+        #[clap(long)]
+        regex: Option<String>,
+    },
+    /// Signs a message using an Aleo private key
+    Sign {
+        /// Specify the account private key of the signer
+        #[clap(long = "private-key")]
+        private_key: Option<String>,
+        /// Specify the path to a file containing the account private key of the signer
+        #[clap(long = "private-key-file")]
+        private_key_file: Option<PathBuf>,
+        /// The message to sign
+        #[clap(short = 'm', long, conflicts_with = "file")]
+        message: Option<String>,
+        /// The path to a file whose contents to sign
+        #[clap(short = 'f', long, conflicts_with = "message")]
+        file: Option<PathBuf>,
+        /// Sign the message as-is, without the domain separator
+        #[clap(long)]
+        raw: bool,
+    },
+    /// Verifies a signature on a message from an Aleo address
+    Verify {
+        /// The address of the signer
+        #[clap(short = 'a', long)]
+        address: String,
+        /// The signature to verify
+        #[clap(short = 's', long)]
+        signature: String,
+        /// The message that was signed
+        #[clap(short = 'm', long, conflicts_with = "file")]
+        message: Option<String>,
+        /// The path to a file whose contents were signed
+        #[clap(short = 'f', long, conflicts_with = "message")]
+        file: Option<PathBuf>,
+        /// Verify the message as-is, without the domain separator
+        #[clap(long)]
+        raw: bool,
     },
 }
 
 impl Account {
     pub fn parse(self) -> Result<String> {
         match self {
-            Self::New { seed, vanity } => {
-                // Ensure only the seed or the vanity string is specified.
-                if seed.is_some() && vanity.is_some() {
-                    bail!("Cannot specify both the '--seed' and '--vanity' flags");
+            Self::New { seed, vanity, prefix, suffix, regex } => {
+                // Parse the vanity pattern, ensuring at most one is specified.
+                let pattern = match (vanity, prefix, suffix, regex) {
+                    (None, None, None, None) => None,
+                    (Some(vanity), None, None, None) => Some(VanityPattern::prefix_or_suffix(&vanity)?),
+                    (None, Some(prefix), None, None) => Some(VanityPattern::prefix(&prefix)?),
+                    (None, None, Some(suffix), None) => Some(VanityPattern::suffix(&suffix)?),
+                    (None, None, None, Some(regex)) => Some(VanityPattern::regex(&regex)?),
+                    _ => bail!(
+                        "Cannot specify more than one of the '--vanity', '--prefix', '--suffix' and '--regex' flags"
+                    ),
+                };
+
+                // Ensure only the seed or the vanity pattern is specified.
+                if seed.is_some() && pattern.is_some() {
+                    bail!("Cannot specify both the '--seed' and a vanity flag");
                 }
 
                 // Generate a vanity account.
-                if let Some(vanity) = vanity {
-                    Self::new_vanity(&vanity)
+                if let Some(pattern) = pattern {
+                    Self::new_vanity(&pattern)
                 }
                 // Default to generating a normal account, with an optional seed.
                 else {
                     Self::new_seeded(seed)
                 }
             }
+            Self::Sign { private_key, private_key_file, message, file, raw } => {
+                // Parse the private key of the signer.
+                let account = Self::parse_private_key(private_key, private_key_file)?;
+                // Prepare the message to sign.
+                let message = Self::parse_message(message, file, raw)?;
+                // Sign the message.
+                let signature = account.sign_bytes(&message, &mut rand::thread_rng())?;
+                Ok(signature.to_string())
+            }
+            Self::Verify { address, signature, message, file, raw } => {
+                // Parse the address of the signer.
+                let address = Address::<Network>::from_str(address.trim())?;
+                // Parse the signature.
+                let signature = Signature::<Network>::from_str(signature.trim())?;
+                // Prepare the message to verify.
+                let message = Self::parse_message(message, file, raw)?;
+                // Verify the signature.
+                match signature.verify_bytes(&address, &message) {
+                    true => Ok(format!("✅ The signature is valid for {address}")),
+                    false => bail!("❌ The signature is invalid for {address}"),
+                }
+            }
+        }
+    }
+
+    /// Read the private key directly from an argument or from a filesystem location,
+    /// returning the Aleo account.
+    fn parse_private_key(
+        private_key: Option<String>,
+        private_key_file: Option<PathBuf>,
+    ) -> Result<snarkos_account::Account<Network>> {
+        match (private_key, private_key_file) {
+            // Parse the private key directly.
+            (Some(private_key), None) => snarkos_account::Account::from_str(private_key.trim()),
+            // Parse the private key from a file.
+            (None, Some(path)) => snarkos_account::Account::from_str(std::fs::read_to_string(path)?.trim()),
+            // Ensure the private key is provided to the CLI.
+            (None, None) => bail!("Missing the '--private-key' or '--private-key-file' argument"),
+            // Ensure only one private key flag is provided to the CLI.
+            (Some(_), Some(_)) => {
+                bail!("Cannot use '--private-key' and '--private-key-file' simultaneously, please use only one")
+            }
         }
     }
 
-    /// Generates a new Aleo account with the given vanity string.
-    fn new_vanity(vanity: &str) -> Result<String> {
+    /// Returns the message bytes from the given text or file.
+    /// Unless `raw` is set, the message is prefixed with the domain separator and the message length,
+    /// to ensure a signature on an arbitrary message can not be mistaken for one on a transaction.
+    fn parse_message(message: Option<String>, file: Option<PathBuf>, raw: bool) -> Result<Vec<u8>> {
+        // Retrieve the message bytes.
+        let message = match (message, file) {
+            (Some(message), None) => message.into_bytes(),
+            (None, Some(path)) => std::fs::read(path)?,
+            (None, None) => bail!("Missing the '--message' or '--file' argument"),
+            (Some(_), Some(_)) => bail!("Cannot use '--message' and '--file' simultaneously, please use only one"),
+        };
+        match raw {
+            true => Ok(message),
+            false => {
+                // Construct the message as (domain separator, message length, message).
+                let mut bytes = Vec::with_capacity(MESSAGE_DOMAIN_SEPARATOR.len() + 8 + message.len());
+                bytes.extend_from_slice(MESSAGE_DOMAIN_SEPARATOR);
+                bytes.extend_from_slice(&(message.len() as u64).to_le_bytes());
+                bytes.extend_from_slice(&message);
+                Ok(bytes)
+            }
+        }
+    }
+
+    /// Generates a new Aleo account whose address matches the given vanity pattern.
+    fn new_vanity(pattern: &VanityPattern) -> Result<String> {
         // A closure to generate a new Aleo account.
         let sample_account = || snarkos_account::Account::<Network>::new(&mut rand::thread_rng());
 
-        const ITERATIONS: u128 = u16::MAX as u128;
-        const ITERATIONS_STR: &str = "65,535";
-
-        // Ensure the vanity string is valid.
-        if !crate::helpers::is_in_bech32m_charset(vanity) {
-            bail!(
-                "The vanity string '{vanity}' contains invalid bech32m characters. Try using characters from the bech32m character set: {}",
-                crate::helpers::BECH32M_CHARSET
-            );
-        }
+        // Determine the expected number of attempts to find a match.
+        let expected_attempts = pattern.expected_attempts();
 
-        // Output a message if the character set is more than 4 characters.
-        if vanity.len() > 4 {
-            let message =
-                format!(" The vanity string '{vanity}' contains 5 or more characters and will take a while to find.\n");
+        // Output a message if the vanity pattern contains 5 or more characters.
+        if expected_attempts.map_or(false, |attempts| attempts > 32f64.powi(4)) {
+            let message = " The vanity pattern contains 5 or more characters and will take a while to find.\n";
             println!("{}", message.yellow());
         }
 
-        loop {
-            // Initialize a timer.
-            let timer = std::time::Instant::now();
-
-            // Generates bech32m addresses in parallel until one is found that
-            // includes the desired vanity string at the start or end of the address.
-            let account = (0..ITERATIONS).into_par_iter().find_map_any(|_| {
-                // Initialize the result.
-                let mut account = None;
-                // Sample a random account.
-                if let Ok(candidate) = sample_account() {
-                    // Encode the address as a bech32m string.
-                    let address = candidate.address().to_string();
-                    // Set the candidate if the address includes the desired vanity string
-                    // at the start or end of the address.
-                    if crate::helpers::has_vanity_string(&address, vanity) {
-                        account = Some(candidate);
+        // Register a Ctrl-C handler, in order to stop the search gracefully.
+        // Note: The handler is unregistered once the search ends, so that repeated searches do not accumulate handlers.
+        let is_cancelled = Arc::new(AtomicBool::new(false));
+        let signal_id = signal_hook::flag::register(signal_hook::consts::SIGINT, is_cancelled.clone())?;
+
+        // Initialize the search state.
+        let is_found = AtomicBool::new(false);
+        let num_attempts = AtomicU64::new(0);
+        let account = Mutex::new(None);
+        // A closure to determine if the search should stop.
+        let is_stopped = || is_found.load(Ordering::Relaxed) || is_cancelled.load(Ordering::Relaxed);
+
+        // Initialize a timer.
+        let timer = Instant::now();
+
+        std::thread::scope(|scope| {
+            // Report the progress of the search, until it is stopped.
+            scope.spawn(|| {
+                let mut last_report = Instant::now();
+                while !is_stopped() {
+                    std::thread::sleep(Duration::from_millis(100));
+                    if last_report.elapsed() >= VANITY_PROGRESS_INTERVAL {
+                        let progress = vanity_progress(num_attempts.load(Ordering::Relaxed), timer, expected_attempts);
+                        print!("\r{progress}");
+                        let _ = stdout().flush();
+                        last_report = Instant::now();
                     }
                 }
-                // Return the result.
-                account
             });
 
-            // Return the result if a candidate was found.
-            if let Some(account) = account {
-                println!(); // Add a newline for formatting.
-                return Ok(account.to_string());
-            } else {
-                let rate = ITERATIONS / timer.elapsed().as_millis();
-                let rate = format!("[{rate} a/ms]");
-                println!(" {} Sampled {ITERATIONS_STR} accounts, searching...", rate.dimmed());
-            }
+            // Sample accounts on every thread of the rayon thread pool, until one is found
+            // whose address matches the vanity pattern, or the search is cancelled.
+            rayon::broadcast(|_| {
+                while !is_stopped() {
+                    // Sample a random account.
+                    if let Ok(candidate) = sample_account() {
+                        num_attempts.fetch_add(1, Ordering::Relaxed);
+                        // Encode the address as a bech32m string, and check it against the vanity pattern.
+                        if pattern.is_match(&candidate.address().to_string()) {
+                            // Keep the first candidate found, and signal the other threads to stop.
+                            account.lock().get_or_insert(candidate);
+                            is_found.store(true, Ordering::Relaxed);
+                        }
+                    }
+                }
+            });
+        });
+
+        // Unregister the Ctrl-C handler.
+        signal_hook::low_level::unregister(signal_id);
+
+        println!(); // Add a newline for formatting.
+
+        // Return the result if a candidate was found.
+        match account.into_inner() {
+            Some(account) => Ok(account.to_string()),
+            None => bail!("The vanity search was cancelled after sampling {} accounts", num_attempts.into_inner()),
         }
     }
 
@@ -142,6 +297,45 @@ impl Account {
     }
 }
 
+/// Returns a progress report for the vanity search, with the rate and the expected time to find a match.
+fn vanity_progress(num_attempts: u64, timer: Instant, expected_attempts: Option<f64>) -> String {
+    // Compute the number of attempts per second.
+    let elapsed = timer.elapsed().as_secs_f64();
+    let rate = match elapsed > 0.0 {
+        true => num_attempts as f64 / elapsed,
+        false => 0.0,
+    };
+    let mut progress = format!(
+        " {} Sampled {num_attempts} accounts in {}",
+        format!("[{rate:.0} a/s]").dimmed(),
+        format_duration(elapsed)
+    );
+    // Estimate the expected time to find a match, and the probability a match was found by now.
+    if let Some(expected_attempts) = expected_attempts {
+        if rate > 0.0 {
+            let probability = 1.0 - (1.0 - 1.0 / expected_attempts).powf(num_attempts as f64);
+            progress += &format!(
+                " (expected {}, {:.1}% likely by now)",
+                format_duration(expected_attempts / rate),
+                probability * 100.0
+            );
+        }
+    }
+    // Pad the report, to overwrite any remnants of the previous report.
+    format!("{progress:<80}")
+}
+
+/// Formats the given number of seconds as a human-readable duration.
+fn format_duration(seconds: f64) -> String {
+    let seconds = seconds.round() as u64;
+    match seconds {
+        0..=59 => format!("{seconds}s"),
+        60..=3599 => format!("{}m {:02}s", seconds / 60, seconds % 60),
+        3600..=86399 => format!("{}h {:02}m", seconds / 3600, (seconds % 3600) / 60),
+        _ => format!("{}d {:02}h", seconds / 86400, (seconds % 86400) / 3600),
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use crate::commands::Account;
@@ -151,11 +345,106 @@ mod tests {
     #[test]
     fn test_new() {
         for _ in 0..3 {
-            let account = Account::New { seed: None, vanity: None };
+            let account = Account::New { seed: None, vanity: None, prefix: None, suffix: None, regex: None };
             assert!(account.parse().is_ok());
         }
     }
 
+    #[test]
+    fn test_new_vanity() {
+        let account =
+            Account::New { seed: None, vanity: None, prefix: Some("q".to_string()), suffix: None, regex: None };
+        let output = account.parse().unwrap();
+        assert!(output.contains("aleo1q"));
+
+        let account =
+            Account::New { seed: None, vanity: None, prefix: None, suffix: None, regex: Some("^[qp]".into()) };
+        let output = account.parse().unwrap();
+        assert!(output.contains("aleo1q") || output.contains("aleo1p"));
+    }
+
+    #[test]
+    fn test_new_vanity_invalid() {
+        // Ensure the vanity pattern is in the bech32m character set.
+        let account =
+            Account::New { seed: None, vanity: None, prefix: Some("bio".to_string()), suffix: None, regex: None };
+        assert!(account.parse().is_err());
+
+        // Ensure at most one vanity pattern is specified.
+        let account =
+            Account::New { seed: None, vanity: None, prefix: Some("q".into()), suffix: Some("q".into()), regex: None };
+        assert!(account.parse().is_err());
+
+        // Ensure the seed and a vanity pattern are not both specified.
+        let account =
+            Account::New { seed: Some("1".into()), vanity: None, prefix: Some("q".into()), suffix: None, regex: None };
+        assert!(account.parse().is_err());
+    }
+
+    #[test]
+    fn test_sign_and_verify() {
+        let private_key = "APrivateKey1zkp2n22c19hNdGF8wuEoQcuiyuWbquY6up4CtG5DYKqPX2X";
+        let address = "aleo1uxl69laseuv3876ksh8k0nd7tvpgjt6ccrgccedpjk9qwyfensxst9ftg5";
+
+        // A closure to sign a message.
+        let sign = |message: &str, raw: bool| {
+            let private_key = Some(private_key.to_string());
+            let message = Some(message.to_string());
+            Account::Sign { private_key, private_key_file: None, message, file: None, raw }.parse().unwrap()
+        };
+        // A closure to verify a signature on a message.
+        let verify = |address: &str, signature: &str, message: &str, raw: bool| {
+            let (address, signature, message) = (address.to_string(), signature.to_string(), Some(message.to_string()));
+            Account::Verify { address, signature, message, file: None, raw }.parse()
+        };
+
+        for raw in [false, true] {
+            // Sign the message.
+            let signature = sign("hello world", raw);
+            assert!(signature.starts_with("sign1"));
+            // Ensure the signature is valid.
+            assert!(verify(address, &signature, "hello world", raw).is_ok());
+            // Ensure the signature is invalid for a different message.
+            assert!(verify(address, &signature, "hello world!", raw).is_err());
+            // Ensure the signature is invalid with a different domain separation.
+            assert!(verify(address, &signature, "hello world", !raw).is_err());
+            // Ensure the signature is invalid for a different address.
+            let other_address = "aleo1zecnqchckrzw7dlsyf65g6z5le2rmys403ecwmcafrag0e030yxqrnlg8j";
+            assert!(verify(other_address, &signature, "hello world", raw).is_err());
+        }
+    }
+
+    #[test]
+    fn test_sign_file() {
+        let private_key = "APrivateKey1zkp2n22c19hNdGF8wuEoQcuiyuWbquY6up4CtG5DYKqPX2X";
+        let address = "aleo1uxl69laseuv3876ksh8k0nd7tvpgjt6ccrgccedpjk9qwyfensxst9ftg5";
+
+        // Write the message to a file.
+        let path = std::env::temp_dir().join(format!("snarkos_test_sign_file_{}.txt", std::process::id()));
+        std::fs::write(&path, b"release manifest").unwrap();
+
+        // Sign the file.
+        let private_key = Some(private_key.to_string());
+        let file = Some(path.clone());
+        let signature =
+            Account::Sign { private_key, private_key_file: None, message: None, file, raw: false }.parse().unwrap();
+
+        // Ensure the signature is valid for the file and for its contents as a message.
+        let address = address.to_string();
+        let verify = Account::Verify {
+            address: address.clone(),
+            signature: signature.clone(),
+            message: None,
+            file: Some(path.clone()),
+            raw: false,
+        };
+        assert!(verify.parse().is_ok());
+        let message = Some("release manifest".to_string());
+        assert!(Account::Verify { address, signature, message, file: None, raw: false }.parse().is_ok());
+
+        std::fs::remove_file(path).unwrap();
+    }
+
     #[test]
     fn test_new_seeded() {
         let seed = Some(1231275789u64.to_string());
@@ -176,8 +465,7 @@ mod tests {
             "aleo1uxl69laseuv3876ksh8k0nd7tvpgjt6ccrgccedpjk9qwyfensxst9ftg5"
         );
 
-        let vanity = None;
-        let account = Account::New { seed, vanity };
+        let account = Account::New { seed, vanity: None, prefix: None, suffix: None, regex: None };
         let actual = account.parse().unwrap();
         assert_eq!(expected, actual);
     }
@@ -202,8 +490,7 @@ mod tests {
             "aleo1zecnqchckrzw7dlsyf65g6z5le2rmys403ecwmcafrag0e030yxqrnlg8j"
         );
 
-        let vanity = None;
-        let account = Account::New { seed, vanity };
+        let account = Account::New { seed, vanity: None, prefix: None, suffix: None, regex: None };
         let actual = account.parse().unwrap();
         assert_eq!(expected, actual);
     }
@@ -42,6 +42,15 @@ impl Clean {
         // Prepare the path string.
         let path_string = format!("(in \"{}\")", path.display()).dimmed();
 
+        // Remove the transaction index, if it exists.
+        let index_path = snarkos_node::indexer::indexer_dir(network, dev);
+        if index_path.exists() {
+            if let Err(error) = std::fs::remove_dir_all(&index_path) {
+                let index_path = format!("(in \"{}\")", index_path.display()).dimmed();
+                bail!("Failed to remove the snarkOS node index {index_path}\n{}", error.to_string().dimmed())
+            }
+        }
+
         // Check if the path to the ledger exists in storage.
         if path.exists() {
             // Remove the ledger files from storage.
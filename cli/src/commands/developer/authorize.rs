@@ -0,0 +1,448 @@
+// Copyright (C) 2019-2023 Aleo Systems Inc.
+// This file is part of the snarkOS library.
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at:
+// http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use super::{CurrentAleo, CurrentNetwork, Developer};
+
+use snarkvm::{
+    console::program::ProgramOwner,
+    prelude::{
+        block::{Deployment, Execution},
+        deployment_cost,
+        execution_cost,
+        store::{helpers::memory::ConsensusMemory, ConsensusStore},
+        Authorization,
+        Field,
+        Identifier,
+        Locator,
+        PrivateKey,
+        Process,
+        Program,
+        ProgramID,
+        Request,
+        Value,
+        VM,
+    },
+};
+
+use anyhow::{bail, ensure, Result};
+use clap::Parser;
+use colored::Colorize;
+use rand::{CryptoRng, Rng};
+use serde::{Deserialize, Serialize};
+use std::{path::PathBuf, str::FromStr};
+
+/// Authorizes an Aleo program function or deployment offline, without proving it.
+///
+/// The resulting authorization can be proven into a transaction by `snarkos developer prove`,
+/// on a machine that never sees the private key.
+#[derive(Debug, Parser)]
+pub struct Authorize {
+    /// The program identifier.
+    program_id: ProgramID<CurrentNetwork>,
+    /// The function name.
+    #[clap(required_unless_present = "deploy")]
+    function: Option<Identifier<CurrentNetwork>>,
+    /// The function inputs.
+    inputs: Vec<Value<CurrentNetwork>>,
+    /// The private key used to authorize the execution or deployment.
+    #[clap(short, long)]
+    private_key: String,
+    /// The paths to the program and its imports, if it is not `credits.aleo`.
+    #[clap(long = "program")]
+    programs: Vec<PathBuf>,
+    /// Authorizes the deployment of the package in the given directory, instead of an execution.
+    #[clap(long, conflicts_with_all = ["function", "inputs", "programs"])]
+    deploy: Option<String>,
+    /// The fee in microcredits, including the priority fee.
+    /// For executions, this must also cover the cost of the proof, as it is not proven yet.
+    /// For deployments, this defaults to the deployment cost.
+    #[clap(short, long)]
+    fee: Option<u64>,
+    /// The record to spend the fee from.
+    #[clap(short, long)]
+    record: Option<String>,
+    /// The path to store the authorization in. Defaults to printing the authorization.
+    #[clap(short, long)]
+    output: Option<PathBuf>,
+}
+
+/// A serialized authorization for an execution or a deployment, and its fee.
+///
+/// Note: The requests contain the function inputs in plaintext, which are revealed to the prover.
+#[derive(Debug, Serialize, Deserialize)]
+pub(super) struct AuthorizationFile {
+    /// The requests of the execution, if this authorizes an execution.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub(super) execution: Vec<Request<CurrentNetwork>>,
+    /// The deployment, if this authorizes a deployment.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub(super) deployment: Option<AuthorizedDeployment>,
+    /// The request of the fee, if the transaction requires one.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub(super) fee: Option<Request<CurrentNetwork>>,
+}
+
+/// A deployment, signed by its owner.
+#[derive(Debug, Serialize, Deserialize)]
+pub(super) struct AuthorizedDeployment {
+    /// The program owner, which signs the deployment ID.
+    pub(super) owner: ProgramOwner<CurrentNetwork>,
+    /// The deployment.
+    pub(super) deployment: Deployment<CurrentNetwork>,
+}
+
+impl AuthorizationFile {
+    /// Returns the authorization for the execution.
+    pub(super) fn execution(&self) -> Authorization<CurrentNetwork> {
+        Authorization::from(self.execution.clone())
+    }
+
+    /// Returns the authorization for the fee, if one exists.
+    pub(super) fn fee(&self) -> Option<Authorization<CurrentNetwork>> {
+        self.fee.as_ref().map(Authorization::from)
+    }
+
+    /// Returns the locator of the main function of the execution.
+    pub(super) fn locator(&self) -> Result<Locator<CurrentNetwork>> {
+        match self.execution.first() {
+            Some(request) => Ok(Locator::new(*request.program_id(), *request.function_name())),
+            None => bail!("The authorization does not contain any requests"),
+        }
+    }
+
+    /// Returns the name of the authorized operation, i.e. the deployed program or the executed function.
+    pub(super) fn operation(&self) -> Result<String> {
+        match (&self.deployment, self.execution.is_empty()) {
+            (Some(deployment), true) => Ok(deployment.deployment.program_id().to_string()),
+            (None, false) => Ok(self.locator()?.to_string()),
+            (Some(_), false) => bail!("The authorization contains both a deployment and an execution"),
+            (None, true) => bail!("The authorization does not contain a deployment or an execution"),
+        }
+    }
+}
+
+impl Authorize {
+    /// Authorizes an Aleo program function or deployment with the provided inputs.
+    #[allow(clippy::format_in_format_args)]
+    pub fn parse(self) -> Result<String> {
+        // Retrieve the private key.
+        let private_key = PrivateKey::from_str(&self.private_key)?;
+
+        let operation = match &self.function {
+            Some(function) => format!("{}/{function}", self.program_id),
+            None => self.program_id.to_string(),
+        };
+        println!("🔑 Authorizing '{}'...\n", &operation.bold());
+
+        // Generate the authorization.
+        let authorization = {
+            // Initialize an RNG.
+            let rng = &mut rand::thread_rng();
+
+            // Initialize the VM.
+            let store = ConsensusStore::<CurrentNetwork, ConsensusMemory<CurrentNetwork>>::open(None)?;
+            let vm = VM::from(store)?;
+
+            match self.function {
+                Some(function) => self.authorize_execution(&vm, &private_key, function, rng)?,
+                None => self.authorize_deployment(&vm, &private_key, rng)?,
+            }
+        };
+        println!("✅ Authorized '{}'\n", operation.bold());
+
+        // Determine if the authorization should be stored or displayed to user.
+        let authorization = serde_json::to_string_pretty(&authorization)?;
+        match self.output {
+            Some(path) => {
+                std::fs::write(&path, authorization)?;
+                Ok(format!("The authorization was stored to {}", path.display()))
+            }
+            None => Ok(authorization),
+        }
+    }
+
+    /// Authorizes the execution of the given function, and its fee.
+    fn authorize_execution<R: Rng + CryptoRng>(
+        &self,
+        vm: &VM<CurrentNetwork, ConsensusMemory<CurrentNetwork>>,
+        private_key: &PrivateKey<CurrentNetwork>,
+        function: Identifier<CurrentNetwork>,
+        rng: &mut R,
+    ) -> Result<AuthorizationFile> {
+        let locator = Locator::new(self.program_id, function);
+
+        // Load the program and its imports into the process.
+        load_programs(&mut vm.process().write(), &self.programs)?;
+        ensure!(
+            vm.process().read().contains_program(&self.program_id),
+            "The program '{}' was not found, specify it with '--program'",
+            self.program_id
+        );
+
+        // Authorize the execution.
+        let authorization = vm.authorize(private_key, self.program_id, function, self.inputs.iter(), rng)?;
+
+        // Authorize the fee, if one is required or specified.
+        let fee = match (authorization.is_split(), self.fee) {
+            (true, None) => None,
+            (_, fee) => {
+                // Run the function without proving it, to determine the execution ID and cost.
+                let (_, trace) = vm.process().read().execute::<CurrentAleo>(authorization.replicate())?;
+                let execution = Execution::from(trace.transitions().iter().cloned(), Default::default(), None)?;
+                let fee = match fee {
+                    Some(fee) => fee,
+                    None => {
+                        let (minimum_cost, _) = execution_cost(vm, &execution)?;
+                        bail!(
+                            "'{locator}' requires a fee of at least {minimum_cost} microcredits, plus the cost of the proof. Specify it with '--fee'"
+                        )
+                    }
+                };
+                Some(self.authorize_fee(vm, private_key, fee, execution.to_execution_id()?, rng)?)
+            }
+        };
+
+        Ok(AuthorizationFile { execution: authorization.to_vec_deque().into(), deployment: None, fee })
+    }
+
+    /// Authorizes the deployment of the package, and its fee.
+    fn authorize_deployment<R: Rng + CryptoRng>(
+        &self,
+        vm: &VM<CurrentNetwork, ConsensusMemory<CurrentNetwork>>,
+        private_key: &PrivateKey<CurrentNetwork>,
+        rng: &mut R,
+    ) -> Result<AuthorizationFile> {
+        // Fetch the package from the directory.
+        let package = Developer::parse_package(self.program_id, self.deploy.clone())?;
+
+        // Generate the deployment, which synthesizes the verifying keys without proving anything.
+        let deployment = package.deploy::<CurrentAleo>(None)?;
+        let deployment_id = deployment.to_deployment_id()?;
+
+        // Sign the deployment as its owner.
+        let owner = ProgramOwner::new(private_key, deployment_id, rng)?;
+
+        // Ensure the fee covers the deployment cost, which is known without proving the fee.
+        let (minimum_cost, _) = deployment_cost(&deployment)?;
+        let fee = self.fee.unwrap_or(minimum_cost);
+        ensure!(
+            fee >= minimum_cost,
+            "The fee ({fee} microcredits) is less than the deployment cost ({minimum_cost} microcredits)"
+        );
+
+        // Authorize the fee.
+        let fee = self.authorize_fee(vm, private_key, fee, deployment_id, rng)?;
+
+        Ok(AuthorizationFile {
+            execution: vec![],
+            deployment: Some(AuthorizedDeployment { owner, deployment }),
+            fee: Some(fee),
+        })
+    }
+
+    /// Authorizes a fee for the given execution or deployment ID, from the fee record if one is specified.
+    fn authorize_fee<R: Rng + CryptoRng>(
+        &self,
+        vm: &VM<CurrentNetwork, ConsensusMemory<CurrentNetwork>>,
+        private_key: &PrivateKey<CurrentNetwork>,
+        fee: u64,
+        id: Field<CurrentNetwork>,
+        rng: &mut R,
+    ) -> Result<Request<CurrentNetwork>> {
+        let authorization = match &self.record {
+            Some(record) => {
+                let record = Developer::parse_record(private_key, record)?;
+                vm.authorize_fee_private(private_key, record, fee, id, rng)?
+            }
+            None => vm.authorize_fee_public(private_key, fee, id, rng)?,
+        };
+        authorization.peek_next()
+    }
+}
+
+/// A helper function to load the programs from the given paths into the process, imports first.
+fn load_programs(process: &mut Process<CurrentNetwork>, paths: &[PathBuf]) -> Result<()> {
+    // Read the programs.
+    let mut programs =
+        paths.iter().map(|path| Program::from_str(&std::fs::read_to_string(path)?)).collect::<Result<Vec<_>>>()?;
+
+    // Add the programs whose imports are already loaded, until all of the programs are added.
+    while !programs.is_empty() {
+        let num_programs = programs.len();
+        for program in std::mem::take(&mut programs) {
+            if process.contains_program(program.id()) {
+                continue;
+            }
+            match program.imports().keys().all(|import| process.contains_program(import)) {
+                true => process.add_program(&program)?,
+                false => programs.push(program),
+            }
+        }
+        // Ensure progress was made, otherwise an import is missing.
+        if programs.len() == num_programs {
+            let missing = programs.iter().map(|program| program.id().to_string()).collect::<Vec<_>>().join(", ");
+            bail!("The imports of {missing} were not found, specify them with '--program'");
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::commands::{Command, CLI};
+
+    #[test]
+    fn clap_snarkos_authorize() {
+        let arg_vec = vec![
+            "snarkos",
+            "developer",
+            "authorize",
+            "--private-key",
+            "PRIVATE_KEY",
+            "--program",
+            "hello.aleo",
+            "--program",
+            "imports/token.aleo",
+            "--fee",
+            "77",
+            "--record",
+            "RECORD",
+            "--output",
+            "authorization.json",
+            "hello.aleo",
+            "hello",
+            "1u32",
+            "2u32",
+        ];
+        let cli = CLI::parse_from(arg_vec);
+
+        if let Command::Developer(Developer::Authorize(authorize)) = cli.command {
+            assert_eq!(authorize.private_key, "PRIVATE_KEY");
+            assert_eq!(authorize.programs, vec![PathBuf::from("hello.aleo"), PathBuf::from("imports/token.aleo")]);
+            assert_eq!(authorize.fee, Some(77));
+            assert_eq!(authorize.record, Some("RECORD".into()));
+            assert_eq!(authorize.output, Some(PathBuf::from("authorization.json")));
+            assert_eq!(authorize.program_id, "hello.aleo".try_into().unwrap());
+            assert_eq!(authorize.function, Some("hello".try_into().unwrap()));
+            assert_eq!(authorize.inputs, vec!["1u32".try_into().unwrap(), "2u32".try_into().unwrap()]);
+            assert_eq!(authorize.deploy, None);
+        } else {
+            panic!("Unexpected result of clap parsing!");
+        }
+    }
+
+    #[test]
+    fn clap_snarkos_authorize_deploy() {
+        let arg_vec =
+            vec!["snarkos", "developer", "authorize", "--private-key", "PRIVATE_KEY", "--deploy", "PATH", "hello.aleo"];
+        let cli = CLI::parse_from(arg_vec);
+
+        if let Command::Developer(Developer::Authorize(authorize)) = cli.command {
+            assert_eq!(authorize.deploy, Some("PATH".into()));
+            assert_eq!(authorize.program_id, "hello.aleo".try_into().unwrap());
+            assert_eq!(authorize.function, None);
+            assert_eq!(authorize.fee, None);
+        } else {
+            panic!("Unexpected result of clap parsing!");
+        }
+
+        // Ensure a function cannot be authorized along with a deployment.
+        let arg_vec = vec![
+            "snarkos",
+            "developer",
+            "authorize",
+            "--private-key",
+            "PRIVATE_KEY",
+            "--deploy",
+            "PATH",
+            "hello.aleo",
+            "hello",
+        ];
+        assert!(CLI::try_parse_from(arg_vec).is_err());
+
+        // Ensure a function is required without a deployment.
+        let arg_vec = vec!["snarkos", "developer", "authorize", "--private-key", "PRIVATE_KEY", "hello.aleo"];
+        assert!(CLI::try_parse_from(arg_vec).is_err());
+    }
+
+    #[test]
+    fn test_authorization_file() {
+        let rng = &mut rand::thread_rng();
+
+        // Initialize the VM.
+        let store = ConsensusStore::<CurrentNetwork, ConsensusMemory<CurrentNetwork>>::open(None).unwrap();
+        let vm = VM::from(store).unwrap();
+
+        // Authorize a public transfer.
+        let private_key = PrivateKey::<CurrentNetwork>::new(rng).unwrap();
+        let recipient = snarkvm::prelude::Address::try_from(private_key).unwrap();
+        let inputs =
+            [Value::<CurrentNetwork>::from_str(&recipient.to_string()).unwrap(), Value::from_str("1u64").unwrap()];
+        let authorization = vm.authorize(&private_key, "credits.aleo", "transfer_public", inputs.iter(), rng).unwrap();
+
+        // Ensure the authorization file round-trips.
+        let file = AuthorizationFile { execution: authorization.to_vec_deque().into(), deployment: None, fee: None };
+        let file = serde_json::from_str::<AuthorizationFile>(&serde_json::to_string(&file).unwrap()).unwrap();
+        assert_eq!(file.locator().unwrap().to_string(), "credits.aleo/transfer_public");
+        assert_eq!(file.operation().unwrap(), "credits.aleo/transfer_public");
+        assert_eq!(file.execution().to_vec_deque(), authorization.to_vec_deque());
+        assert!(file.deployment.is_none());
+        assert!(file.fee().is_none());
+    }
+
+    #[test]
+    fn test_deployment_authorization_file() {
+        let rng = &mut rand::thread_rng();
+
+        // Initialize the VM.
+        let store = ConsensusStore::<CurrentNetwork, ConsensusMemory<CurrentNetwork>>::open(None).unwrap();
+        let vm = VM::from(store).unwrap();
+
+        // Deploy a program.
+        let program = Program::<CurrentNetwork>::from_str(
+            r"
+program authorize_test.aleo;
+
+function hello:
+    input r0 as u32.public;
+    input r1 as u32.private;
+    add r0 r1 into r2;
+    output r2 as u32.private;",
+        )
+        .unwrap();
+        let deployment = vm.process().read().deploy::<CurrentAleo, _>(&program, rng).unwrap();
+        let deployment_id = deployment.to_deployment_id().unwrap();
+
+        // Authorize the deployment and its fee.
+        let private_key = PrivateKey::<CurrentNetwork>::new(rng).unwrap();
+        let owner = ProgramOwner::new(&private_key, deployment_id, rng).unwrap();
+        let (minimum_cost, _) = deployment_cost(&deployment).unwrap();
+        let fee = vm.authorize_fee_public(&private_key, minimum_cost, deployment_id, rng).unwrap();
+
+        // Ensure the authorization file round-trips.
+        let file = AuthorizationFile {
+            execution: vec![],
+            deployment: Some(AuthorizedDeployment { owner, deployment }),
+            fee: Some(fee.peek_next().unwrap()),
+        };
+        let file = serde_json::from_str::<AuthorizationFile>(&serde_json::to_string(&file).unwrap()).unwrap();
+        assert_eq!(file.operation().unwrap(), "authorize_test.aleo");
+        assert!(file.locator().is_err());
+        let deployment = file.deployment.as_ref().unwrap();
+        assert_eq!(deployment.deployment.to_deployment_id().unwrap(), deployment_id);
+        assert!(deployment.owner.verify(deployment_id));
+        assert_eq!(file.fee().unwrap().to_vec_deque(), fee.to_vec_deque());
+    }
+}
@@ -0,0 +1,80 @@
+// Copyright (C) 2019-2023 Aleo Systems Inc.
+// This file is part of the snarkOS library.
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at:
+// http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use super::{CurrentNetwork, Developer};
+
+use snarkvm::prelude::{block::Transaction, FromBytes};
+
+use anyhow::{bail, Result};
+use clap::Parser;
+use std::path::{Path, PathBuf};
+
+/// Broadcasts a stored transaction.
+#[derive(Debug, Parser)]
+pub struct Broadcast {
+    /// The path to the transaction, stored by `--store`, or in JSON.
+    transaction: PathBuf,
+    /// The endpoint used to broadcast the transaction.
+    #[clap(short, long)]
+    endpoint: String,
+}
+
+impl Broadcast {
+    /// Broadcasts the transaction to the endpoint.
+    pub fn parse(self) -> Result<String> {
+        // Read the transaction.
+        let transaction = Self::read_transaction(&self.transaction)?;
+
+        // Determine the operation performed by the transaction.
+        let operation = match &transaction {
+            Transaction::Deploy(_, _, deployment, _) => deployment.program_id().to_string(),
+            Transaction::Execute(_, execution, _) => match execution.transitions().next() {
+                Some(transition) => format!("{}/{}", transition.program_id(), transition.function_name()),
+                None => bail!("The execution in transaction {} is empty", transaction.id()),
+            },
+            Transaction::Fee(..) => bail!("The transaction is a fee transaction and cannot be broadcast"),
+        };
+
+        // Broadcast the transaction.
+        Developer::handle_transaction(Some(self.endpoint), false, None, transaction, operation)
+    }
+
+    /// Reads the transaction from the given path, in bytes or in JSON.
+    fn read_transaction(path: &Path) -> Result<Transaction<CurrentNetwork>> {
+        let bytes = std::fs::read(path)?;
+        match bytes.first() {
+            Some(b'{') => Ok(serde_json::from_slice(&bytes)?),
+            _ => Transaction::read_le(&bytes[..]),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::commands::{Command, CLI};
+
+    #[test]
+    fn clap_snarkos_broadcast() {
+        let arg_vec = vec!["snarkos", "developer", "broadcast", "--endpoint", "ENDPOINT", "transaction.bin"];
+        let cli = CLI::parse_from(arg_vec);
+
+        if let Command::Developer(Developer::Broadcast(broadcast)) = cli.command {
+            assert_eq!(broadcast.transaction, PathBuf::from("transaction.bin"));
+            assert_eq!(broadcast.endpoint, "ENDPOINT");
+        } else {
+            panic!("Unexpected result of clap parsing!");
+        }
+    }
+}
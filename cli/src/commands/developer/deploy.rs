@@ -12,46 +12,64 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
-use super::{CurrentAleo, CurrentNetwork, Developer};
+use super::{load_program, CurrentAleo, CurrentNetwork, Developer, LedgerSnapshot};
 
+use snarkos_node_rest_client::{blocking::RestClient, FeeRequest};
 use snarkvm::{
     console::program::ProgramOwner,
+    package::Package,
     prelude::{
-        block::Transaction,
+        block::{Deployment, Output, Transaction},
         deployment_cost,
         query::Query,
-        store::{helpers::memory::ConsensusMemory, ConsensusStore},
+        store::{helpers::memory::ConsensusMemory, ConsensusStorage, ConsensusStore},
+        Plaintext,
         PrivateKey,
+        Program,
         ProgramID,
+        Record,
+        ViewKey,
         VM,
     },
 };
 
-use anyhow::{anyhow, bail, Result};
+use anyhow::{anyhow, bail, ensure, Result};
 use clap::Parser;
 use colored::Colorize;
-use std::str::FromStr;
+use indexmap::{IndexMap, IndexSet};
+use std::{
+    path::{Path, PathBuf},
+    str::FromStr,
+};
 
 /// Deploys an Aleo program.
 #[derive(Debug, Parser)]
 pub struct Deploy {
-    /// The name of the program to deploy.
-    program_id: ProgramID<CurrentNetwork>,
+    /// The name of the program to deploy. Optional with `--workspace`, to deploy the whole workspace.
+    #[clap(required_unless_present = "workspace")]
+    program_id: Option<ProgramID<CurrentNetwork>>,
     /// A path to a directory containing a manifest file. Defaults to the current working directory.
     #[clap(long)]
     path: Option<String>,
+    /// A path to a directory of packages. Deploys the program and the imports it finds in the packages,
+    /// or all of the packages if no program is specified, skipping those that are already deployed.
+    #[clap(long, conflicts_with_all = ["path", "store"], requires = "broadcast", requires = "query")]
+    workspace: Option<PathBuf>,
     /// The private key used to generate the deployment.
     #[clap(short, long)]
     private_key: String,
     /// The endpoint to query node state from.
-    #[clap(short, long)]
-    query: String,
+    #[clap(short, long, required_unless_present = "ledger")]
+    query: Option<String>,
+    /// The path to a local ledger to query node state from, such as `~/.aleo/storage/ledger-3`.
+    #[clap(long, conflicts_with = "query")]
+    ledger: Option<PathBuf>,
     /// The priority fee in microcredits.
-    #[clap(short, long)]
-    fee: u64,
+    #[clap(short, long, required_unless_present = "estimate")]
+    fee: Option<u64>,
     /// The record to spend the fee from.
-    #[clap(short, long)]
-    record: String,
+    #[clap(short, long, required_unless_present = "estimate")]
+    record: Option<String>,
     /// The endpoint used to broadcast the generated transaction.
     #[clap(short, long, conflicts_with = "dry_run")]
     broadcast: Option<String>,
@@ -61,69 +79,306 @@ pub struct Deploy {
     /// Store generated deployment transaction to a local file.
     #[clap(long)]
     store: Option<String>,
+    /// Waits for the broadcast transaction to be confirmed in a block.
+    #[clap(long, requires = "broadcast", requires = "query")]
+    wait: bool,
+    /// The number of seconds to wait for the transaction to be confirmed.
+    #[clap(long, default_value_t = 60, requires = "wait")]
+    timeout: u64,
+    /// Estimates the fee of the deployment, without creating a transaction.
+    #[clap(long, requires = "query", conflicts_with_all = ["broadcast", "dry_run", "store", "workspace"])]
+    estimate: bool,
 }
 
 impl Deploy {
     /// Deploys an Aleo program.
     pub fn parse(self) -> Result<String> {
         // Ensure that the user has specified an action.
-        if !self.dry_run && self.broadcast.is_none() && self.store.is_none() {
-            bail!("❌ Please specify one of the following actions: --broadcast, --dry-run, --store");
+        if !self.dry_run && self.broadcast.is_none() && self.store.is_none() && !self.estimate {
+            bail!("❌ Please specify one of the following actions: --broadcast, --dry-run, --store, --estimate");
         }
 
-        // Specify the query
-        let query = Query::from(&self.query);
+        // Deploy the program and its imports from the workspace, if one is specified.
+        if let Some(workspace) = &self.workspace {
+            return self.deploy_workspace(workspace);
+        }
+
+        // Retrieve the program ID.
+        let program_id = match self.program_id {
+            Some(program_id) => program_id,
+            None => bail!("❌ Please specify the program to deploy"),
+        };
 
         // Retrieve the private key.
         let private_key = PrivateKey::from_str(&self.private_key)?;
+        // Initialize the client of the query endpoint, which is shared by all of the requests.
+        let client = self.query.as_deref().map(RestClient::<CurrentNetwork>::new).transpose()?;
 
         // Fetch the package from the directory.
-        let package = Developer::parse_package(self.program_id, self.path)?;
+        let package = Developer::parse_package(program_id, self.path.clone())?;
+
+        // Estimate the fee of the deployment, if requested.
+        if let (true, Some(client)) = (self.estimate, &client) {
+            let deployment = package.deploy::<CurrentAleo>(None)?;
+            return Developer::estimate_fee(client, FeeRequest::Deployment(deployment));
+        }
 
-        println!("📦 Creating deployment transaction for '{}'...\n", &self.program_id.to_string().bold());
+        println!("📦 Creating deployment transaction for '{}'...\n", &program_id.to_string().bold());
 
         // Generate the deployment
         let deployment = package.deploy::<CurrentAleo>(None)?;
-        let deployment_id = deployment.to_deployment_id()?;
 
         // Generate the deployment transaction.
-        let transaction = {
-            // Initialize an RNG.
-            let rng = &mut rand::thread_rng();
-
-            // Initialize the VM.
-            let store = ConsensusStore::<CurrentNetwork, ConsensusMemory<CurrentNetwork>>::open(None)?;
-            let vm = VM::from(store)?;
-
-            // Compute the minimum deployment cost.
-            let (minimum_deployment_cost, (_, _)) = deployment_cost(&deployment)?;
-            // Determine the fee.
-            let fee_in_microcredits = minimum_deployment_cost
-                .checked_add(self.fee)
-                .ok_or_else(|| anyhow!("Fee overflowed for a deployment transaction"))?;
-
-            // Prepare the fees.
-            let fee_record = Developer::parse_record(&private_key, &self.record)?;
-            let fee_authorization =
-                vm.authorize_fee_private(&private_key, fee_record, fee_in_microcredits, deployment_id, rng)?;
-            let fee = vm.execute_fee_authorization(fee_authorization, Some(query), rng)?;
-
-            // Construct the owner.
-            let owner = ProgramOwner::new(&private_key, deployment_id, rng)?;
-
-            // Create a new transaction.
-            Transaction::from_deployment(owner, deployment, fee)?
+        let transaction = match (&self.ledger, &self.query) {
+            (Some(path), _) => {
+                // Open a snapshot of the ledger, which contains the deployed programs.
+                let ledger = LedgerSnapshot::open(path)?;
+                ensure!(
+                    ledger.fetch_program(&program_id).is_err(),
+                    "The program '{program_id}' is already deployed in the ledger"
+                );
+                for import_program_id in deployment.program().imports().keys() {
+                    ensure!(
+                        ledger.fetch_program(import_program_id).is_ok(),
+                        "The import '{import_program_id}' is not deployed in the ledger"
+                    );
+                }
+
+                // Create a new transaction, using the state of the ledger.
+                self.deploy(ledger.vm(), &private_key, deployment, None)?
+            }
+            (None, Some(query)) => {
+                // Initialize the VM.
+                let store = ConsensusStore::<CurrentNetwork, ConsensusMemory<CurrentNetwork>>::open(None)?;
+                let vm = VM::from(store)?;
+
+                // Create a new transaction, using the state of the query endpoint.
+                self.deploy(&vm, &private_key, deployment, Some(Query::from(query)))?
+            }
+            (None, None) => bail!("❌ Please specify one of the following sources: --query, --ledger"),
         };
-        println!("✅ Created deployment transaction for '{}'", self.program_id.to_string().bold());
+        println!("✅ Created deployment transaction for '{}'", program_id.to_string().bold());
 
         // Determine if the transaction should be broadcast, stored, or displayed to user.
-        Developer::handle_transaction(
+        let output = Developer::handle_transaction(
             self.broadcast,
             self.dry_run,
             self.store,
-            transaction,
-            self.program_id.to_string(),
-        )
+            transaction.clone(),
+            program_id.to_string(),
+        )?;
+
+        // Wait for the transaction to be confirmed, if requested.
+        if let (true, Some(client)) = (self.wait, &client) {
+            Developer::wait_for_transaction(client, &transaction, self.timeout)?;
+        }
+        Ok(output)
+    }
+
+    /// Creates the deployment transaction, paying the fee from the fee record.
+    fn deploy<C: ConsensusStorage<CurrentNetwork>>(
+        &self,
+        vm: &VM<CurrentNetwork, C>,
+        private_key: &PrivateKey<CurrentNetwork>,
+        deployment: Deployment<CurrentNetwork>,
+        query: Option<Query<CurrentNetwork, C::BlockStorage>>,
+    ) -> Result<Transaction<CurrentNetwork>> {
+        // Initialize an RNG.
+        let rng = &mut rand::thread_rng();
+
+        // Retrieve the priority fee and the fee record.
+        let (priority_fee, record) = self.fee_and_record()?;
+
+        // Compute the minimum deployment cost.
+        let deployment_id = deployment.to_deployment_id()?;
+        let (minimum_deployment_cost, (_, _)) = deployment_cost(&deployment)?;
+        // Determine the fee.
+        let fee_in_microcredits = minimum_deployment_cost
+            .checked_add(priority_fee)
+            .ok_or_else(|| anyhow!("Fee overflowed for a deployment transaction"))?;
+
+        // Prepare the fees.
+        let fee_record = Developer::parse_record(private_key, record)?;
+        let fee_authorization =
+            vm.authorize_fee_private(private_key, fee_record, fee_in_microcredits, deployment_id, rng)?;
+        let fee = vm.execute_fee_authorization(fee_authorization, query, rng)?;
+
+        // Construct the owner.
+        let owner = ProgramOwner::new(private_key, deployment_id, rng)?;
+
+        // Create a new transaction.
+        Transaction::from_deployment(owner, deployment, fee)
+    }
+
+    /// Deploys the program and its imports, or all of the programs, from the workspace, in dependency order.
+    /// Each deployment is confirmed before its dependents are deployed.
+    fn deploy_workspace(&self, workspace: &Path) -> Result<String> {
+        // Retrieve the private key and view key.
+        let private_key = PrivateKey::from_str(&self.private_key)?;
+        let view_key = ViewKey::try_from(&private_key)?;
+
+        // Retrieve the broadcast and query endpoints.
+        let broadcast = match &self.broadcast {
+            Some(broadcast) => broadcast,
+            None => bail!("❌ Please specify the --broadcast endpoint to deploy a workspace"),
+        };
+        let query = match &self.query {
+            Some(query) => query,
+            None => bail!("❌ Please specify the --query endpoint to deploy a workspace"),
+        };
+        // Initialize the client of the query endpoint, which is shared by all of the requests.
+        let client = RestClient::<CurrentNetwork>::new(query)?;
+
+        // Load the programs in the workspace, and determine the order to deploy them in.
+        let programs = Self::load_workspace(workspace)?;
+        let program_ids = match self.program_id {
+            Some(program_id) => vec![program_id],
+            None => programs.keys().copied().collect(),
+        };
+        let order = Self::deployment_order(&program_ids, &programs)?;
+
+        // Initialize an RNG.
+        let rng = &mut rand::thread_rng();
+
+        // Initialize the VM.
+        let store = ConsensusStore::<CurrentNetwork, ConsensusMemory<CurrentNetwork>>::open(None)?;
+        let vm = VM::from(store)?;
+
+        // Skip the programs that are already deployed, before deploying any of them.
+        let mut pending = Vec::new();
+        for program_id in order {
+            match client.find_program(&program_id)? {
+                Some(deployed_program) => {
+                    ensure!(
+                        deployed_program == programs[&program_id],
+                        "❌ '{program_id}' is already deployed with different bytecode"
+                    );
+                    println!("✅ '{}' is already deployed, skipping", program_id.to_string().bold());
+                    load_program(&client, &mut vm.process().write(), &program_id)?;
+                }
+                None => pending.push(program_id),
+            }
+        }
+
+        // Prepare the fee record, which is replaced by its change after each deployment.
+        let (priority_fee, record) = self.fee_and_record()?;
+        let mut fee_record = Developer::parse_record(&private_key, record)?;
+
+        let mut transaction_ids = Vec::new();
+        for program_id in pending {
+            let program = &programs[&program_id];
+
+            // Load the imports that are not in the workspace, which must already be deployed.
+            for import_program_id in program.imports().keys() {
+                if !vm.process().read().contains_program(import_program_id) {
+                    load_program(&client, &mut vm.process().write(), import_program_id)?;
+                }
+            }
+
+            println!("📦 Creating deployment transaction for '{}'...\n", program_id.to_string().bold());
+
+            // Generate the deployment transaction.
+            let transaction =
+                vm.deploy(&private_key, program, Some(fee_record), priority_fee, Some(Query::from(query)), rng)?;
+            println!("✅ Created deployment transaction for '{}'", program_id.to_string().bold());
+
+            // Retrieve the change of the fee, to pay for the next deployment.
+            fee_record = Self::fee_change(&transaction, &view_key)?;
+
+            // Broadcast the transaction, and wait for it to be confirmed before deploying its dependents.
+            transaction_ids.push(Developer::handle_transaction(
+                Some(broadcast.clone()),
+                false,
+                None,
+                transaction.clone(),
+                program_id.to_string(),
+            )?);
+            Developer::wait_for_transaction(&client, &transaction, self.timeout)?;
+
+            // Add the program to the process, for its dependents.
+            vm.process().write().add_program(program)?;
+        }
+
+        Ok(transaction_ids.join("\n"))
+    }
+
+    /// Returns the priority fee and the fee record, which are required unless estimating the fee.
+    fn fee_and_record(&self) -> Result<(u64, &str)> {
+        match (self.fee, &self.record) {
+            (Some(fee), Some(record)) => Ok((fee, record)),
+            _ => bail!("❌ Please specify the --fee and the --record to pay for the deployment"),
+        }
+    }
+
+    /// Returns the programs of the packages in the given workspace directory.
+    fn load_workspace(workspace: &Path) -> Result<IndexMap<ProgramID<CurrentNetwork>, Program<CurrentNetwork>>> {
+        let mut programs = IndexMap::new();
+        for entry in std::fs::read_dir(workspace)? {
+            let path = entry?.path();
+            // Skip the entries that are not packages.
+            if !path.join("program.json").is_file() {
+                continue;
+            }
+            let package = Package::<CurrentNetwork>::open(&path)?;
+            if let Some(program) = programs.insert(*package.program_id(), package.program().clone()) {
+                bail!("The program '{}' is defined by multiple packages in the workspace", program.id());
+            }
+        }
+        Ok(programs)
+    }
+
+    /// Returns the order to deploy the given programs and their imports from the workspace in, imports first.
+    /// Imports that are not in the workspace are expected to be deployed already.
+    fn deployment_order(
+        program_ids: &[ProgramID<CurrentNetwork>],
+        programs: &IndexMap<ProgramID<CurrentNetwork>, Program<CurrentNetwork>>,
+    ) -> Result<Vec<ProgramID<CurrentNetwork>>> {
+        /// Appends the imports of the given program to the order, followed by the program itself.
+        fn visit(
+            program_id: &ProgramID<CurrentNetwork>,
+            programs: &IndexMap<ProgramID<CurrentNetwork>, Program<CurrentNetwork>>,
+            visiting: &mut IndexSet<ProgramID<CurrentNetwork>>,
+            order: &mut IndexSet<ProgramID<CurrentNetwork>>,
+        ) -> Result<()> {
+            // Skip the program if it was already visited, or if it is not in the workspace.
+            let program = match programs.get(program_id) {
+                Some(program) if !order.contains(program_id) => program,
+                _ => return Ok(()),
+            };
+            // Ensure the imports do not form a cycle.
+            if !visiting.insert(*program_id) {
+                let cycle = visiting.iter().map(ToString::to_string).collect::<Vec<_>>().join(" -> ");
+                bail!("The imports form a cycle: {cycle} -> {program_id}");
+            }
+            for import_program_id in program.imports().keys() {
+                visit(import_program_id, programs, visiting, order)?;
+            }
+            visiting.pop();
+            order.insert(*program_id);
+            Ok(())
+        }
+
+        let mut order = IndexSet::new();
+        for program_id in program_ids {
+            ensure!(programs.contains_key(program_id), "The program '{program_id}' was not found in the workspace");
+            visit(program_id, programs, &mut IndexSet::new(), &mut order)?;
+        }
+        Ok(order.into_iter().collect())
+    }
+
+    /// Returns the change record of the private fee in the given transaction.
+    fn fee_change(
+        transaction: &Transaction<CurrentNetwork>,
+        view_key: &ViewKey<CurrentNetwork>,
+    ) -> Result<Record<CurrentNetwork, Plaintext<CurrentNetwork>>> {
+        let fee = transaction.fee_transition().ok_or_else(|| anyhow!("The transaction does not contain a fee"))?;
+        match fee.transition().outputs().iter().find_map(|output| match output {
+            Output::Record(_, _, Some(record)) => Some(record),
+            _ => None,
+        }) {
+            Some(record) => record.decrypt(view_key),
+            None => bail!("The fee of transaction {} does not contain a change record", transaction.id()),
+        }
     }
 }
 
@@ -151,13 +406,192 @@ mod tests {
         let cli = CLI::parse_from(arg_vec);
 
         if let Command::Developer(Developer::Deploy(deploy)) = cli.command {
-            assert_eq!(deploy.program_id, "hello.aleo".try_into().unwrap());
+            assert_eq!(deploy.program_id, Some("hello.aleo".try_into().unwrap()));
             assert_eq!(deploy.private_key, "PRIVATE_KEY");
-            assert_eq!(deploy.query, "QUERY");
-            assert_eq!(deploy.fee, 77);
-            assert_eq!(deploy.record, "RECORD");
+            assert_eq!(deploy.query, Some("QUERY".into()));
+            assert_eq!(deploy.fee, Some(77));
+            assert_eq!(deploy.record, Some("RECORD".into()));
+            assert_eq!(deploy.workspace, None);
+        } else {
+            panic!("Unexpected result of clap parsing!");
+        }
+    }
+
+    #[test]
+    fn clap_snarkos_deploy_workspace() {
+        let arg_vec = vec![
+            "snarkos",
+            "developer",
+            "deploy",
+            "--private-key",
+            "PRIVATE_KEY",
+            "--query",
+            "QUERY",
+            "--fee",
+            "77",
+            "--record",
+            "RECORD",
+            "--workspace",
+            "WORKSPACE",
+            "--broadcast",
+            "BROADCAST",
+            "hello.aleo",
+        ];
+        let cli = CLI::parse_from(arg_vec);
+
+        if let Command::Developer(Developer::Deploy(deploy)) = cli.command {
+            assert_eq!(deploy.workspace, Some(PathBuf::from("WORKSPACE")));
+            assert_eq!(deploy.broadcast, Some("BROADCAST".into()));
         } else {
             panic!("Unexpected result of clap parsing!");
         }
+
+        // The program is optional with a workspace, to deploy the whole workspace.
+        let arg_vec = vec![
+            "snarkos",
+            "developer",
+            "deploy",
+            "--private-key",
+            "PRIVATE_KEY",
+            "--query",
+            "QUERY",
+            "--fee",
+            "77",
+            "--record",
+            "RECORD",
+            "--workspace",
+            "WORKSPACE",
+            "--broadcast",
+            "BROADCAST",
+        ];
+        let cli = CLI::parse_from(arg_vec);
+
+        if let Command::Developer(Developer::Deploy(deploy)) = cli.command {
+            assert_eq!(deploy.program_id, None);
+            assert_eq!(deploy.workspace, Some(PathBuf::from("WORKSPACE")));
+        } else {
+            panic!("Unexpected result of clap parsing!");
+        }
+    }
+
+    #[test]
+    fn clap_snarkos_deploy_estimate() {
+        let arg_vec = vec![
+            "snarkos",
+            "developer",
+            "deploy",
+            "--private-key",
+            "PRIVATE_KEY",
+            "--query",
+            "QUERY",
+            "--estimate",
+            "hello.aleo",
+        ];
+        let cli = CLI::parse_from(arg_vec);
+
+        if let Command::Developer(Developer::Deploy(deploy)) = cli.command {
+            assert!(deploy.estimate);
+            assert_eq!(deploy.fee, None);
+            assert_eq!(deploy.record, None);
+        } else {
+            panic!("Unexpected result of clap parsing!");
+        }
+
+        // The fee and the record are required, unless estimating the fee.
+        let arg_vec = vec![
+            "snarkos",
+            "developer",
+            "deploy",
+            "--private-key",
+            "PRIVATE_KEY",
+            "--query",
+            "QUERY",
+            "--dry-run",
+            "hello.aleo",
+        ];
+        assert!(CLI::try_parse_from(arg_vec).is_err());
+    }
+
+    #[test]
+    fn clap_snarkos_deploy_ledger() {
+        let arg_vec = vec![
+            "snarkos",
+            "developer",
+            "deploy",
+            "--private-key",
+            "PRIVATE_KEY",
+            "--ledger",
+            "LEDGER",
+            "--fee",
+            "77",
+            "--record",
+            "RECORD",
+            "--store",
+            "STORE",
+            "hello.aleo",
+        ];
+        let cli = CLI::parse_from(arg_vec);
+
+        if let Command::Developer(Developer::Deploy(deploy)) = cli.command {
+            assert_eq!(deploy.query, None);
+            assert_eq!(deploy.ledger, Some(PathBuf::from("LEDGER")));
+        } else {
+            panic!("Unexpected result of clap parsing!");
+        }
+
+        // Waiting for the deployment requires a query endpoint.
+        let arg_vec = vec![
+            "snarkos",
+            "developer",
+            "deploy",
+            "--private-key",
+            "PRIVATE_KEY",
+            "--ledger",
+            "LEDGER",
+            "--fee",
+            "77",
+            "--record",
+            "RECORD",
+            "--broadcast",
+            "BROADCAST",
+            "--wait",
+            "hello.aleo",
+        ];
+        assert!(CLI::try_parse_from(arg_vec).is_err());
+    }
+
+    #[test]
+    fn test_deployment_order() {
+        let program = |source: &str| {
+            let program = Program::<CurrentNetwork>::from_str(source).unwrap();
+            (*program.id(), program)
+        };
+
+        // Initialize a workspace, where `c.aleo` imports `a.aleo` and `b.aleo`, and `b.aleo` imports `a.aleo`.
+        let function = "function foo:\n    input r0 as u8.private;\n    output r0 as u8.private;\n";
+        let programs = IndexMap::from([
+            program(&format!("import a.aleo;\nimport b.aleo;\nprogram c.aleo;\n\n{function}")),
+            program(&format!("import a.aleo;\nprogram b.aleo;\n\n{function}")),
+            program(&format!("program a.aleo;\n\n{function}")),
+        ]);
+
+        let order = Deploy::deployment_order(&["c.aleo".try_into().unwrap()], &programs).unwrap();
+        assert_eq!(order.iter().map(ToString::to_string).collect::<Vec<_>>(), ["a.aleo", "b.aleo", "c.aleo"]);
+
+        let order = Deploy::deployment_order(&["b.aleo".try_into().unwrap()], &programs).unwrap();
+        assert_eq!(order.iter().map(ToString::to_string).collect::<Vec<_>>(), ["a.aleo", "b.aleo"]);
+
+        // The whole workspace is deployed in dependency order.
+        let program_ids = programs.keys().copied().collect::<Vec<_>>();
+        let order = Deploy::deployment_order(&program_ids, &programs).unwrap();
+        assert_eq!(order.iter().map(ToString::to_string).collect::<Vec<_>>(), ["a.aleo", "b.aleo", "c.aleo"]);
+
+        // Imports outside of the workspace are skipped.
+        let programs = IndexMap::from([program(&format!("import token.aleo;\nprogram d.aleo;\n\n{function}"))]);
+        let order = Deploy::deployment_order(&["d.aleo".try_into().unwrap()], &programs).unwrap();
+        assert_eq!(order.iter().map(ToString::to_string).collect::<Vec<_>>(), ["d.aleo"]);
+
+        // The program must be in the workspace.
+        assert!(Deploy::deployment_order(&["e.aleo".try_into().unwrap()], &programs).is_err());
     }
 }
@@ -12,11 +12,13 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
-use super::{CurrentNetwork, Developer};
+use super::{CurrentAleo, CurrentNetwork, Developer, LedgerSnapshot};
+use snarkos_node_rest_client::{blocking::RestClient, FeeRequest};
 
 use snarkvm::prelude::{
+    block::{Execution, Transaction},
     query::Query,
-    store::{helpers::memory::ConsensusMemory, ConsensusStore},
+    store::{helpers::memory::ConsensusMemory, ConsensusStorage, ConsensusStore},
     Identifier,
     Locator,
     PrivateKey,
@@ -26,10 +28,10 @@ use snarkvm::prelude::{
     VM,
 };
 
-use anyhow::{bail, Result};
+use anyhow::{bail, ensure, Result};
 use clap::Parser;
 use colored::Colorize;
-use std::str::FromStr;
+use std::{path::PathBuf, str::FromStr};
 
 /// Executes an Aleo program function.
 #[derive(Debug, Parser)]
@@ -44,8 +46,11 @@ pub struct Execute {
     #[clap(short, long)]
     private_key: String,
     /// The endpoint to query node state from.
-    #[clap(short, long)]
-    query: String,
+    #[clap(short, long, required_unless_present = "ledger")]
+    query: Option<String>,
+    /// The path to a local ledger to query node state from, such as `~/.aleo/storage/ledger-3`.
+    #[clap(long, conflicts_with = "query")]
+    ledger: Option<PathBuf>,
     /// The priority fee in microcredits.
     #[clap(short, long)]
     fee: Option<u64>,
@@ -61,6 +66,16 @@ pub struct Execute {
     /// Store generated deployment transaction to a local file.
     #[clap(long)]
     store: Option<String>,
+    /// Waits for the broadcast transaction to be confirmed in a block.
+    #[clap(long, requires = "broadcast", requires = "query")]
+    wait: bool,
+    /// The number of seconds to wait for the transaction to be confirmed.
+    #[clap(long, default_value_t = 60, requires = "wait")]
+    timeout: u64,
+    /// Estimates the fee of the execution, without creating a transaction.
+    /// Note: The execution is run locally without proving it, and only its transitions are sent to the query endpoint.
+    #[clap(long, requires = "query", conflicts_with_all = ["broadcast", "dry_run", "store"])]
+    estimate: bool,
 }
 
 impl Execute {
@@ -68,64 +83,133 @@ impl Execute {
     #[allow(clippy::format_in_format_args)]
     pub fn parse(self) -> Result<String> {
         // Ensure that the user has specified an action.
-        if !self.dry_run && self.broadcast.is_none() && self.store.is_none() {
-            bail!("❌ Please specify one of the following actions: --broadcast, --dry-run, --store");
+        if !self.dry_run && self.broadcast.is_none() && self.store.is_none() && !self.estimate {
+            bail!("❌ Please specify one of the following actions: --broadcast, --dry-run, --store, --estimate");
         }
 
-        // Specify the query
-        let query = Query::from(&self.query);
-
         // Retrieve the private key.
         let private_key = PrivateKey::from_str(&self.private_key)?;
+        // Initialize the client of the query endpoint, which is shared by all of the requests.
+        let client = self.query.as_deref().map(RestClient::<CurrentNetwork>::new).transpose()?;
+
+        // Estimate the fee of the execution, if requested.
+        if let (true, Some(client)) = (self.estimate, &client) {
+            return self.estimate_fee(&private_key, client);
+        }
 
         let locator = Locator::<CurrentNetwork>::from_str(&format!("{}/{}", self.program_id, self.function))?;
         println!("📦 Creating execution transaction for '{}'...\n", &locator.to_string().bold());
 
         // Generate the execution transaction.
-        let transaction = {
-            // Initialize an RNG.
-            let rng = &mut rand::thread_rng();
-
-            // Initialize the VM.
-            let store = ConsensusStore::<CurrentNetwork, ConsensusMemory<CurrentNetwork>>::open(None)?;
-            let vm = VM::from(store)?;
-
-            // Load the program and it's imports into the process.
-            load_program(&self.query, &mut vm.process().write(), &self.program_id)?;
-
-            // Prepare the fee.
-            let fee_record = match self.record {
-                Some(record_string) => Some(Developer::parse_record(&private_key, &record_string)?),
-                None => None,
-            };
-            let priority_fee = self.fee.unwrap_or(0);
-
-            // Create a new transaction.
-            vm.execute(
-                &private_key,
-                (self.program_id, self.function),
-                self.inputs.iter(),
-                fee_record,
-                priority_fee,
-                Some(query),
-                rng,
-            )?
+        let transaction = match (&self.ledger, &self.query, &client) {
+            (Some(path), ..) => {
+                // Open a snapshot of the ledger, which contains the deployed programs.
+                let ledger = LedgerSnapshot::open(path)?;
+                ensure!(
+                    ledger.vm().process().read().contains_program(&self.program_id),
+                    "The program '{}' does not exist in the ledger",
+                    self.program_id
+                );
+
+                // Create a new transaction, using the state of the ledger.
+                self.execute(ledger.vm(), &private_key, None)?
+            }
+            (None, Some(query), Some(client)) => {
+                // Initialize the VM.
+                let store = ConsensusStore::<CurrentNetwork, ConsensusMemory<CurrentNetwork>>::open(None)?;
+                let vm = VM::from(store)?;
+
+                // Load the program and it's imports into the process.
+                load_program(client, &mut vm.process().write(), &self.program_id)?;
+
+                // Create a new transaction, using the state of the query endpoint.
+                self.execute(&vm, &private_key, Some(Query::from(query)))?
+            }
+            (None, ..) => bail!("❌ Please specify one of the following sources: --query, --ledger"),
         };
         println!("✅ Created execution transaction for '{}'", locator.to_string().bold());
 
         // Determine if the transaction should be broadcast, stored, or displayed to user.
-        Developer::handle_transaction(self.broadcast, self.dry_run, self.store, transaction, locator.to_string())
+        let output = Developer::handle_transaction(
+            self.broadcast,
+            self.dry_run,
+            self.store,
+            transaction.clone(),
+            locator.to_string(),
+        )?;
+
+        // Wait for the transaction to be confirmed, if requested.
+        if let (true, Some(client)) = (self.wait, &client) {
+            Developer::wait_for_transaction(client, &transaction, self.timeout)?;
+        }
+        Ok(output)
+    }
+
+    /// Estimates the fee of the execution, by running it without a proof or a fee.
+    fn estimate_fee(
+        &self,
+        private_key: &PrivateKey<CurrentNetwork>,
+        client: &RestClient<CurrentNetwork>,
+    ) -> Result<String> {
+        // Initialize an RNG.
+        let rng = &mut rand::thread_rng();
+
+        // Initialize the VM.
+        let store = ConsensusStore::<CurrentNetwork, ConsensusMemory<CurrentNetwork>>::open(None)?;
+        let vm = VM::from(store)?;
+
+        // Load the program and it's imports into the process.
+        load_program(client, &mut vm.process().write(), &self.program_id)?;
+
+        // Authorize the execution.
+        let authorization = vm.authorize(private_key, self.program_id, self.function, self.inputs.iter(), rng)?;
+        // Run the function without proving it, to determine its transitions.
+        // Note: The signed requests of the authorization never leave this machine.
+        let (_, trace) = vm.process().read().execute::<CurrentAleo>(authorization)?;
+        let execution = Execution::from(trace.transitions().iter().cloned(), Default::default(), None)?;
+
+        // Request the fee estimate of the unproven execution.
+        Developer::estimate_fee(client, FeeRequest::Execution(execution))
+    }
+
+    /// Creates the execution transaction, using a VM that contains the program and its imports.
+    fn execute<C: ConsensusStorage<CurrentNetwork>>(
+        &self,
+        vm: &VM<CurrentNetwork, C>,
+        private_key: &PrivateKey<CurrentNetwork>,
+        query: Option<Query<CurrentNetwork, C::BlockStorage>>,
+    ) -> Result<Transaction<CurrentNetwork>> {
+        // Initialize an RNG.
+        let rng = &mut rand::thread_rng();
+
+        // Prepare the fee.
+        let fee_record = match &self.record {
+            Some(record_string) => Some(Developer::parse_record(private_key, record_string)?),
+            None => None,
+        };
+        let priority_fee = self.fee.unwrap_or(0);
+
+        // Create a new transaction.
+        vm.execute(
+            private_key,
+            (self.program_id, self.function),
+            self.inputs.iter(),
+            fee_record,
+            priority_fee,
+            query,
+            rng,
+        )
     }
 }
 
 /// A helper function to recursively load the program and all of its imports into the process.
-fn load_program(
-    endpoint: &str,
+pub(super) fn load_program(
+    client: &RestClient<CurrentNetwork>,
     process: &mut Process<CurrentNetwork>,
     program_id: &ProgramID<CurrentNetwork>,
 ) -> Result<()> {
     // Fetch the program.
-    let program = Developer::fetch_program(program_id, endpoint)?;
+    let program = client.get_program(program_id)?;
 
     // Return early if the program is already loaded.
     if process.contains_program(program.id()) {
@@ -137,7 +221,7 @@ fn load_program(
         // Add the imports to the process if does not exist yet.
         if !process.contains_program(import_program_id) {
             // Recursively load the program and its imports.
-            load_program(endpoint, process, import_program_id)?;
+            load_program(client, process, import_program_id)?;
         }
     }
 
@@ -168,6 +252,11 @@ mod tests {
             "77",
             "--record",
             "RECORD",
+            "--broadcast",
+            "BROADCAST",
+            "--wait",
+            "--timeout",
+            "30",
             "hello.aleo",
             "hello",
             "1u32",
@@ -177,9 +266,13 @@ mod tests {
 
         if let Command::Developer(Developer::Execute(execute)) = cli.command {
             assert_eq!(execute.private_key, "PRIVATE_KEY");
-            assert_eq!(execute.query, "QUERY");
+            assert_eq!(execute.query, Some("QUERY".into()));
+            assert_eq!(execute.ledger, None);
             assert_eq!(execute.fee, Some(77));
             assert_eq!(execute.record, Some("RECORD".into()));
+            assert_eq!(execute.broadcast, Some("BROADCAST".into()));
+            assert!(execute.wait);
+            assert_eq!(execute.timeout, 30);
             assert_eq!(execute.program_id, "hello.aleo".try_into().unwrap());
             assert_eq!(execute.function, "hello".try_into().unwrap());
             assert_eq!(execute.inputs, vec!["1u32".try_into().unwrap(), "2u32".try_into().unwrap()]);
@@ -187,4 +280,88 @@ mod tests {
             panic!("Unexpected result of clap parsing!");
         }
     }
+
+    #[test]
+    fn clap_snarkos_execute_wait_requires_broadcast() {
+        let arg_vec = vec![
+            "snarkos",
+            "developer",
+            "execute",
+            "--private-key",
+            "PRIVATE_KEY",
+            "--query",
+            "QUERY",
+            "--dry-run",
+            "--wait",
+            "hello.aleo",
+            "hello",
+        ];
+        assert!(CLI::try_parse_from(arg_vec).is_err());
+    }
+
+    #[test]
+    fn clap_snarkos_execute_ledger() {
+        let arg_vec = vec![
+            "snarkos",
+            "developer",
+            "execute",
+            "--private-key",
+            "PRIVATE_KEY",
+            "--ledger",
+            "LEDGER",
+            "--store",
+            "STORE",
+            "hello.aleo",
+            "hello",
+        ];
+        let cli = CLI::parse_from(arg_vec);
+
+        if let Command::Developer(Developer::Execute(execute)) = cli.command {
+            assert_eq!(execute.query, None);
+            assert_eq!(execute.ledger, Some(PathBuf::from("LEDGER")));
+        } else {
+            panic!("Unexpected result of clap parsing!");
+        }
+
+        // `ledger` conflicts with `query`.
+        let arg_vec = vec![
+            "snarkos",
+            "developer",
+            "execute",
+            "--private-key",
+            "PRIVATE_KEY",
+            "--query",
+            "QUERY",
+            "--ledger",
+            "LEDGER",
+            "--store",
+            "STORE",
+            "hello.aleo",
+            "hello",
+        ];
+        assert!(CLI::try_parse_from(arg_vec).is_err());
+    }
+
+    #[test]
+    fn clap_snarkos_execute_estimate() {
+        let arg_vec = vec![
+            "snarkos",
+            "developer",
+            "execute",
+            "--private-key",
+            "PRIVATE_KEY",
+            "--query",
+            "QUERY",
+            "--estimate",
+            "hello.aleo",
+            "hello",
+        ];
+        let cli = CLI::parse_from(arg_vec);
+
+        if let Command::Developer(Developer::Execute(execute)) = cli.command {
+            assert!(execute.estimate);
+        } else {
+            panic!("Unexpected result of clap parsing!");
+        }
+    }
 }
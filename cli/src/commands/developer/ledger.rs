@@ -0,0 +1,128 @@
+// Copyright (C) 2019-2023 Aleo Systems Inc.
+// This file is part of the snarkOS library.
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at:
+// http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use super::CurrentNetwork;
+
+use snarkvm::prelude::{
+    store::{helpers::rocksdb::ConsensusDB, ConsensusStore},
+    Network,
+    Program,
+    ProgramID,
+    VM,
+};
+
+use anyhow::{anyhow, ensure, Result};
+use rocksdb::{checkpoint::Checkpoint, Options, DB};
+use std::{
+    fs,
+    path::{Path, PathBuf},
+};
+
+/// A snapshot of a local ledger, such as the one written by `snarkos start`.
+///
+/// The snapshot is a checkpoint of the ledger, so the original ledger is never modified,
+/// and can be used while a node is running.
+pub(super) struct LedgerSnapshot {
+    /// The VM, which serves the programs, mappings, and state paths of the ledger.
+    vm: VM<CurrentNetwork, ConsensusDB<CurrentNetwork>>,
+    /// The directory containing the checkpoint.
+    directory: PathBuf,
+}
+
+impl LedgerSnapshot {
+    /// Opens a snapshot of the ledger in the given directory.
+    pub(super) fn open(path: &Path) -> Result<Self> {
+        ensure!(path.join("CURRENT").is_file(), "'{}' is not a ledger directory", path.display());
+
+        // Note: snarkVM only opens development ledgers from `.ledger-{network}-{id}` in the current directory,
+        // so the checkpoint is created there, under a development ID that is not in use.
+        let (dev, directory) = (0..=u16::MAX)
+            .rev()
+            .map(|dev| (dev, aleo_std::aleo_ledger_dir(CurrentNetwork::ID, Some(dev))))
+            .find(|(_, directory)| !directory.exists())
+            .ok_or_else(|| anyhow!("There are no development IDs left to open the ledger with"))?;
+
+        match Self::checkpoint(path, &directory).and_then(|_| Ok(VM::from(ConsensusStore::open(Some(dev))?)?)) {
+            Ok(vm) => Ok(Self { vm, directory }),
+            Err(error) => {
+                let _ = fs::remove_dir_all(&directory);
+                Err(error)
+            }
+        }
+    }
+
+    /// Returns the VM of the ledger.
+    pub(super) const fn vm(&self) -> &VM<CurrentNetwork, ConsensusDB<CurrentNetwork>> {
+        &self.vm
+    }
+
+    /// Returns the program with the given ID, if it is deployed in the ledger.
+    pub(super) fn fetch_program(&self, program_id: &ProgramID<CurrentNetwork>) -> Result<Program<CurrentNetwork>> {
+        self.vm.process().read().get_program(program_id).cloned()
+    }
+
+    /// Creates a checkpoint of the ledger in the given path, in the given directory.
+    fn checkpoint(path: &Path, directory: &Path) -> Result<()> {
+        // Open the ledger read-only, which does not require the lock of a running node.
+        let ledger = DB::open_for_read_only(&Options::default(), path, false)?;
+        // Create the checkpoint, which links the immutable table files rather than copying them, where possible.
+        Checkpoint::new(&ledger)?.create_checkpoint(directory)?;
+        Ok(())
+    }
+}
+
+impl Drop for LedgerSnapshot {
+    /// Removes the snapshot.
+    fn drop(&mut self) {
+        let _ = fs::remove_dir_all(&self.directory);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_open_missing_ledger() {
+        let path = std::env::temp_dir().join(format!("snarkos_test_missing_ledger_{}", std::process::id()));
+        assert!(LedgerSnapshot::open(&path).is_err());
+    }
+
+    #[test]
+    fn test_checkpoint_running_ledger() {
+        let path = std::env::temp_dir().join(format!("snarkos_test_ledger_{}", std::process::id()));
+        let directory = std::env::temp_dir().join(format!("snarkos_test_ledger_checkpoint_{}", std::process::id()));
+
+        // Write to a ledger, which is kept open, as by a running node.
+        let mut options = Options::default();
+        options.create_if_missing(true);
+        let ledger = DB::open(&options, &path).unwrap();
+        ledger.put(b"key", b"value").unwrap();
+
+        // Ensure the checkpoint contains the writes, including those that were not flushed yet.
+        LedgerSnapshot::checkpoint(&path, &directory).unwrap();
+        ledger.put(b"key", b"updated").unwrap();
+        {
+            let checkpoint = DB::open_for_read_only(&Options::default(), &directory, false).unwrap();
+            assert_eq!(checkpoint.get(b"key").unwrap(), Some(b"value".to_vec()));
+        }
+
+        // Ensure the original ledger is not modified.
+        assert_eq!(ledger.get(b"key").unwrap(), Some(b"updated".to_vec()));
+
+        drop(ledger);
+        fs::remove_dir_all(&path).unwrap();
+        fs::remove_dir_all(&directory).unwrap();
+    }
+}
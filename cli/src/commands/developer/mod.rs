@@ -12,6 +12,12 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+mod authorize;
+pub use authorize::*;
+
+mod broadcast;
+pub use broadcast::*;
+
 mod decrypt;
 pub use decrypt::*;
 
@@ -21,12 +27,22 @@ pub use deploy::*;
 mod execute;
 pub use execute::*;
 
+mod ledger;
+use ledger::*;
+
+mod prove;
+pub use prove::*;
+
 mod scan;
 pub use scan::*;
 
 mod transfer_private;
 pub use transfer_private::*;
 
+mod wallet;
+use wallet::*;
+
+use snarkos_node_rest_client::{blocking::RestClient, FeeRequest};
 use snarkvm::{
     package::Package,
     prelude::{block::Transaction, Ciphertext, Plaintext, PrivateKey, Program, ProgramID, Record, ToBytes, ViewKey},
@@ -35,20 +51,43 @@ use snarkvm::{
 use anyhow::{bail, ensure, Result};
 use clap::Parser;
 use colored::Colorize;
-use std::{path::PathBuf, str::FromStr};
+use std::{
+    path::PathBuf,
+    str::FromStr,
+    time::{Duration, Instant},
+};
 
 type CurrentAleo = snarkvm::circuit::AleoV0;
 type CurrentNetwork = snarkvm::prelude::Testnet3;
 
+/// The interval at which to poll for a broadcast transaction to be confirmed.
+const WAIT_INTERVAL: Duration = Duration::from_secs(1);
+
+/// The error returned when a transaction broadcast with `--wait` is not accepted.
+#[derive(Debug, Error)]
+pub enum WaitError {
+    #[error("❌ Transaction {0} was rejected in block {1} (fee: {2} microcredits)")]
+    Rejected(String, u32, u64),
+
+    #[error("❌ Transaction {0} was not confirmed within {1} seconds")]
+    Timeout(String, u64),
+}
+
 /// Commands to manage Aleo accounts.
 #[derive(Debug, Parser)]
 pub enum Developer {
+    /// Authorize a program function offline, without proving it.
+    Authorize(Authorize),
+    /// Broadcast a stored transaction.
+    Broadcast(Broadcast),
     /// Decrypt a ciphertext.
     Decrypt(Decrypt),
     /// Deploy a program.
     Deploy(Deploy),
     /// Execute a program function.
     Execute(Execute),
+    /// Prove an authorization into a transaction.
+    Prove(Prove),
     /// Scan the node for records.
     Scan(Scan),
     /// Execute the `credits.aleo/transfer_private` function.
@@ -58,9 +97,12 @@ pub enum Developer {
 impl Developer {
     pub fn parse(self) -> Result<String> {
         match self {
+            Self::Authorize(authorize) => authorize.parse(),
+            Self::Broadcast(broadcast) => broadcast.parse(),
             Self::Decrypt(decrypt) => decrypt.parse(),
             Self::Deploy(deploy) => deploy.parse(),
             Self::Execute(execute) => execute.parse(),
+            Self::Prove(prove) => prove.parse(),
             Self::Scan(scan) => scan.parse(),
             Self::TransferPrivate(transfer_private) => transfer_private.parse(),
         }
@@ -104,23 +146,6 @@ impl Developer {
         }
     }
 
-    /// Fetch the program from the given endpoint.
-    fn fetch_program(program_id: &ProgramID<CurrentNetwork>, endpoint: &str) -> Result<Program<CurrentNetwork>> {
-        // Send a request to the query node.
-        let response = ureq::get(&format!("{endpoint}/testnet3/program/{program_id}")).call();
-
-        // Deserialize the program.
-        match response {
-            Ok(response) => response.into_json().map_err(|err| err.into()),
-            Err(err) => match err {
-                ureq::Error::Status(_status, response) => {
-                    bail!(response.into_string().unwrap_or("Response too large!".to_owned()))
-                }
-                err => bail!(err),
-            },
-        }
-    }
-
     /// Determine if the transaction should be broadcast or displayed to user.
     fn handle_transaction(
         broadcast: Option<String>,
@@ -222,4 +247,58 @@ impl Developer {
             Ok("".to_string())
         }
     }
+
+    /// Waits for the transaction to be confirmed in a block, and reports the block height and fee paid.
+    /// Returns an error if the transaction was rejected, or if it is not confirmed before the timeout.
+    fn wait_for_transaction(
+        client: &RestClient<CurrentNetwork>,
+        transaction: &Transaction<CurrentNetwork>,
+        timeout: u64,
+    ) -> Result<()> {
+        // Get the transaction id and fee.
+        let transaction_id = transaction.id();
+        let fee = *transaction.fee_amount()?;
+
+        println!("⏳ Waiting up to {timeout} seconds for transaction {transaction_id} to be confirmed...");
+
+        let deadline = Instant::now() + Duration::from_secs(timeout);
+        loop {
+            // Check if the transaction was accepted.
+            if let Some(block_hash) = client.find_block_hash(&transaction_id)? {
+                let height = client.get_height(&block_hash)?;
+                println!("✅ Transaction {transaction_id} was accepted in block {height} (fee: {fee} microcredits)");
+                return Ok(());
+            }
+
+            // Check if the transaction was rejected, in which case only its fee is confirmed, in a new transaction.
+            if let Some(fee_transition) = transaction.fee_transition() {
+                let fee_transition_id = fee_transition.transition_id();
+                let fee_transaction_id = client.find_transaction_id_from_transition_id(fee_transition_id)?;
+                if let Some(fee_transaction_id) = fee_transaction_id.filter(|id| *id != transaction_id) {
+                    if let Some(block_hash) = client.find_block_hash(&fee_transaction_id)? {
+                        let height = client.get_height(&block_hash)?;
+                        // Output the transaction ID, which is otherwise only output once it is accepted.
+                        println!("{transaction_id}\n");
+                        return Err(WaitError::Rejected(transaction_id.to_string(), height, fee).into());
+                    }
+                }
+            }
+
+            // Ensure the timeout has not elapsed.
+            if Instant::now() >= deadline {
+                // Output the transaction ID, which is otherwise only output once it is accepted.
+                println!("{transaction_id}\n");
+                return Err(WaitError::Timeout(transaction_id.to_string(), timeout).into());
+            }
+            std::thread::sleep(WAIT_INTERVAL);
+        }
+    }
+
+    /// Requests the fee estimate of the given deployment or execution from the node.
+    fn estimate_fee(client: &RestClient<CurrentNetwork>, request: FeeRequest<CurrentNetwork>) -> Result<String> {
+        match client.estimate_fee(&request) {
+            Ok(estimate) => Ok(serde_json::to_string_pretty(&estimate)?),
+            Err(error) => bail!("❌ Failed to estimate the fee {error}"),
+        }
+    }
 }
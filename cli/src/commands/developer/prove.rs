@@ -0,0 +1,177 @@
+// Copyright (C) 2019-2023 Aleo Systems Inc.
+// This file is part of the snarkOS library.
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at:
+// http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use super::{load_program, AuthorizationFile, AuthorizedDeployment, CurrentNetwork, Developer};
+use snarkos_node_rest_client::blocking::RestClient;
+
+use snarkvm::prelude::{
+    block::Transaction,
+    deployment_cost,
+    execution_cost,
+    query::Query,
+    store::{helpers::memory::ConsensusMemory, ConsensusStore},
+    VM,
+};
+
+use anyhow::{anyhow, bail, ensure, Result};
+use clap::Parser;
+use colored::Colorize;
+use std::path::PathBuf;
+
+/// Proves an authorization from `snarkos developer authorize` into a transaction.
+#[derive(Debug, Parser)]
+pub struct Prove {
+    /// The path to the authorization.
+    authorization: PathBuf,
+    /// The endpoint to query node state from.
+    #[clap(short, long)]
+    query: String,
+    /// The endpoint used to broadcast the generated transaction.
+    #[clap(short, long, conflicts_with = "dry_run")]
+    broadcast: Option<String>,
+    /// Performs a dry-run of transaction generation.
+    #[clap(short, long, conflicts_with = "broadcast")]
+    dry_run: bool,
+    /// Store generated transaction to a local file.
+    #[clap(long)]
+    store: Option<String>,
+}
+
+impl Prove {
+    /// Proves the authorization into an execution or deployment transaction.
+    #[allow(clippy::format_in_format_args)]
+    pub fn parse(self) -> Result<String> {
+        // Ensure that the user has specified an action.
+        if !self.dry_run && self.broadcast.is_none() && self.store.is_none() {
+            bail!("❌ Please specify one of the following actions: --broadcast, --dry-run, --store");
+        }
+
+        // Read the authorization.
+        let authorization: AuthorizationFile = serde_json::from_str(&std::fs::read_to_string(&self.authorization)?)?;
+
+        let operation = authorization.operation()?;
+        let kind = match authorization.deployment {
+            Some(_) => "deployment",
+            None => "execution",
+        };
+        println!("📦 Proving {kind} transaction for '{}'...\n", &operation.bold());
+
+        // Generate the transaction.
+        let transaction = match &authorization.deployment {
+            Some(deployment) => self.prove_deployment(deployment, &authorization)?,
+            None => self.prove_execution(&authorization)?,
+        };
+        println!("✅ Proved {kind} transaction for '{}'", operation.bold());
+
+        // Determine if the transaction should be broadcast, stored, or displayed to user.
+        Developer::handle_transaction(self.broadcast, self.dry_run, self.store, transaction, operation)
+    }
+
+    /// Proves the execution and its fee.
+    fn prove_execution(&self, authorization: &AuthorizationFile) -> Result<Transaction<CurrentNetwork>> {
+        // Initialize an RNG.
+        let rng = &mut rand::thread_rng();
+
+        // Initialize the VM.
+        let store = ConsensusStore::<CurrentNetwork, ConsensusMemory<CurrentNetwork>>::open(None)?;
+        let vm = VM::from(store)?;
+
+        // Load the program and it's imports into the process.
+        let locator = authorization.locator()?;
+        load_program(&RestClient::new(&self.query)?, &mut vm.process().write(), locator.program_id())?;
+
+        // Ensure the fee is authorized, if one is required.
+        let execution = authorization.execution();
+        ensure!(
+            authorization.fee.is_some() || execution.is_split(),
+            "The authorization for '{locator}' is missing a fee"
+        );
+
+        // Prove the execution and the fee.
+        let transaction =
+            vm.execute_authorization(execution, authorization.fee(), Some(Query::from(&self.query)), rng)?;
+
+        // Ensure the authorized fee covers the cost of the proven execution.
+        if let (Some(execution), true) = (transaction.execution(), authorization.fee.is_some()) {
+            let (minimum_cost, _) = execution_cost(&vm, execution)?;
+            let fee = *transaction.fee_amount()?;
+            ensure!(
+                fee >= minimum_cost,
+                "The authorized fee ({fee} microcredits) is less than the execution cost ({minimum_cost} microcredits)"
+            );
+        }
+        Ok(transaction)
+    }
+
+    /// Proves the fee of the deployment.
+    fn prove_deployment(
+        &self,
+        deployment: &AuthorizedDeployment,
+        authorization: &AuthorizationFile,
+    ) -> Result<Transaction<CurrentNetwork>> {
+        let AuthorizedDeployment { owner, deployment } = deployment;
+        let program_id = deployment.program_id();
+
+        // Ensure the deployment is signed by its owner.
+        let deployment_id = deployment.to_deployment_id()?;
+        ensure!(owner.verify(deployment_id), "The owner of '{program_id}' did not sign the deployment");
+
+        // Ensure the fee is authorized.
+        let fee =
+            authorization.fee().ok_or_else(|| anyhow!("The authorization for '{program_id}' is missing a fee"))?;
+
+        // Initialize an RNG.
+        let rng = &mut rand::thread_rng();
+
+        // Initialize the VM.
+        let store = ConsensusStore::<CurrentNetwork, ConsensusMemory<CurrentNetwork>>::open(None)?;
+        let vm = VM::from(store)?;
+
+        // Prove the fee.
+        let fee = vm.execute_fee_authorization(fee, Some(Query::from(&self.query)), rng)?;
+        let transaction = Transaction::from_deployment(*owner, deployment.clone(), fee)?;
+
+        // Ensure the authorized fee covers the cost of the deployment.
+        let (minimum_cost, _) = deployment_cost(deployment)?;
+        let fee = *transaction.fee_amount()?;
+        ensure!(
+            fee >= minimum_cost,
+            "The authorized fee ({fee} microcredits) is less than the deployment cost ({minimum_cost} microcredits)"
+        );
+        Ok(transaction)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::commands::{Command, CLI};
+
+    #[test]
+    fn clap_snarkos_prove() {
+        let arg_vec =
+            vec!["snarkos", "developer", "prove", "--query", "QUERY", "--store", "STORE", "authorization.json"];
+        let cli = CLI::parse_from(arg_vec);
+
+        if let Command::Developer(Developer::Prove(prove)) = cli.command {
+            assert_eq!(prove.authorization, PathBuf::from("authorization.json"));
+            assert_eq!(prove.query, "QUERY");
+            assert_eq!(prove.store, Some("STORE".into()));
+            assert_eq!(prove.broadcast, None);
+            assert!(!prove.dry_run);
+        } else {
+            panic!("Unexpected result of clap parsing!");
+        }
+    }
+}
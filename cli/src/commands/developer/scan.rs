@@ -12,14 +12,29 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
-use super::CurrentNetwork;
-
-use snarkvm::prelude::{block::Block, Ciphertext, Field, Network, Plaintext, PrivateKey, Record, ViewKey};
+use super::{CurrentNetwork, Wallet, WalletRecord};
+
+use snarkos_node_rest_client::{blocking::RestClient, StatusError};
+use snarkvm::prelude::{
+    block::{Output, Transition},
+    Field,
+    Identifier,
+    Network,
+    Plaintext,
+    PrivateKey,
+    Program,
+    ProgramID,
+    Record,
+    ValueType,
+    ViewKey,
+};
 
 use anyhow::{bail, ensure, Result};
 use clap::Parser;
+use indexmap::IndexMap;
 use std::{
     io::{stdout, Write},
+    path::PathBuf,
     str::FromStr,
 };
 
@@ -49,6 +64,18 @@ pub struct Scan {
     /// The endpoint to scan blocks from.
     #[clap(long)]
     endpoint: String,
+
+    /// The path to the wallet database. Defaults to a file in the Aleo directory for the view key.
+    #[clap(long)]
+    db: Option<PathBuf>,
+
+    /// Discards the wallet database, and scans the block range from scratch.
+    #[clap(long)]
+    reset: bool,
+
+    /// Outputs the balances of the unspent records, per program and record type.
+    #[clap(long)]
+    balances: bool,
 }
 
 impl Scan {
@@ -56,11 +83,49 @@ impl Scan {
         // Derive the view key and optional private key.
         let (private_key, view_key) = self.parse_account()?;
 
-        // Find the start and end height to scan.
-        let (start_height, end_height) = self.parse_block_range()?;
+        // Open the wallet database.
+        let path = self.db.clone().unwrap_or_else(|| Wallet::default_path(&view_key));
+        let mut wallet = Wallet::open(&path, &view_key)?;
+        if self.reset {
+            wallet.reset();
+        }
+
+        // Initialize the client of the endpoint, which is shared by all of the requests.
+        let client = RestClient::<CurrentNetwork>::new(&self.endpoint)?;
+
+        // Find the start and end height to scan, resuming from the wallet checkpoint if no range is specified.
+        let (start_height, end_height) = match (self.start, self.end, self.last, wallet.next_height()) {
+            (None, None, None, Some(_)) => (wallet.start_height(), client.latest_height()?),
+            _ => self.parse_block_range(&client)?,
+        };
+
+        // If a private key is provided, determine the spent status of records that were found without one.
+        if let Some(private_key) = private_key {
+            Self::update_spent_records(&mut wallet, private_key, &client)?;
+        }
+
+        // Scan the blocks that have not been scanned yet.
+        match wallet.remaining_range(start_height, end_height)? {
+            Some((start, end)) => Self::scan_blocks(&mut wallet, private_key, &view_key, &client, start, end)?,
+            None => println!("✅ The wallet is already synced to block {end_height}.\n"),
+        }
+        wallet.save()?;
+
+        // Output the balances of the unspent records.
+        if self.balances {
+            if private_key.is_none() {
+                println!("⚠️  These balances may include records that have already been spent.\n");
+            }
+            return Ok(serde_json::to_string_pretty(&wallet.balances())?);
+        }
 
-        // Fetch the records from the network.
-        let records = Self::fetch_records(private_key, &view_key, &self.endpoint, start_height, end_height)?;
+        // Retrieve the unspent records within the block range.
+        let records = wallet
+            .records()
+            .iter()
+            .filter(|record| !record.is_spent && (start_height..=end_height).contains(&record.height))
+            .map(|record| &record.record)
+            .collect::<Vec<_>>();
 
         // Output the decrypted records associated with the view key.
         if records.is_empty() {
@@ -106,7 +171,7 @@ impl Scan {
     }
 
     /// Returns the `start` and `end` blocks to scan.
-    fn parse_block_range(&self) -> Result<(u32, u32)> {
+    fn parse_block_range(&self, client: &RestClient<CurrentNetwork>) -> Result<(u32, u32)> {
         match (self.start, self.end, self.last) {
             (Some(start), Some(end), None) => {
                 ensure!(end > start, "The given scan range is invalid (start = {start}, end = {end})");
@@ -115,8 +180,7 @@ impl Scan {
             }
             (Some(start), None, None) => {
                 // Request the latest block height from the endpoint.
-                let endpoint = format!("{}/testnet3/latest/height", self.endpoint);
-                let latest_height = u32::from_str(&ureq::get(&endpoint).call()?.into_string()?)?;
+                let latest_height = client.latest_height()?;
 
                 // Print warning message if the user is attempting to scan the whole chain.
                 if start == 0 {
@@ -128,8 +192,7 @@ impl Scan {
             (None, Some(end), None) => Ok((0, end)),
             (None, None, Some(last)) => {
                 // Request the latest block height from the endpoint.
-                let endpoint = format!("{}/testnet3/latest/height", self.endpoint);
-                let latest_height = u32::from_str(&ureq::get(&endpoint).call()?.into_string()?)?;
+                let latest_height = client.latest_height()?;
 
                 Ok((latest_height.saturating_sub(last), latest_height))
             }
@@ -138,14 +201,16 @@ impl Scan {
         }
     }
 
-    /// Fetch owned ciphertext records from the endpoint.
-    fn fetch_records(
+    /// Scans the blocks from the endpoint for records owned by the view key, and stores them in the wallet.
+    /// The wallet checkpoint is saved after every batch of blocks, so an interrupted scan can be resumed.
+    fn scan_blocks(
+        wallet: &mut Wallet,
         private_key: Option<PrivateKey<CurrentNetwork>>,
         view_key: &ViewKey<CurrentNetwork>,
-        endpoint: &str,
+        client: &RestClient<CurrentNetwork>,
         start_height: u32,
         end_height: u32,
-    ) -> Result<Vec<Record<CurrentNetwork, Plaintext<CurrentNetwork>>>> {
+    ) -> Result<()> {
         // Check the bounds of the request.
         if start_height > end_height {
             bail!("Invalid block range");
@@ -156,7 +221,8 @@ impl Scan {
 
         const MAX_BLOCK_RANGE: u32 = 50;
 
-        let mut records = Vec::new();
+        // Initialize a cache of the programs, to look up record names.
+        let mut programs = IndexMap::new();
 
         // Calculate the number of blocks to scan.
         let total_blocks = end_height.saturating_sub(start_height);
@@ -173,27 +239,62 @@ impl Scan {
                 std::cmp::min(MAX_BLOCK_RANGE, end_height.saturating_sub(request_start).saturating_add(1));
             let request_end = request_start.saturating_add(num_blocks_to_request);
 
-            // Establish the endpoint.
-            let blocks_endpoint = format!("{endpoint}/testnet3/blocks?start={request_start}&end={request_end}");
-
             // Fetch blocks
-            let blocks: Vec<Block<CurrentNetwork>> = ureq::get(&blocks_endpoint).call()?.into_json()?;
+            let blocks = client.get_blocks(request_start, request_end)?;
 
-            // Scan the blocks for owned records.
             for block in &blocks {
-                for (commitment, ciphertext_record) in block.records() {
-                    // Check if the record is owned by the given view key.
-                    if ciphertext_record.is_owner_with_address_x_coordinate(view_key, &address_x_coordinate) {
-                        // Decrypt and optionally filter the records.
-                        if let Some(record) =
-                            Self::decrypt_record(private_key, view_key, endpoint, *commitment, ciphertext_record)?
-                        {
-                            records.push(record);
+                // Ensure the block extends the last scanned block.
+                if let Some(last_hash) = wallet.last_hash() {
+                    ensure!(
+                        block.previous_hash() == last_hash,
+                        "Block {} does not extend the last scanned block ({last_hash}), rerun the scan with '--reset'",
+                        block.height()
+                    );
+                }
+
+                // Scan the block for owned records.
+                for transition in block.transitions() {
+                    for (index, output) in transition.outputs().iter().enumerate() {
+                        if let Output::Record(commitment, _, Some(ciphertext_record)) = output {
+                            // Check if the record is owned by the given view key.
+                            if ciphertext_record.is_owner_with_address_x_coordinate(view_key, &address_x_coordinate) {
+                                // Compute the serial number, if a private key was provided.
+                                let serial_number = match private_key {
+                                    Some(private_key) => {
+                                        Some(Record::<CurrentNetwork, Plaintext<CurrentNetwork>>::serial_number(
+                                            private_key,
+                                            *commitment,
+                                        )?)
+                                    }
+                                    None => None,
+                                };
+                                // Decrypt and store the record.
+                                wallet.insert_record(WalletRecord {
+                                    commitment: *commitment,
+                                    program_id: *transition.program_id(),
+                                    record_name: Self::record_name(&mut programs, client, transition, index),
+                                    height: block.height(),
+                                    record: ciphertext_record.decrypt(view_key)?,
+                                    serial_number,
+                                    is_spent: false,
+                                });
+                            }
                         }
                     }
                 }
+
+                // Mark the records spent in the block, including any that were created in the same block.
+                for serial_number in block.serial_numbers() {
+                    wallet.mark_spent(serial_number);
+                }
+
+                // Update the wallet checkpoint.
+                wallet.set_checkpoint(block.height(), block.hash());
             }
 
+            // Save the wallet checkpoint.
+            wallet.save()?;
+
             request_start = request_start.saturating_add(num_blocks_to_request);
         }
 
@@ -201,42 +302,54 @@ impl Scan {
         println!("\rScanning {total_blocks} blocks for records (100% complete)...   \n");
         stdout().flush()?;
 
-        Ok(records)
+        Ok(())
     }
 
-    /// Decrypts the ciphertext record and filters spend record if a private key was provided.
-    fn decrypt_record(
-        private_key: Option<PrivateKey<CurrentNetwork>>,
-        view_key: &ViewKey<CurrentNetwork>,
-        endpoint: &str,
-        commitment: Field<CurrentNetwork>,
-        ciphertext_record: &Record<CurrentNetwork, Ciphertext<CurrentNetwork>>,
-    ) -> Result<Option<Record<CurrentNetwork, Plaintext<CurrentNetwork>>>> {
-        // Check if a private key was provided.
-        if let Some(private_key) = private_key {
+    /// Returns the record name of the given transition output, if it can be determined from the program.
+    fn record_name(
+        programs: &mut IndexMap<ProgramID<CurrentNetwork>, Option<Program<CurrentNetwork>>>,
+        client: &RestClient<CurrentNetwork>,
+        transition: &Transition<CurrentNetwork>,
+        index: usize,
+    ) -> Option<Identifier<CurrentNetwork>> {
+        // Fetch the program, if it is not cached.
+        let program = programs
+            .entry(*transition.program_id())
+            .or_insert_with(|| client.get_program(transition.program_id()).ok());
+        // Retrieve the record name from the output type of the function.
+        match program.as_ref()?.get_function(transition.function_name()).ok()?.output_types().get(index)? {
+            ValueType::Record(record_name) => Some(*record_name),
+            _ => None,
+        }
+    }
+
+    /// Computes the serial numbers of the records that were found without a private key,
+    /// and checks whether they have been spent.
+    fn update_spent_records(
+        wallet: &mut Wallet,
+        private_key: PrivateKey<CurrentNetwork>,
+        client: &RestClient<CurrentNetwork>,
+    ) -> Result<()> {
+        for record in wallet.records_mut().iter_mut().filter(|record| record.serial_number.is_none()) {
             // Compute the serial number.
             let serial_number =
-                Record::<CurrentNetwork, Plaintext<CurrentNetwork>>::serial_number(private_key, commitment)?;
-
-            // Establish the endpoint.
-            let endpoint = format!("{endpoint}/testnet3/find/transitionID/{serial_number}");
-
-            // Check if the record is spent.
-            match ureq::get(&endpoint).call() {
-                // On success, skip as the record is spent.
-                Ok(_) => Ok(None),
-                // On error, add the record.
-                Err(_error) => {
-                    // TODO: Dedup the error types. We're adding the record as valid because the endpoint failed,
-                    //  meaning it couldn't find the serial number (ie. unspent). However if there's a DNS error or request error,
-                    //  we have a false positive here then.
-                    // Decrypt the record.
-                    Ok(Some(ciphertext_record.decrypt(view_key)?))
-                }
+                Record::<CurrentNetwork, Plaintext<CurrentNetwork>>::serial_number(private_key, record.commitment)?;
+            // Note: The serial number is only saved once the record is checked, so a failed check is retried.
+            record.is_spent = Self::is_spent(client, serial_number)?;
+            record.serial_number = Some(serial_number);
+        }
+        Ok(())
+    }
+
+    /// Returns `true` if the given serial number has been spent, according to the endpoint.
+    fn is_spent(client: &RestClient<CurrentNetwork>, serial_number: Field<CurrentNetwork>) -> Result<bool> {
+        // Check if the record is spent, treating only a serial number that is not found as unspent.
+        match client.find_transition_id(&serial_number) {
+            Ok(_) => Ok(true),
+            Err(error) if error.downcast_ref::<StatusError>().map_or(false, StatusError::is_not_found) => Ok(false),
+            Err(error) => {
+                bail!("Failed to check if the record with serial number '{serial_number}' is spent - {error}")
             }
-        } else {
-            // If no private key was provided, return the record.
-            Ok(Some(ciphertext_record.decrypt(view_key)?))
         }
     }
 }
@@ -297,16 +410,18 @@ mod tests {
 
     #[test]
     fn test_parse_block_range() {
+        // Note: The client is not used when both heights are given.
+        let client = RestClient::new("http://127.0.0.1:3033").unwrap();
         let config =
             Scan::try_parse_from(["snarkos", "--view-key", "", "--start", "0", "--end", "10", "--endpoint", ""].iter())
                 .unwrap();
-        assert!(config.parse_block_range().is_ok());
+        assert!(config.parse_block_range(&client).is_ok());
 
         // `start` height can't be greater than `end` height.
         let config =
             Scan::try_parse_from(["snarkos", "--view-key", "", "--start", "10", "--end", "5", "--endpoint", ""].iter())
                 .unwrap();
-        assert!(config.parse_block_range().is_err());
+        assert!(config.parse_block_range(&client).is_err());
 
         // `last` conflicts with `start`
         assert!(
@@ -12,11 +12,13 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
-use super::{CurrentNetwork, Developer};
+use super::{CurrentNetwork, Developer, LedgerSnapshot};
+use snarkos_node_rest_client::blocking::RestClient;
 
 use snarkvm::prelude::{
+    block::Transaction,
     query::Query,
-    store::{helpers::memory::ConsensusMemory, ConsensusStore},
+    store::{helpers::memory::ConsensusMemory, ConsensusStorage, ConsensusStore},
     Address,
     Locator,
     PrivateKey,
@@ -26,7 +28,7 @@ use snarkvm::prelude::{
 
 use anyhow::{bail, Result};
 use clap::Parser;
-use std::str::FromStr;
+use std::{path::PathBuf, str::FromStr};
 
 /// Executes the `transfer_private` function in the `credits.aleo` program.
 #[derive(Debug, Parser)]
@@ -44,8 +46,11 @@ pub struct TransferPrivate {
     #[clap(short, long)]
     private_key: String,
     /// The endpoint to query node state from.
-    #[clap(short, long)]
-    query: String,
+    #[clap(short, long, required_unless_present = "ledger")]
+    query: Option<String>,
+    /// The path to a local ledger to query node state from, such as `~/.aleo/storage/ledger-3`.
+    #[clap(long, conflicts_with = "query")]
+    ledger: Option<PathBuf>,
     /// The priority fee in microcredits.
     #[clap(short, long)]
     fee: u64,
@@ -61,6 +66,12 @@ pub struct TransferPrivate {
     /// Store generated deployment transaction to a local file.
     #[clap(long)]
     store: Option<String>,
+    /// Waits for the broadcast transaction to be confirmed in a block.
+    #[clap(long, requires = "broadcast", requires = "query")]
+    wait: bool,
+    /// The number of seconds to wait for the transaction to be confirmed.
+    #[clap(long, default_value_t = 60, requires = "wait")]
+    timeout: u64,
 }
 
 impl TransferPrivate {
@@ -72,50 +83,80 @@ impl TransferPrivate {
             bail!("❌ Please specify one of the following actions: --broadcast, --dry-run, --store");
         }
 
-        // Specify the query
-        let query = Query::from(&self.query);
-
         // Retrieve the private key.
         let private_key = PrivateKey::from_str(&self.private_key)?;
 
         println!("📦 Creating private transfer of {} microcredits to {}...\n", self.amount, self.recipient);
 
         // Generate the transfer_private transaction.
-        let transaction = {
-            // Initialize an RNG.
-            let rng = &mut rand::thread_rng();
-
-            // Initialize the VM.
-            let store = ConsensusStore::<CurrentNetwork, ConsensusMemory<CurrentNetwork>>::open(None)?;
-            let vm = VM::from(store)?;
-
-            // Prepare the fee.
-            let fee_record = Developer::parse_record(&private_key, &self.fee_record)?;
-            let priority_fee = self.fee;
-
-            // Prepare the inputs for a transfer.
-            let input_record = Developer::parse_record(&private_key, &self.input_record)?;
-            let inputs = vec![
-                Value::Record(input_record),
-                Value::from_str(&format!("{}", self.recipient))?,
-                Value::from_str(&format!("{}u64", self.amount))?,
-            ];
-
-            // Create a new transaction.
-            vm.execute(
-                &private_key,
-                ("credits.aleo", "transfer_private"),
-                inputs.iter(),
-                Some(fee_record),
-                priority_fee,
-                Some(query),
-                rng,
-            )?
+        let transaction = match (&self.ledger, &self.query) {
+            (Some(path), _) => {
+                // Open a snapshot of the ledger.
+                let ledger = LedgerSnapshot::open(path)?;
+
+                // Create a new transaction, using the state of the ledger.
+                self.transfer(ledger.vm(), &private_key, None)?
+            }
+            (None, Some(query)) => {
+                // Initialize the VM.
+                let store = ConsensusStore::<CurrentNetwork, ConsensusMemory<CurrentNetwork>>::open(None)?;
+                let vm = VM::from(store)?;
+
+                // Create a new transaction, using the state of the query endpoint.
+                self.transfer(&vm, &private_key, Some(Query::from(query)))?
+            }
+            (None, None) => bail!("❌ Please specify one of the following sources: --query, --ledger"),
         };
         let locator = Locator::<CurrentNetwork>::from_str("credits.aleo/transfer_private")?;
         println!("✅ Created private transfer of {} microcredits to {}\n", &self.amount, self.recipient);
 
         // Determine if the transaction should be broadcast, stored, or displayed to user.
-        Developer::handle_transaction(self.broadcast, self.dry_run, self.store, transaction, locator.to_string())
+        let output = Developer::handle_transaction(
+            self.broadcast,
+            self.dry_run,
+            self.store,
+            transaction.clone(),
+            locator.to_string(),
+        )?;
+
+        // Wait for the transaction to be confirmed, if requested.
+        if let (true, Some(query)) = (self.wait, &self.query) {
+            Developer::wait_for_transaction(&RestClient::new(query)?, &transaction, self.timeout)?;
+        }
+        Ok(output)
+    }
+
+    /// Creates the transfer_private transaction.
+    fn transfer<C: ConsensusStorage<CurrentNetwork>>(
+        &self,
+        vm: &VM<CurrentNetwork, C>,
+        private_key: &PrivateKey<CurrentNetwork>,
+        query: Option<Query<CurrentNetwork, C::BlockStorage>>,
+    ) -> Result<Transaction<CurrentNetwork>> {
+        // Initialize an RNG.
+        let rng = &mut rand::thread_rng();
+
+        // Prepare the fee.
+        let fee_record = Developer::parse_record(private_key, &self.fee_record)?;
+        let priority_fee = self.fee;
+
+        // Prepare the inputs for a transfer.
+        let input_record = Developer::parse_record(private_key, &self.input_record)?;
+        let inputs = vec![
+            Value::Record(input_record),
+            Value::from_str(&format!("{}", self.recipient))?,
+            Value::from_str(&format!("{}u64", self.amount))?,
+        ];
+
+        // Create a new transaction.
+        vm.execute(
+            private_key,
+            ("credits.aleo", "transfer_private"),
+            inputs.iter(),
+            Some(fee_record),
+            priority_fee,
+            query,
+            rng,
+        )
     }
 }
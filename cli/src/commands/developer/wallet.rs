@@ -0,0 +1,346 @@
+// Copyright (C) 2019-2023 Aleo Systems Inc.
+// This file is part of the snarkOS library.
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at:
+// http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use super::CurrentNetwork;
+
+use snarkvm::prelude::{Address, Entry, Field, Identifier, Literal, Network, Plaintext, ProgramID, Record, ViewKey};
+
+use anyhow::{bail, ensure, Result};
+use indexmap::IndexMap;
+use serde::{Deserialize, Serialize};
+use std::{
+    io::Write,
+    path::{Path, PathBuf},
+    str::FromStr,
+};
+
+/// A record owned by the wallet, along with its provenance and spent status.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub(super) struct WalletRecord {
+    /// The commitment of the record.
+    pub commitment: Field<CurrentNetwork>,
+    /// The program ID that created the record.
+    pub program_id: ProgramID<CurrentNetwork>,
+    /// The name of the record type, if it is known.
+    pub record_name: Option<Identifier<CurrentNetwork>>,
+    /// The height of the block that contains the record.
+    pub height: u32,
+    /// The decrypted record.
+    pub record: Record<CurrentNetwork, Plaintext<CurrentNetwork>>,
+    /// The serial number of the record, if the private key was provided.
+    pub serial_number: Option<Field<CurrentNetwork>>,
+    /// Whether the record is known to be spent.
+    pub is_spent: bool,
+}
+
+impl WalletRecord {
+    /// Returns the type of the record, as `program_id/record_name`.
+    pub fn record_type(&self) -> String {
+        match &self.record_name {
+            Some(record_name) => format!("{}/{record_name}", self.program_id),
+            None => format!("{}/unknown", self.program_id),
+        }
+    }
+
+    /// Returns the number of microcredits in the record, if it has a `microcredits` entry.
+    pub fn microcredits(&self) -> Option<u64> {
+        match self.record.data().get(&Identifier::from_str("microcredits").ok()?)? {
+            Entry::Private(Plaintext::Literal(Literal::U64(amount), _))
+            | Entry::Public(Plaintext::Literal(Literal::U64(amount), _)) => Some(**amount),
+            _ => None,
+        }
+    }
+}
+
+/// The balance of a record type in the wallet.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub(super) struct WalletBalance {
+    /// The number of unspent records.
+    pub records: u64,
+    /// The total number of microcredits in the unspent records, if the record type holds microcredits.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub microcredits: Option<u64>,
+}
+
+/// A local database of the records owned by a view key, and the block range that has been scanned for them.
+#[derive(Debug, Serialize, Deserialize)]
+pub(super) struct Wallet {
+    /// The address of the view key.
+    address: Address<CurrentNetwork>,
+    /// The first block height that was scanned (inclusive).
+    start_height: u32,
+    /// The next block height to scan, which is the checkpoint for incremental scans.
+    next_height: u32,
+    /// The hash of the last block that was scanned.
+    last_hash: Option<<CurrentNetwork as Network>::BlockHash>,
+    /// The records owned by the view key, in the order they were found.
+    records: Vec<WalletRecord>,
+    /// The path to the wallet file.
+    #[serde(skip)]
+    path: PathBuf,
+}
+
+impl Wallet {
+    /// Returns the default path to the wallet file for the given view key.
+    pub fn default_path(view_key: &ViewKey<CurrentNetwork>) -> PathBuf {
+        aleo_std::aleo_dir().join("wallets").join(format!("{}.json", view_key.to_address()))
+    }
+
+    /// Opens the wallet at the given path, or initializes a new wallet if the file does not exist.
+    pub fn open(path: &Path, view_key: &ViewKey<CurrentNetwork>) -> Result<Self> {
+        let address = view_key.to_address();
+        // Initialize a new wallet, if the file does not exist.
+        if !path.exists() {
+            return Ok(Self::new(path, address));
+        }
+        // Load the wallet from the file.
+        let mut wallet: Self = serde_json::from_slice(&std::fs::read(path)?)?;
+        ensure!(
+            wallet.address == address,
+            "The wallet at '{}' belongs to a different account ({})",
+            path.display(),
+            wallet.address
+        );
+        wallet.path = path.to_path_buf();
+        Ok(wallet)
+    }
+
+    /// Initializes a new, empty wallet at the given path.
+    fn new(path: &Path, address: Address<CurrentNetwork>) -> Self {
+        Self { address, start_height: 0, next_height: 0, last_hash: None, records: vec![], path: path.to_path_buf() }
+    }
+
+    /// Discards all of the scanned state, and the records, in the wallet.
+    pub fn reset(&mut self) {
+        *self = Self::new(&self.path.clone(), self.address);
+    }
+
+    /// Saves the wallet to its file.
+    pub fn save(&self) -> Result<()> {
+        // Create the directories tree for the wallet file, if it doesn't exist.
+        if let Some(parent) = self.path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        // Write to a temporary file first, so the wallet is never left partially written.
+        let temp_path = self.path.with_extension("json.tmp");
+        let _ = std::fs::remove_file(&temp_path);
+        let mut options = std::fs::OpenOptions::new();
+        options.write(true).create_new(true);
+        // Ensure only the user can read the decrypted records.
+        #[cfg(target_family = "unix")]
+        std::os::unix::fs::OpenOptionsExt::mode(&mut options, 0o600);
+        options.open(&temp_path)?.write_all(&serde_json::to_vec_pretty(self)?)?;
+        std::fs::rename(temp_path, &self.path)?;
+        Ok(())
+    }
+
+    /// Returns the path to the wallet file.
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Returns the first block height that was scanned.
+    pub const fn start_height(&self) -> u32 {
+        self.start_height
+    }
+
+    /// Returns the next block height to scan, if any block has been scanned.
+    pub fn next_height(&self) -> Option<u32> {
+        self.last_hash.map(|_| self.next_height)
+    }
+
+    /// Returns the hash of the last block that was scanned.
+    pub fn last_hash(&self) -> Option<<CurrentNetwork as Network>::BlockHash> {
+        self.last_hash
+    }
+
+    /// Returns the block range `[start, end]` that remains to be scanned, for the requested range.
+    /// If the requested range is not contiguous with the scanned range, an error is returned,
+    /// as the wallet must be reset to scan it.
+    pub fn remaining_range(&mut self, start_height: u32, end_height: u32) -> Result<Option<(u32, u32)>> {
+        match self.next_height() {
+            // Resume from the checkpoint, if the requested range starts within the scanned range.
+            Some(next_height) if (self.start_height..=next_height).contains(&start_height) => {
+                Ok((next_height <= end_height).then_some((next_height, end_height)))
+            }
+            Some(next_height) => bail!(
+                "The wallet at '{}' has scanned blocks {} to {}, and block {start_height} is not contiguous with them. \
+                 Rerun the scan with '--reset' to discard the wallet and scan from block {start_height}",
+                self.path.display(),
+                self.start_height,
+                next_height.saturating_sub(1),
+            ),
+            // Start from the requested start height, if nothing was scanned yet.
+            None => {
+                self.start_height = start_height;
+                self.next_height = start_height;
+                Ok(Some((start_height, end_height)))
+            }
+        }
+    }
+
+    /// Updates the checkpoint, after the block with the given height and hash was scanned.
+    pub fn set_checkpoint(&mut self, height: u32, hash: <CurrentNetwork as Network>::BlockHash) {
+        self.next_height = height.saturating_add(1);
+        self.last_hash = Some(hash);
+    }
+
+    /// Returns `true` if the wallet contains a record with the given commitment.
+    pub fn contains_record(&self, commitment: &Field<CurrentNetwork>) -> bool {
+        self.records.iter().any(|record| &record.commitment == commitment)
+    }
+
+    /// Adds the given record to the wallet, if it does not already exist.
+    pub fn insert_record(&mut self, record: WalletRecord) {
+        if !self.contains_record(&record.commitment) {
+            self.records.push(record);
+        }
+    }
+
+    /// Marks the record with the given serial number as spent, returning `true` if such a record exists.
+    pub fn mark_spent(&mut self, serial_number: &Field<CurrentNetwork>) -> bool {
+        match self.records.iter_mut().find(|record| record.serial_number.as_ref() == Some(serial_number)) {
+            Some(record) => {
+                record.is_spent = true;
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Returns the records in the wallet.
+    pub fn records(&self) -> &[WalletRecord] {
+        &self.records
+    }
+
+    /// Returns the records in the wallet, mutably.
+    pub fn records_mut(&mut self) -> &mut [WalletRecord] {
+        &mut self.records
+    }
+
+    /// Returns the balances of the unspent records in the wallet, keyed by record type.
+    pub fn balances(&self) -> IndexMap<String, WalletBalance> {
+        let mut balances = IndexMap::<String, WalletBalance>::new();
+        for record in self.records.iter().filter(|record| !record.is_spent) {
+            let balance = balances.entry(record.record_type()).or_default();
+            balance.records += 1;
+            if let Some(microcredits) = record.microcredits() {
+                balance.microcredits = Some(balance.microcredits.unwrap_or(0).saturating_add(microcredits));
+            }
+        }
+        balances
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use snarkvm::prelude::{Group, Owner, PrivateKey, TestRng, Uniform};
+
+    /// Samples a wallet record with the given number of microcredits, owned by the given address.
+    fn sample_record(address: Address<CurrentNetwork>, microcredits: u64, rng: &mut TestRng) -> WalletRecord {
+        let record = Record::<CurrentNetwork, Plaintext<CurrentNetwork>>::from_str(&format!(
+            "{{ owner: {address}.private, microcredits: {microcredits}u64.private, _nonce: {}.public }}",
+            Group::<CurrentNetwork>::rand(rng)
+        ))
+        .unwrap();
+        WalletRecord {
+            commitment: Field::rand(rng),
+            program_id: ProgramID::from_str("credits.aleo").unwrap(),
+            record_name: Some(Identifier::from_str("credits").unwrap()),
+            height: 1,
+            record,
+            serial_number: Some(Field::rand(rng)),
+            is_spent: false,
+        }
+    }
+
+    #[test]
+    fn test_wallet_checkpoint() {
+        let rng = &mut TestRng::default();
+
+        let view_key = ViewKey::try_from(PrivateKey::<CurrentNetwork>::new(rng).unwrap()).unwrap();
+        let path = std::env::temp_dir().join(format!("snarkos_test_wallet_{}.json", view_key.to_address()));
+        let mut wallet = Wallet::open(&path, &view_key).unwrap();
+
+        // Ensure a new wallet scans the full requested range.
+        assert_eq!(wallet.next_height(), None);
+        assert_eq!(wallet.remaining_range(10, 20).unwrap(), Some((10, 20)));
+        wallet.set_checkpoint(20, Default::default());
+        assert_eq!(wallet.next_height(), Some(21));
+
+        // Ensure a contiguous range resumes from the checkpoint.
+        assert_eq!(wallet.remaining_range(10, 30).unwrap(), Some((21, 30)));
+        assert_eq!(wallet.remaining_range(21, 30).unwrap(), Some((21, 30)));
+        assert_eq!(wallet.remaining_range(15, 20).unwrap(), None);
+
+        // Ensure the wallet persists across runs.
+        wallet.insert_record(sample_record(view_key.to_address(), 100, rng));
+        wallet.save().unwrap();
+        let mut wallet = Wallet::open(&path, &view_key).unwrap();
+        assert_eq!(wallet.next_height(), Some(21));
+        assert_eq!(wallet.records().len(), 1);
+
+        // Ensure only the user can read the wallet file.
+        #[cfg(target_family = "unix")]
+        {
+            use std::os::unix::fs::PermissionsExt;
+            assert_eq!(std::fs::metadata(&path).unwrap().permissions().mode() & 0o777, 0o600);
+        }
+
+        // Ensure a non-contiguous range is rejected, and leaves the wallet intact.
+        assert!(wallet.remaining_range(50, 60).is_err());
+        assert_eq!(wallet.next_height(), Some(21));
+        assert_eq!(wallet.records().len(), 1);
+
+        // Ensure a reset wallet scans the new range.
+        wallet.reset();
+        assert_eq!(wallet.remaining_range(50, 60).unwrap(), Some((50, 60)));
+        assert_eq!(wallet.start_height(), 50);
+        assert!(wallet.records().is_empty());
+
+        // Ensure the wallet can not be opened by a different view key.
+        let other_view_key = ViewKey::try_from(PrivateKey::<CurrentNetwork>::new(rng).unwrap()).unwrap();
+        assert!(Wallet::open(&path, &other_view_key).is_err());
+
+        std::fs::remove_file(path).unwrap();
+    }
+
+    #[test]
+    fn test_wallet_balances() {
+        let rng = &mut TestRng::default();
+
+        let view_key = ViewKey::try_from(PrivateKey::<CurrentNetwork>::new(rng).unwrap()).unwrap();
+        let mut wallet = Wallet::new(&Wallet::default_path(&view_key), view_key.to_address());
+
+        // Add the records.
+        let records = (0..3).map(|i| sample_record(view_key.to_address(), 100 * (i + 1), rng)).collect::<Vec<_>>();
+        for record in &records {
+            wallet.insert_record(record.clone());
+        }
+        // Ensure duplicate records are not added.
+        wallet.insert_record(records[0].clone());
+        assert_eq!(wallet.records().len(), 3);
+
+        let expected = WalletBalance { records: 3, microcredits: Some(600) };
+        assert_eq!(wallet.balances().get("credits.aleo/credits"), Some(&expected));
+
+        // Spend a record.
+        assert!(wallet.mark_spent(records[1].serial_number.as_ref().unwrap()));
+        assert!(!wallet.mark_spent(&Field::rand(rng)));
+
+        let expected = WalletBalance { records: 2, microcredits: Some(400) };
+        assert_eq!(wallet.balances().get("credits.aleo/credits"), Some(&expected));
+    }
+}
@@ -21,6 +21,9 @@ pub use clean::*;
 mod developer;
 pub use developer::*;
 
+mod peers;
+pub use peers::*;
+
 mod start;
 pub use start::*;
 
@@ -57,6 +60,8 @@ pub enum Command {
     Clean(Clean),
     #[clap(subcommand)]
     Developer(Developer),
+    #[clap(name = "peers")]
+    Peers(Peers),
     #[clap(name = "start")]
     Start(Box<Start>),
     #[clap(name = "update")]
@@ -70,6 +75,7 @@ impl Command {
             Self::Account(command) => command.parse(),
             Self::Clean(command) => command.parse(),
             Self::Developer(command) => command.parse(),
+            Self::Peers(command) => command.parse(),
             Self::Start(command) => command.parse(),
             Self::Update(command) => command.parse(),
         }
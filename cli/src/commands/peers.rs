@@ -0,0 +1,183 @@
+// Copyright (C) 2019-2023 Aleo Systems Inc.
+// This file is part of the snarkOS library.
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at:
+// http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use snarkos_node_rest_client::blocking::RestClient;
+use snarkvm::console::network::Testnet3;
+
+use anyhow::{bail, Context, Result};
+use clap::Parser;
+use std::{
+    net::{IpAddr, SocketAddr},
+    path::PathBuf,
+};
+
+type CurrentNetwork = Testnet3;
+
+/// The environment variable of the JSON Web Token of the node.
+const TOKEN_ENV: &str = "SNARKOS_REST_TOKEN";
+
+/// Manages the peers of a running node, with its REST server.
+#[derive(Debug, Parser)]
+pub struct Peers {
+    /// Specify the endpoint of the REST server of the node.
+    #[clap(default_value = "http://127.0.0.1:3033", long = "endpoint")]
+    pub endpoint: String,
+    /// Specify the path to a file containing the JSON Web Token printed by the node on startup.
+    /// Otherwise, the token is read from the `SNARKOS_REST_TOKEN` environment variable.
+    #[clap(long = "token-file")]
+    pub token_file: Option<PathBuf>,
+    /// Specify the peer command.
+    #[clap(subcommand)]
+    pub command: PeersCommand,
+}
+
+/// The peer commands.
+#[derive(Debug, Parser)]
+pub enum PeersCommand {
+    /// Connects to the given peer.
+    Connect {
+        /// The IP address and port of the peer.
+        peer_ip: SocketAddr,
+        /// If the flag is set, connects to the peer as a validator in the memory pool.
+        #[clap(long)]
+        validator: bool,
+    },
+    /// Disconnects from the given peer.
+    Disconnect {
+        /// The IP address and port of the peer.
+        peer_ip: SocketAddr,
+        /// If the flag is set, disconnects from the peer as a validator in the memory pool.
+        #[clap(long)]
+        validator: bool,
+    },
+    /// Bans the given IP address until it is unbanned, and disconnects its peers.
+    Ban {
+        /// The IP address of the peer.
+        ip: IpAddr,
+    },
+    /// Lifts the ban of the given IP address.
+    Unban {
+        /// The IP address of the peer.
+        ip: IpAddr,
+    },
+    /// Lists the banned IP addresses.
+    Bans,
+}
+
+impl Peers {
+    /// Runs the peer command against the node.
+    pub fn parse(self) -> Result<String> {
+        let client = RestClient::<CurrentNetwork>::new(&self.endpoint)?.with_token(&self.parse_token()?);
+
+        match self.command {
+            PeersCommand::Connect { peer_ip, validator } => {
+                let connected = match validator {
+                    true => client.connect_validator(peer_ip)?,
+                    false => client.connect_peer(peer_ip)?,
+                };
+                match connected {
+                    true => Ok(format!("✅ Connected to '{peer_ip}'")),
+                    false => bail!("Unable to connect to '{peer_ip}'"),
+                }
+            }
+            PeersCommand::Disconnect { peer_ip, validator } => {
+                let disconnected = match validator {
+                    true => client.disconnect_validator(peer_ip)?,
+                    false => client.disconnect_peer(peer_ip)?,
+                };
+                match disconnected {
+                    true => Ok(format!("✅ Disconnected from '{peer_ip}'")),
+                    false => Ok(format!("✅ '{peer_ip}' was not connected")),
+                }
+            }
+            PeersCommand::Ban { ip } => {
+                client.ban_peer(ip)?;
+                Ok(format!("✅ Banned '{ip}'"))
+            }
+            PeersCommand::Unban { ip } => match client.unban_peer(ip)? {
+                true => Ok(format!("✅ Unbanned '{ip}'")),
+                false => Ok(format!("✅ '{ip}' was not banned")),
+            },
+            PeersCommand::Bans => {
+                let bans = client.get_peers_bans()?;
+                Ok(bans.iter().map(|ip| ip.to_string()).collect::<Vec<_>>().join("\n"))
+            }
+        }
+    }
+
+    /// Returns the JSON Web Token of the node, from the token file or the environment variable.
+    /// Note: The token is not accepted as an argument, as the arguments are visible to the other users of the system.
+    fn parse_token(&self) -> Result<String> {
+        let token = match &self.token_file {
+            Some(path) => std::fs::read_to_string(path)
+                .with_context(|| format!("Failed to read the token file '{}'", path.display()))?,
+            None => match std::env::var(TOKEN_ENV) {
+                Ok(token) => token,
+                Err(_) => {
+                    bail!("Specify the JSON Web Token of the node with --token-file or the '{TOKEN_ENV}' variable")
+                }
+            },
+        };
+        match token.trim() {
+            "" => bail!("The JSON Web Token of the node is empty"),
+            token => Ok(token.to_string()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::commands::{Command, CLI};
+
+    #[test]
+    fn clap_snarkos_peers() {
+        let arg_vec = vec!["snarkos", "peers", "--token-file", "token.txt", "connect", "1.2.3.4:4133", "--validator"];
+        let cli = CLI::parse_from(arg_vec);
+
+        if let Command::Peers(peers) = cli.command {
+            assert_eq!(peers.endpoint, "http://127.0.0.1:3033");
+            assert_eq!(peers.token_file, Some(PathBuf::from("token.txt")));
+            assert!(matches!(peers.command, PeersCommand::Connect { validator: true, .. }));
+        } else {
+            panic!("Unexpected result of clap parsing!");
+        }
+
+        // The token is not accepted as an argument, and a ban is on an IP address.
+        assert!(CLI::try_parse_from(["snarkos", "peers", "--token", "TOKEN", "bans"]).is_err());
+        assert!(CLI::try_parse_from(["snarkos", "peers", "ban", "1.2.3.4:4133"]).is_err());
+        assert!(CLI::try_parse_from(["snarkos", "peers", "ban", "1.2.3.4"]).is_ok());
+    }
+
+    #[test]
+    fn test_parse_token() {
+        let path = std::env::temp_dir().join(format!("snarkos_test_token_{}.txt", std::process::id()));
+        let peers = Peers {
+            endpoint: "http://127.0.0.1:3033".to_string(),
+            token_file: Some(path.clone()),
+            command: PeersCommand::Bans,
+        };
+        // The token file must exist.
+        assert!(peers.parse_token().is_err());
+
+        // The token is trimmed, as written by a text editor.
+        std::fs::write(&path, "TOKEN\n").unwrap();
+        assert_eq!(peers.parse_token().unwrap(), "TOKEN");
+        // The token must not be empty.
+        std::fs::write(&path, "\n").unwrap();
+        assert!(peers.parse_token().is_err());
+
+        std::fs::remove_file(path).unwrap();
+    }
+}
@@ -12,9 +12,15 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+use crate::helpers::{LogFormat, LogRotation};
 use snarkos_account::Account;
 use snarkos_display::Display;
-use snarkos_node::{narwhal::MEMORY_POOL_PORT, router::messages::NodeType, Node};
+use snarkos_node::{
+    narwhal::MEMORY_POOL_PORT,
+    rest::{Quota, RateLimits, TlsConfig},
+    router::messages::NodeType,
+    Node,
+};
 use snarkvm::{
     console::{
         account::{Address, PrivateKey},
@@ -37,7 +43,10 @@ use colored::Colorize;
 use core::str::FromStr;
 use rand::SeedableRng;
 use rand_chacha::ChaChaRng;
-use std::{net::SocketAddr, path::PathBuf};
+use std::{
+    net::{IpAddr, SocketAddr},
+    path::PathBuf,
+};
 use tokio::runtime::{self, Runtime};
 
 /// The recommended minimum number of 'open files' limit for a validator.
@@ -92,6 +101,30 @@ pub struct Start {
     /// If the flag is set, the node will not initialize the REST server
     #[clap(long)]
     pub norest: bool,
+    /// Specify the requests per second allowed from each IP on the REST server, or 0 for no limit
+    #[clap(default_value = "50", long = "rest-rps")]
+    pub rest_rps: u32,
+    /// Specify the burst of requests allowed from each IP on the REST server
+    #[clap(default_value = "100", long = "rest-burst")]
+    pub rest_burst: u32,
+    /// Specify the requests per second allowed from each IP on the heavy REST routes, or 0 for no limit
+    #[clap(default_value = "5", long = "rest-heavy-rps")]
+    pub rest_heavy_rps: u32,
+    /// Specify the burst of requests allowed from each IP on the heavy REST routes
+    #[clap(default_value = "10", long = "rest-heavy-burst")]
+    pub rest_heavy_burst: u32,
+    /// Specify the maximum number of heavy REST requests served at once, or 0 for no limit
+    #[clap(default_value = "32", long = "rest-heavy-in-flight")]
+    pub rest_heavy_in_flight: usize,
+    /// Specify the IP address(es) exempt from the REST rate limits
+    #[clap(default_value = "", long = "rest-allowlist")]
+    pub rest_allowlist: String,
+    /// Specify the path to the PEM certificate chain, to serve the REST server over HTTPS
+    #[clap(long = "rest-tls-cert")]
+    pub rest_tls_cert: Option<PathBuf>,
+    /// Specify the path to the PEM private key, to serve the REST server over HTTPS
+    #[clap(long = "rest-tls-key")]
+    pub rest_tls_key: Option<PathBuf>,
 
     /// If the flag is set, the node will not render the display
     #[clap(long)]
@@ -102,10 +135,28 @@ pub struct Start {
     /// Specify the path to the file where logs will be stored
     #[clap(default_value_os_t = std::env::temp_dir().join("snarkos.log"), long = "logfile")]
     pub logfile: PathBuf,
+    /// Specify the format of the logs [options: text, json]
+    #[clap(value_enum, default_value_t = LogFormat::Text, long = "log-format")]
+    pub log_format: LogFormat,
+    /// Specify the size in megabytes at which the logfile is rotated, where `0` disables it
+    #[clap(default_value = "0", long = "log-max-size")]
+    pub log_max_size: u64,
+    /// Specify the interval at which the logfile is rotated [options: never, hourly, daily]
+    #[clap(value_enum, default_value_t = LogRotation::Never, long = "log-rotation")]
+    pub log_rotation: LogRotation,
+    /// Specify the number of rotated logfiles to keep
+    #[clap(default_value = "10", long = "log-max-files")]
+    pub log_max_files: usize,
+    /// Specify the endpoint of the OTLP collector to export the tracing spans to, e.g. `http://127.0.0.1:4317`
+    #[clap(long = "otlp-endpoint")]
+    pub otlp_endpoint: Option<String>,
 
     /// Enables the node to prefetch initial blocks from a CDN
     #[clap(default_value = "https://s3.us-west-1.amazonaws.com/testnet3.blocks/phase3", long = "cdn")]
     pub cdn: String,
+    /// If the flag is set, the node will index the transactions of each address and program
+    #[clap(long)]
+    pub indexer: bool,
     /// Enables development mode, specify a unique ID for this node
     #[clap(long)]
     pub dev: Option<u16>,
@@ -114,10 +165,29 @@ pub struct Start {
 impl Start {
     /// Starts the snarkOS node.
     pub fn parse(self) -> Result<String> {
-        // Initialize the logger.
-        let log_receiver = crate::helpers::initialize_logger(self.verbosity, self.nodisplay, self.logfile.clone());
         // Initialize the runtime.
-        Self::runtime().block_on(async move {
+        let runtime = Self::runtime();
+        // Initialize the logger.
+        // Note: The logger is initialized within the runtime, which runs the OTLP exporter.
+        let (log_receiver, log_filter) = {
+            let _guard = runtime.enter();
+            crate::helpers::initialize_logger(
+                self.verbosity,
+                self.nodisplay,
+                self.log_format,
+                self.logfile.clone(),
+                self.parse_log_max_size(),
+                self.log_rotation,
+                self.log_max_files,
+                self.otlp_endpoint.as_deref(),
+            )
+        };
+        runtime.block_on(async move {
+            // Change the log level on `SIGUSR1`.
+            #[cfg(target_family = "unix")]
+            crate::helpers::handle_log_signals(log_filter.clone(), self.verbosity);
+            // Change the log filter from the REST server.
+            log_filter.register().expect("Failed to register the log filter");
             // Clone the configurations.
             let mut cli = self.clone();
             // Parse the network.
@@ -179,6 +249,51 @@ impl Start {
         }
     }
 
+    /// Returns the rate limits of the REST server, from the given configurations.
+    fn parse_rest_limits(&self) -> Result<RateLimits> {
+        let allowlist = match self.rest_allowlist.is_empty() {
+            true => vec![],
+            false => self
+                .rest_allowlist
+                .split(',')
+                .map(|ip| match ip.parse::<IpAddr>() {
+                    Ok(ip) => Ok(ip),
+                    Err(e) => bail!("The IP supplied to --rest-allowlist ('{ip}') is malformed: {e}"),
+                })
+                .collect::<Result<_>>()?,
+        };
+        Ok(RateLimits {
+            standard: Quota { per_second: self.rest_rps, burst: self.rest_burst },
+            heavy: Quota { per_second: self.rest_heavy_rps, burst: self.rest_heavy_burst },
+            max_heavy_in_flight: self.rest_heavy_in_flight,
+            allowlist,
+        })
+    }
+
+    /// Returns the TLS configuration of the REST server, from the given configurations.
+    fn parse_rest_tls(&self) -> Result<Option<TlsConfig>> {
+        match (&self.rest_tls_cert, &self.rest_tls_key) {
+            (Some(cert_path), Some(key_path)) => {
+                for path in [cert_path, key_path] {
+                    if !path.is_file() {
+                        bail!("The REST TLS file '{}' does not exist", path.display());
+                    }
+                }
+                Ok(Some(TlsConfig::new(cert_path.clone(), key_path.clone())))
+            }
+            (None, None) => Ok(None),
+            _ => bail!("Both --rest-tls-cert and --rest-tls-key must be specified to serve the REST server over HTTPS"),
+        }
+    }
+
+    /// Returns the size in bytes at which the logfile is rotated, from the given configurations.
+    fn parse_log_max_size(&self) -> Option<u64> {
+        match self.log_max_size {
+            0 => None,
+            megabytes => Some(megabytes.saturating_mul(1024 * 1024)),
+        }
+    }
+
     /// Returns the CDN to prefetch initial blocks from, from the given configurations.
     fn parse_cdn(&self) -> Option<String> {
         // Determine if the node type is not declared.
@@ -347,6 +462,10 @@ impl Start {
             true => None,
             false => Some(self.rest),
         };
+        // Parse the REST rate limits.
+        let rate_limits = self.parse_rest_limits()?;
+        // Parse the REST TLS configuration.
+        let rest_tls = self.parse_rest_tls()?;
 
         // If the display is not enabled, render the welcome message.
         if self.nodisplay {
@@ -362,9 +481,10 @@ impl Start {
             );
 
             // If the node is running a REST server, print the REST IP and JWT.
-            if node_type.is_validator() {
+            if !node_type.is_prover() {
                 if let Some(rest_ip) = rest_ip {
-                    println!("🌐 Starting the REST server at {}.\n", rest_ip.to_string().bold());
+                    let scheme = if rest_tls.is_some() { "https" } else { "http" };
+                    println!("🌐 Starting the REST server at {}.\n", format!("{scheme}://{rest_ip}").bold());
 
                     if let Ok(jwt_token) = snarkos_node_rest::Claims::new(account.address()).to_jwt_string() {
                         println!("🔑 Your one-time JWT token is {}\n", jwt_token.dimmed());
@@ -383,9 +503,9 @@ impl Start {
 
         // Initialize the node.
         match node_type {
-            NodeType::Validator => Node::new_validator(self.node, rest_ip, account, &trusted_peers, &trusted_validators, genesis, cdn, self.dev).await,
+            NodeType::Validator => Node::new_validator(self.node, rest_ip, rate_limits, rest_tls, account, &trusted_peers, &trusted_validators, genesis, cdn, self.indexer, self.dev).await,
             NodeType::Prover => Node::new_prover(self.node, account, &trusted_peers, genesis, self.dev).await,
-            NodeType::Client => Node::new_client(self.node, rest_ip, account, &trusted_peers, genesis, cdn, self.dev).await,
+            NodeType::Client => Node::new_client(self.node, rest_ip, rate_limits, rest_tls, account, &trusted_peers, genesis, cdn, self.indexer, self.dev).await,
         }
     }
 
@@ -514,6 +634,73 @@ mod tests {
         ]);
     }
 
+    #[test]
+    fn test_parse_rest_limits() {
+        let config = Start::try_parse_from(["snarkos"].iter()).unwrap();
+        let limits = config.parse_rest_limits().unwrap();
+        assert_eq!(limits.standard, Quota { per_second: 50, burst: 100 });
+        assert_eq!(limits.heavy, Quota { per_second: 5, burst: 10 });
+        assert_eq!(limits.max_heavy_in_flight, 32);
+        assert!(limits.allowlist.is_empty());
+
+        let config = Start::try_parse_from(
+            ["snarkos", "--rest-rps", "0", "--rest-heavy-in-flight", "4", "--rest-allowlist", "1.2.3.4,::1"].iter(),
+        )
+        .unwrap();
+        let limits = config.parse_rest_limits().unwrap();
+        assert_eq!(limits.standard.per_second, 0);
+        assert_eq!(limits.max_heavy_in_flight, 4);
+        assert_eq!(limits.allowlist, vec![IpAddr::from_str("1.2.3.4").unwrap(), IpAddr::from_str("::1").unwrap()]);
+
+        let config = Start::try_parse_from(["snarkos", "--rest-allowlist", "1.2.3.4:5"].iter()).unwrap();
+        assert!(config.parse_rest_limits().is_err());
+    }
+
+    #[test]
+    fn test_parse_rest_tls() {
+        let config = Start::try_parse_from(["snarkos"].iter()).unwrap();
+        assert!(config.parse_rest_tls().unwrap().is_none());
+
+        // The certificate and key must be specified together.
+        let config = Start::try_parse_from(["snarkos", "--rest-tls-cert", "cert.pem"].iter()).unwrap();
+        assert!(config.parse_rest_tls().is_err());
+        let config = Start::try_parse_from(["snarkos", "--rest-tls-key", "key.pem"].iter()).unwrap();
+        assert!(config.parse_rest_tls().is_err());
+
+        // The files must exist.
+        let cert_path = std::env::temp_dir().join(format!("snarkos_test_rest_cert_{}.pem", std::process::id()));
+        let key_path = std::env::temp_dir().join(format!("snarkos_test_rest_key_{}.pem", std::process::id()));
+        let args =
+            ["snarkos", "--rest-tls-cert", cert_path.to_str().unwrap(), "--rest-tls-key", key_path.to_str().unwrap()];
+        let config = Start::try_parse_from(args.iter()).unwrap();
+        assert!(config.parse_rest_tls().is_err());
+
+        std::fs::write(&cert_path, "").unwrap();
+        std::fs::write(&key_path, "").unwrap();
+        let tls = config.parse_rest_tls().unwrap().unwrap();
+        assert_eq!(tls.cert_path, cert_path);
+        assert_eq!(tls.key_path, key_path);
+
+        std::fs::remove_file(cert_path).unwrap();
+        std::fs::remove_file(key_path).unwrap();
+    }
+
+    #[test]
+    fn test_parse_log_options() {
+        let config = Start::try_parse_from(["snarkos"].iter()).unwrap();
+        assert_eq!(config.log_format, LogFormat::Text);
+        assert_eq!(config.log_rotation, LogRotation::Never);
+        assert_eq!(config.parse_log_max_size(), None);
+
+        let args = ["snarkos", "--log-format", "json", "--log-rotation", "daily", "--log-max-size", "100"];
+        let config = Start::try_parse_from(args.iter()).unwrap();
+        assert_eq!(config.log_format, LogFormat::Json);
+        assert_eq!(config.log_rotation, LogRotation::Daily);
+        assert_eq!(config.parse_log_max_size(), Some(100 * 1024 * 1024));
+
+        assert!(Start::try_parse_from(["snarkos", "--log-format", "yaml"].iter()).is_err());
+    }
+
     #[test]
     fn test_parse_cdn() {
         // Validator (Prod)
@@ -12,6 +12,10 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+use anyhow::{bail, Result};
+use regex::Regex;
+use regex_syntax::hir::{Class, Hir, HirKind};
+
 pub const BECH32M_CHARSET: &str = "qpzry9x8gf2tvdw0s3jn54khce6mua7l1";
 
 /// Check if a string is a valid bech32m character set.
@@ -56,6 +60,114 @@ pub fn has_vanity_string(s: &str, vanity: &str) -> bool {
     data.starts_with(vanity) || data.ends_with(vanity)
 }
 
+/// A pattern to match against the data part of a bech32m string.
+#[derive(Clone, Debug)]
+pub enum VanityPattern {
+    /// The data part starts with the given string.
+    Prefix(String),
+    /// The data part ends with the given string.
+    Suffix(String),
+    /// The data part starts or ends with the given string.
+    PrefixOrSuffix(String),
+    /// The data part matches the given regular expression.
+    Regex(Regex),
+}
+
+impl VanityPattern {
+    /// Initializes a new prefix pattern, ensuring it is in the bech32m character set.
+    pub fn prefix(prefix: &str) -> Result<Self> {
+        ensure_in_bech32m_charset(prefix)?;
+        Ok(Self::Prefix(prefix.to_string()))
+    }
+
+    /// Initializes a new suffix pattern, ensuring it is in the bech32m character set.
+    pub fn suffix(suffix: &str) -> Result<Self> {
+        ensure_in_bech32m_charset(suffix)?;
+        Ok(Self::Suffix(suffix.to_string()))
+    }
+
+    /// Initializes a new prefix-or-suffix pattern, ensuring it is in the bech32m character set.
+    pub fn prefix_or_suffix(vanity: &str) -> Result<Self> {
+        ensure_in_bech32m_charset(vanity)?;
+        Ok(Self::PrefixOrSuffix(vanity.to_string()))
+    }
+
+    /// Initializes a new regex pattern, ensuring it can match a string in the bech32m character set.
+    pub fn regex(pattern: &str) -> Result<Self> {
+        // Parse the pattern into its high-level intermediate representation.
+        let hir = match regex_syntax::parse(pattern) {
+            Ok(hir) => hir,
+            Err(error) => bail!("The vanity regex '{pattern}' is invalid - {error}"),
+        };
+        // Ensure every required literal and character class can be satisfied by the bech32m character set.
+        if !is_satisfiable_in_bech32m_charset(&hir) {
+            bail!(
+                "The vanity regex '{pattern}' cannot match any bech32m string. Try using characters from the bech32m character set: {BECH32M_CHARSET}"
+            );
+        }
+        Ok(Self::Regex(Regex::new(pattern)?))
+    }
+
+    /// Returns `true` if the data part of the given bech32m string matches the pattern.
+    pub fn is_match(&self, s: &str) -> bool {
+        match self {
+            Self::Prefix(prefix) => data_part(s).map_or(false, |data| data.starts_with(prefix.as_str())),
+            Self::Suffix(suffix) => data_part(s).map_or(false, |data| data.ends_with(suffix.as_str())),
+            Self::PrefixOrSuffix(vanity) => has_vanity_string(s, vanity),
+            Self::Regex(regex) => data_part(s).map_or(false, |data| regex.is_match(data)),
+        }
+    }
+
+    /// Returns the expected number of attempts to find a match, if it can be estimated.
+    pub fn expected_attempts(&self) -> Option<f64> {
+        // Each character in the data part encodes 5 bits.
+        let num_characters = 32f64;
+        match self {
+            Self::Prefix(string) | Self::Suffix(string) => Some(num_characters.powi(string.len() as i32)),
+            // Note: For strings longer than one character, a match at the start and end are (roughly) disjoint events.
+            Self::PrefixOrSuffix(string) => Some(num_characters.powi(string.len() as i32) / 2.0),
+            Self::Regex(_) => None,
+        }
+    }
+}
+
+/// Returns the data part of a bech32m string, if the string is well-formed.
+fn data_part(s: &str) -> Option<&str> {
+    match s.split_once('1') {
+        Some((hrp, data)) if !hrp.is_empty() && !data.is_empty() => Some(data),
+        _ => None,
+    }
+}
+
+/// Ensures the given string is in the bech32m character set.
+fn ensure_in_bech32m_charset(s: &str) -> Result<()> {
+    if !is_in_bech32m_charset(s) {
+        bail!(
+            "The vanity string '{s}' contains invalid bech32m characters. Try using characters from the bech32m character set: {BECH32M_CHARSET}"
+        );
+    }
+    Ok(())
+}
+
+/// Returns `true` if the given regex can match at least one string in the bech32m character set.
+fn is_satisfiable_in_bech32m_charset(hir: &Hir) -> bool {
+    match hir.kind() {
+        HirKind::Empty | HirKind::Look(_) => true,
+        HirKind::Literal(literal) => core::str::from_utf8(&literal.0).map_or(false, is_in_bech32m_charset),
+        HirKind::Class(Class::Unicode(class)) => {
+            BECH32M_CHARSET.chars().any(|c| class.ranges().iter().any(|range| range.start() <= c && c <= range.end()))
+        }
+        HirKind::Class(Class::Bytes(class)) => {
+            BECH32M_CHARSET.bytes().any(|b| class.ranges().iter().any(|range| range.start() <= b && b <= range.end()))
+        }
+        // A repetition with a minimum of zero is satisfied by the empty string.
+        HirKind::Repetition(repetition) => repetition.min == 0 || is_satisfiable_in_bech32m_charset(&repetition.sub),
+        HirKind::Capture(capture) => is_satisfiable_in_bech32m_charset(&capture.sub),
+        HirKind::Concat(hirs) => hirs.iter().all(is_satisfiable_in_bech32m_charset),
+        HirKind::Alternation(hirs) => hirs.iter().any(is_satisfiable_in_bech32m_charset),
+    }
+}
+
 #[test]
 fn test_is_in_bech32m_charset() {
     assert!(is_in_bech32m_charset("qpzry9x8gf2tvdw0s3jn54khce6mua7l1qpzry9x8gf2tvdw0s3jn54khce6mua7l1"));
@@ -70,3 +182,31 @@ fn test_has_vanity_string() {
     assert!(has_vanity_string("myhrp11234myvanitystring", "myvanitystring"));
     assert!(!has_vanity_string("myhrp1anotherstring1234", "myvanitystring"));
 }
+
+#[test]
+fn test_vanity_pattern() {
+    // Ensure the patterns are validated against the bech32m character set.
+    assert!(VanityPattern::prefix("qpzry").is_ok());
+    assert!(VanityPattern::prefix("bio").is_err());
+    assert!(VanityPattern::suffix("qpzry").is_ok());
+    assert!(VanityPattern::suffix("bio").is_err());
+    assert!(VanityPattern::prefix_or_suffix("qpzry").is_ok());
+    assert!(VanityPattern::prefix_or_suffix("bio").is_err());
+    assert!(VanityPattern::regex("^(aleo|a1e0)+").is_ok());
+    assert!(VanityPattern::regex("^[qp]{2}.*x$").is_ok());
+    assert!(VanityPattern::regex("^b").is_err());
+    assert!(VanityPattern::regex("^[bio]").is_err());
+    assert!(VanityPattern::regex("^(").is_err());
+
+    // Ensure the patterns are matched against the data part.
+    let address = "aleo1qpzry9x8gf2tvdw0s3jn54khce6mua7l";
+    assert!(VanityPattern::prefix("qpz").unwrap().is_match(address));
+    assert!(!VanityPattern::prefix("ua7l").unwrap().is_match(address));
+    assert!(VanityPattern::suffix("ua7l").unwrap().is_match(address));
+    assert!(!VanityPattern::suffix("qpz").unwrap().is_match(address));
+    assert!(VanityPattern::prefix_or_suffix("qpz").unwrap().is_match(address));
+    assert!(VanityPattern::prefix_or_suffix("ua7l").unwrap().is_match(address));
+    assert!(VanityPattern::regex("^qp.*7l$").unwrap().is_match(address));
+    assert!(!VanityPattern::regex("^ua7l").unwrap().is_match(address));
+    assert!(VanityPattern::regex("^aleo").is_err());
+}
@@ -0,0 +1,192 @@
+// Copyright (C) 2019-2023 Aleo Systems Inc.
+// This file is part of the snarkOS library.
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at:
+// http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use clap::ValueEnum;
+use std::{
+    fs::{self, File},
+    io::{self, Write},
+    path::{Path, PathBuf},
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+/// The interval at which the log file is rotated.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, ValueEnum)]
+pub enum LogRotation {
+    /// The log file is not rotated by time.
+    Never,
+    /// The log file is rotated every hour.
+    Hourly,
+    /// The log file is rotated every day.
+    Daily,
+}
+
+impl LogRotation {
+    /// Returns the index of the period containing the given UNIX timestamp, in seconds.
+    fn period(&self, timestamp: u64) -> u64 {
+        match self {
+            Self::Never => 0,
+            Self::Hourly => timestamp / 3600,
+            Self::Daily => timestamp / 86400,
+        }
+    }
+}
+
+/// A log file which is rotated by size and time, keeping the given number of rotated files.
+///
+/// The rotated files are renamed to `{logfile}.{timestamp_in_millis}`, so they sort by age.
+pub struct LogFile {
+    /// The path to the log file.
+    path: PathBuf,
+    /// The size in bytes at which the log file is rotated, if any.
+    max_size: Option<u64>,
+    /// The interval at which the log file is rotated.
+    rotation: LogRotation,
+    /// The number of rotated files to keep.
+    max_files: usize,
+    /// The open log file.
+    file: File,
+    /// The size of the open log file.
+    size: u64,
+    /// The rotation period of the open log file.
+    period: u64,
+}
+
+impl LogFile {
+    /// Opens the log file at the given path, appending to it if it exists.
+    pub fn open(path: &Path, max_size: Option<u64>, rotation: LogRotation, max_files: usize) -> io::Result<Self> {
+        let file = File::options().append(true).create(true).open(path)?;
+        let size = file.metadata()?.len();
+        let period = rotation.period(now());
+        Ok(Self { path: path.to_path_buf(), max_size, rotation, max_files, file, size, period })
+    }
+
+    /// Returns `true` if the log file must be rotated before writing the given number of bytes.
+    fn should_rotate(&self, len: usize, timestamp: u64) -> bool {
+        let exceeds_size = self.max_size.map_or(false, |max_size| self.size > 0 && self.size + len as u64 > max_size);
+        exceeds_size || self.rotation.period(timestamp) != self.period
+    }
+
+    /// Renames the log file, opens a new log file, and removes the rotated files beyond the retention.
+    fn rotate(&mut self, timestamp: u64) -> io::Result<()> {
+        self.file.flush()?;
+        let millis = SystemTime::now().duration_since(UNIX_EPOCH).map_or(0, |duration| duration.as_millis());
+        fs::rename(&self.path, self.rotated_path(millis))?;
+
+        self.file = File::options().append(true).create(true).open(&self.path)?;
+        self.size = 0;
+        self.period = self.rotation.period(timestamp);
+
+        self.remove_expired_files()
+    }
+
+    /// Returns the path of the log file, rotated at the given UNIX timestamp, in milliseconds.
+    fn rotated_path(&self, millis: u128) -> PathBuf {
+        let mut path = self.path.clone().into_os_string();
+        path.push(format!(".{millis}"));
+        PathBuf::from(path)
+    }
+
+    /// Removes the oldest rotated files, keeping `max_files` of them.
+    fn remove_expired_files(&self) -> io::Result<()> {
+        let (Some(directory), Some(file_name)) = (self.path.parent(), self.path.file_name()) else {
+            return Ok(());
+        };
+        let prefix = format!("{}.", file_name.to_string_lossy());
+
+        // Collect the rotated files, by the timestamp of their rotation.
+        let mut rotated_files = fs::read_dir(directory)?
+            .filter_map(|entry| entry.ok())
+            .filter_map(|entry| {
+                let name = entry.file_name().to_string_lossy().into_owned();
+                let millis = name.strip_prefix(&prefix)?.parse::<u128>().ok()?;
+                Some((millis, entry.path()))
+            })
+            .collect::<Vec<_>>();
+        rotated_files.sort_unstable();
+
+        let num_expired = rotated_files.len().saturating_sub(self.max_files);
+        for (_, path) in rotated_files.into_iter().take(num_expired) {
+            fs::remove_file(path)?;
+        }
+        Ok(())
+    }
+}
+
+impl Write for LogFile {
+    /// Writes the given buffer into the log file, rotating it first if needed.
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        let timestamp = now();
+        if self.should_rotate(buf.len(), timestamp) {
+            self.rotate(timestamp)?;
+        }
+        let written = self.file.write(buf)?;
+        self.size += written as u64;
+        Ok(written)
+    }
+
+    /// Flushes the log file.
+    fn flush(&mut self) -> io::Result<()> {
+        self.file.flush()
+    }
+}
+
+/// Returns the current UNIX timestamp, in seconds.
+fn now() -> u64 {
+    SystemTime::now().duration_since(UNIX_EPOCH).map_or(0, |duration| duration.as_secs())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Returns the rotated files of the given log file.
+    fn rotated_files(path: &Path) -> Vec<PathBuf> {
+        let prefix = format!("{}.", path.file_name().unwrap().to_string_lossy());
+        fs::read_dir(path.parent().unwrap())
+            .unwrap()
+            .map(|entry| entry.unwrap())
+            .filter(|entry| entry.file_name().to_string_lossy().starts_with(&prefix))
+            .map(|entry| entry.path())
+            .collect()
+    }
+
+    #[test]
+    fn test_rotate_by_size() {
+        let directory = std::env::temp_dir().join(format!("snarkos_test_log_file_{}", std::process::id()));
+        fs::create_dir_all(&directory).unwrap();
+        let path = directory.join("snarkos.log");
+
+        let mut log_file = LogFile::open(&path, Some(10), LogRotation::Never, 2).unwrap();
+        for _ in 0..5 {
+            log_file.write_all(b"0123456789").unwrap();
+            // Ensure the rotated files have distinct timestamps.
+            std::thread::sleep(std::time::Duration::from_millis(2));
+        }
+
+        // Each write after the first one rotates the log file, and only 2 rotated files are kept.
+        assert_eq!(fs::read(&path).unwrap(), b"0123456789");
+        assert_eq!(rotated_files(&path).len(), 2);
+
+        fs::remove_dir_all(directory).unwrap();
+    }
+
+    #[test]
+    fn test_rotation_period() {
+        assert_eq!(LogRotation::Never.period(100_000), 0);
+        assert_eq!(LogRotation::Hourly.period(7199), 1);
+        assert_eq!(LogRotation::Hourly.period(7200), 2);
+        assert_eq!(LogRotation::Daily.period(86399), 0);
+        assert_eq!(LogRotation::Daily.period(86400), 1);
+    }
+}
@@ -12,18 +12,42 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
-use crate::helpers::LogWriter;
+use crate::helpers::{LogFile, LogRotation, LogWriter};
+use snarkos_node::rest::LogFilter;
 
+use anyhow::Result;
+use clap::ValueEnum;
 use crossterm::tty::IsTty;
-use std::{fs::File, io, path::Path};
+use opentelemetry::{
+    sdk::{trace, Resource},
+    KeyValue,
+};
+use opentelemetry_otlp::WithExportConfig;
+use std::{io, path::Path, sync::Mutex};
 use tokio::sync::mpsc;
+use tracing::Subscriber;
 use tracing_subscriber::{
+    filter,
     layer::{Layer, SubscriberExt},
+    registry::LookupSpan,
+    reload,
     util::SubscriberInitExt,
     EnvFilter,
 };
 
-/// Initializes the logger.
+/// The format of the logs.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, ValueEnum)]
+pub enum LogFormat {
+    /// Human-readable lines.
+    Text,
+    /// One JSON object per event, with the event fields (e.g. `peer`, `round`, `height`) as keys.
+    Json,
+}
+
+/// The maximum verbosity of the logger.
+pub const MAX_VERBOSITY: u8 = 7;
+
+/// Returns the log filter directives for the given verbosity.
 ///
 /// ```ignore
 /// 0 => info
@@ -34,46 +58,74 @@ use tracing_subscriber::{
 /// 5 => info, debug, trace, snarkos_node_router=trace
 /// 6 => info, debug, trace, snarkos_node_tcp=trace
 /// ```
-pub fn initialize_logger<P: AsRef<Path>>(verbosity: u8, nodisplay: bool, logfile: P) -> mpsc::Receiver<Vec<u8>> {
-    match verbosity {
-        0 => std::env::set_var("RUST_LOG", "info"),
-        1 => std::env::set_var("RUST_LOG", "debug"),
-        2.. => std::env::set_var("RUST_LOG", "trace"),
+pub fn verbosity_directives(verbosity: u8) -> String {
+    let level = match verbosity {
+        0 => "info",
+        1 => "debug",
+        2.. => "trace",
     };
+    let gateway = if verbosity > 3 { "trace" } else { "debug" };
+    let narwhal = if verbosity > 4 { "trace" } else { "debug" };
+    let router = if verbosity > 5 { "trace" } else { "off" };
+    let tcp = if verbosity > 6 { "trace" } else { "off" };
 
+    format!(
+        "{level},snarkos_node_narwhal::gateway={gateway},snarkos_node_narwhal={narwhal},snarkos_node_router={router},snarkos_node_tcp={tcp}"
+    )
+}
+
+/// Returns the log filter for the given directives, which filters out the undesirable logs of dependencies,
+/// unless the directives specify their level.
+fn log_filter(directives: &str) -> Result<EnvFilter> {
+    Ok(EnvFilter::builder()
+        .parse(format!("mio=off,tokio_util=off,hyper=off,reqwest=off,want=off,warp=off,{directives}"))?)
+}
+
+/// Returns the layer which exports the spans of snarkOS to the OTLP collector at the given endpoint.
+///
+/// Note: This must be called within a Tokio runtime, which runs the batch exporter.
+fn otlp_layer<S>(endpoint: &str) -> Result<impl Layer<S>>
+where
+    S: Subscriber + for<'span> LookupSpan<'span>,
+{
+    let tracer = opentelemetry_otlp::new_pipeline()
+        .tracing()
+        .with_exporter(opentelemetry_otlp::new_exporter().tonic().with_endpoint(endpoint))
+        .with_trace_config(trace::config().with_resource(Resource::new([KeyValue::new("service.name", "snarkos")])))
+        .install_batch(opentelemetry::runtime::Tokio)?;
+
+    // Export only the spans of snarkOS, as the events are already in the logs.
+    let spans = filter::filter_fn(|metadata| metadata.is_span() && metadata.target().starts_with("snarkos"));
+    Ok(tracing_opentelemetry::layer().with_tracer(tracer).with_filter(spans))
+}
+
+/// Initializes the logger, and returns the receiver of the logs for the display,
+/// and the handle which changes the log filter at runtime.
+///
+/// The logfile is rotated once it exceeds `max_size` bytes, or at each `rotation` interval,
+/// and the `max_files` most recent rotated logfiles are kept.
+/// If an OTLP endpoint is given, the spans are exported to it.
+#[allow(clippy::too_many_arguments)]
+pub fn initialize_logger<P: AsRef<Path>>(
+    verbosity: u8,
+    nodisplay: bool,
+    format: LogFormat,
+    logfile: P,
+    max_size: Option<u64>,
+    rotation: LogRotation,
+    max_files: usize,
+    otlp_endpoint: Option<&str>,
+) -> (mpsc::Receiver<Vec<u8>>, LogFilter) {
     // Filter out undesirable logs. (unfortunately EnvFilter cannot be cloned)
-    let [filter, filter2] = std::array::from_fn(|_| {
-        let filter = EnvFilter::from_default_env()
-            .add_directive("mio=off".parse().unwrap())
-            .add_directive("tokio_util=off".parse().unwrap())
-            .add_directive("hyper=off".parse().unwrap())
-            .add_directive("reqwest=off".parse().unwrap())
-            .add_directive("want=off".parse().unwrap())
-            .add_directive("warp=off".parse().unwrap());
-
-        let filter = if verbosity > 3 {
-            filter.add_directive("snarkos_node_narwhal::gateway=trace".parse().unwrap())
-        } else {
-            filter.add_directive("snarkos_node_narwhal::gateway=debug".parse().unwrap())
-        };
-
-        let filter = if verbosity > 4 {
-            filter.add_directive("snarkos_node_narwhal=trace".parse().unwrap())
-        } else {
-            filter.add_directive("snarkos_node_narwhal=debug".parse().unwrap())
-        };
-
-        let filter = if verbosity > 5 {
-            filter.add_directive("snarkos_node_router=trace".parse().unwrap())
-        } else {
-            filter.add_directive("snarkos_node_router=off".parse().unwrap())
-        };
-
-        if verbosity > 6 {
-            filter.add_directive("snarkos_node_tcp=trace".parse().unwrap())
-        } else {
-            filter.add_directive("snarkos_node_tcp=off".parse().unwrap())
-        }
+    let directives = verbosity_directives(verbosity);
+    let (filter, filter_handle) = reload::Layer::new(log_filter(&directives).expect("Invalid log filter"));
+    let (filter2, filter2_handle) = reload::Layer::new(log_filter(&directives).expect("Invalid log filter"));
+
+    // Initialize the handle which reloads both filters.
+    let log_filter = LogFilter::new(directives, move |directives| {
+        filter_handle.reload(self::log_filter(directives)?)?;
+        filter2_handle.reload(self::log_filter(directives)?)?;
+        Ok(())
     });
 
     // Create the directories tree for a logfile if it doesn't exist.
@@ -83,8 +135,8 @@ pub fn initialize_logger<P: AsRef<Path>>(verbosity: u8, nodisplay: bool, logfile
             .expect("Failed to create a directories: '{logfile_dir}', please check if user has permissions");
     }
     // Create a file to write logs to.
-    let logfile =
-        File::options().append(true).create(true).open(logfile).expect("Failed to open the file for writing logs");
+    let logfile = LogFile::open(logfile.as_ref(), max_size, rotation, max_files)
+        .expect("Failed to open the file for writing logs");
 
     // Initialize the log channel.
     let (log_sender, log_receiver) = mpsc::channel(1024);
@@ -95,27 +147,68 @@ pub fn initialize_logger<P: AsRef<Path>>(verbosity: u8, nodisplay: bool, logfile
         false => Some(log_sender),
     };
 
+    // Add layer using LogWriter for stdout / terminal.
+    // The display always renders text, so the JSON format only applies to stdout without the display.
+    // The display always receives the targets, to filter the logs by module.
+    let display = log_sender.is_some();
+    let ansi = !display && io::stdout().is_tty();
+    let json = format == LogFormat::Json && !display;
+    let terminal_layer = tracing_subscriber::fmt::Layer::default()
+        .with_writer(move || LogWriter::new(&log_sender))
+        .with_target(verbosity > 2 || display);
+    let terminal_layer = match json {
+        true => terminal_layer.json().flatten_event(true).boxed(),
+        false => terminal_layer.with_ansi(ansi).boxed(),
+    };
+
+    // Add layer redirecting logs to the file.
+    let file_layer = tracing_subscriber::fmt::Layer::default()
+        .with_ansi(false)
+        .with_writer(Mutex::new(logfile))
+        .with_target(verbosity > 2);
+    let file_layer = match format {
+        LogFormat::Json => file_layer.json().flatten_event(true).boxed(),
+        LogFormat::Text => file_layer.boxed(),
+    };
+
+    // Add layer exporting the spans to the OTLP collector.
+    let otlp_layer =
+        otlp_endpoint.map(|endpoint| otlp_layer(endpoint).expect("Failed to initialize the OTLP exporter"));
+
     // Initialize tracing.
     let _ = tracing_subscriber::registry()
-        .with(
-            // Add layer using LogWriter for stdout / terminal
-            tracing_subscriber::fmt::Layer::default()
-                .with_ansi(log_sender.is_none() && io::stdout().is_tty())
-                .with_writer(move || LogWriter::new(&log_sender))
-                .with_target(verbosity > 2)
-                .with_filter(filter),
-        )
-        .with(
-            // Add layer redirecting logs to the file
-            tracing_subscriber::fmt::Layer::default()
-                .with_ansi(false)
-                .with_writer(logfile)
-                .with_target(verbosity > 2)
-                .with_filter(filter2),
-        )
+        .with(terminal_layer.with_filter(filter))
+        .with(file_layer.with_filter(filter2))
+        .with(otlp_layer)
         .try_init();
 
-    log_receiver
+    (log_receiver, log_filter)
+}
+
+/// Raises the verbosity of the logger by one level on each `SIGUSR1`, wrapping around to the given verbosity.
+#[cfg(target_family = "unix")]
+pub fn handle_log_signals(log_filter: LogFilter, verbosity: u8) {
+    use tokio::signal::unix::{signal, SignalKind};
+    use tracing::{info, warn};
+
+    let mut signals = match signal(SignalKind::user_defined1()) {
+        Ok(signals) => signals,
+        Err(error) => {
+            warn!("Unable to listen for SIGUSR1 to change the log level - {error}");
+            return;
+        }
+    };
+
+    tokio::spawn(async move {
+        let mut current = verbosity;
+        while signals.recv().await.is_some() {
+            current = if current >= MAX_VERBOSITY { verbosity } else { current + 1 };
+            match log_filter.set_directives(&verbosity_directives(current)) {
+                Ok(()) => info!("Changed the log verbosity to {current}"),
+                Err(error) => warn!("Unable to change the log verbosity - {error}"),
+            }
+        }
+    });
 }
 
 /// Returns the welcome message as a string.
@@ -143,3 +236,22 @@ pub fn welcome_message() -> String {
     output += &"👋 Welcome to Aleo! We thank you for running a node and supporting privacy.\n".bold();
     output
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_verbosity_directives() {
+        for verbosity in 0..=MAX_VERBOSITY {
+            assert!(log_filter(&verbosity_directives(verbosity)).is_ok());
+        }
+        assert!(verbosity_directives(0).starts_with("info,"));
+        assert!(verbosity_directives(3).contains("snarkos_node_narwhal::gateway=debug"));
+        assert!(verbosity_directives(4).contains("snarkos_node_narwhal::gateway=trace"));
+        assert!(verbosity_directives(MAX_VERBOSITY).contains("snarkos_node_tcp=trace"));
+
+        // Invalid directives are rejected.
+        assert!(log_filter("snarkos_node_narwhal=loud").is_err());
+    }
+}
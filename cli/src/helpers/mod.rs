@@ -15,6 +15,9 @@
 mod bech32m;
 pub use bech32m::*;
 
+pub mod log_file;
+pub use log_file::*;
+
 mod log_writer;
 use log_writer::*;
 
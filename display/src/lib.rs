@@ -96,6 +96,8 @@ impl<N: Network> Display<N> {
     fn render<B: Backend>(&mut self, terminal: &mut Terminal<B>) -> io::Result<()> {
         let mut last_tick = Instant::now();
         loop {
+            // Receive the new logs, even if the logs page is not shown.
+            self.logs.update();
             terminal.draw(|f| self.draw(f))?;
 
             // Set the timeout duration.
@@ -103,6 +105,10 @@ impl<N: Network> Display<N> {
 
             if event::poll(timeout)? {
                 if let Event::Key(key) = event::read()? {
+                    // Let the logs page handle its keys first.
+                    if self.tabs.index == 1 && self.logs.handle_key(key) {
+                        continue;
+                    }
                     match key.code {
                         KeyCode::Esc => {
                             // // TODO (howardwu): @ljedrz to implement a wrapping scope for Display within Node/Server.
@@ -172,6 +178,7 @@ impl<N: Network> Display<N> {
         match self.tabs.index {
             0 => Overview.draw(f, chunks[1], &self.node),
             1 => self.logs.draw(f, chunks[1]),
+            2 => Consensus.draw(f, chunks[1], &self.node),
             _ => unreachable!(),
         };
     }
@@ -0,0 +1,189 @@
+// Copyright (C) 2019-2023 Aleo Systems Inc.
+// This file is part of the snarkOS library.
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at:
+// http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use snarkos_node::{
+    narwhal::{ledger_service::LedgerService, BFT},
+    Node,
+};
+use snarkvm::prelude::{Address, Network};
+
+use std::collections::HashMap;
+use tui::{
+    backend::Backend,
+    layout::{Constraint, Direction, Layout, Rect},
+    style::{Color, Modifier, Style},
+    text::{Span, Spans},
+    widgets::{Block, Borders, Cell, Gauge, Paragraph, Row, Table},
+    Frame,
+};
+
+/// The width of the column with the committee members.
+const MEMBER_WIDTH: u16 = 20;
+/// The width of the column of each round.
+const ROUND_WIDTH: u16 = 6;
+/// The maximum number of rounds in the view.
+const MAX_ROUNDS: u16 = 50;
+
+pub(crate) struct Consensus;
+
+impl Consensus {
+    pub(crate) fn draw<B: Backend, N: Network>(&self, f: &mut Frame<B>, area: Rect, node: &Node<N>) {
+        // Initialize the layout of the page.
+        let chunks = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([Constraint::Length(3), Constraint::Min(0), Constraint::Length(3)].as_ref())
+            .split(area);
+
+        let Some(consensus) = node.consensus() else {
+            let text = format!("Not available for a {} node", node.node_type());
+            f.render_widget(
+                Paragraph::new(text).block(Block::default().borders(Borders::ALL).title("Consensus")),
+                area,
+            );
+            return;
+        };
+        let bft = consensus.bft();
+        let current_round = bft.primary().current_round();
+
+        /* Proposal */
+
+        let title = "Proposal";
+        let proposal =
+            bft.primary().proposed_batch().read().as_ref().map(|proposal| (proposal.round(), proposal.signers()));
+        match proposal.map(|(round, signers)| (round, signers, bft.ledger().get_previous_committee_for_round(round))) {
+            Some((round, signers, Ok(committee))) => {
+                let stake = signers.iter().map(|signer| committee.get_stake(*signer)).sum::<u64>();
+                let quorum = committee.quorum_threshold().max(1);
+                let label = format!(
+                    "Round {round} - {}/{} signatures, {stake}/{quorum} stake for quorum",
+                    signers.len(),
+                    committee.members().len()
+                );
+                let gauge = Gauge::default()
+                    .block(Block::default().borders(Borders::ALL).title(title))
+                    .gauge_style(Style::default().fg(Color::Green).bg(Color::Black))
+                    .ratio((stake as f64 / quorum as f64).min(1.0))
+                    .label(label);
+                f.render_widget(gauge, chunks[0]);
+            }
+            Some((round, _, Err(error))) => {
+                let text = format!("Unable to retrieve the committee for round {round} - {error}");
+                f.render_widget(
+                    Paragraph::new(text).block(Block::default().borders(Borders::ALL).title(title)),
+                    chunks[0],
+                );
+            }
+            None => {
+                let text = "No batch is being proposed";
+                f.render_widget(
+                    Paragraph::new(text).block(Block::default().borders(Borders::ALL).title(title)),
+                    chunks[0],
+                );
+            }
+        }
+
+        /* DAG */
+
+        let title = format!("DAG (round {current_round}, last committed round {})", bft.last_committed_round());
+        let block = Block::default().borders(Borders::ALL).title(title);
+        match bft.ledger().get_previous_committee_for_round(current_round) {
+            Ok(committee) => {
+                // Determine the rounds which fit in the view.
+                let num_rounds = (chunks[1].width.saturating_sub(2 + MEMBER_WIDTH) / ROUND_WIDTH).clamp(1, MAX_ROUNDS);
+                let rounds = current_round.saturating_sub(u64::from(num_rounds) - 1)..=current_round;
+
+                // Retrieve the certificates and the leader of each round.
+                let columns = rounds.clone().map(|round| self.round_column(bft, round)).collect::<Vec<_>>();
+
+                let header = Row::new(
+                    Some(Cell::from("Member")).into_iter().chain(rounds.map(|round| Cell::from(round.to_string()))),
+                )
+                .style(Style::default().fg(Color::Yellow).add_modifier(Modifier::BOLD));
+
+                let rows = committee.members().keys().map(|member| {
+                    let cells = columns.iter().map(|(certificates, leader)| {
+                        let is_leader = leader.as_ref() == Some(member);
+                        let (symbol, color) = match certificates.get(member) {
+                            Some(true) => ("●", Color::Green),
+                            Some(false) => ("●", Color::White),
+                            None => ("✗", Color::Red),
+                        };
+                        let style = match is_leader {
+                            true => Style::default().fg(color).add_modifier(Modifier::REVERSED | Modifier::BOLD),
+                            false => Style::default().fg(color),
+                        };
+                        Cell::from(Span::styled(symbol, style))
+                    });
+                    Row::new(Some(Cell::from(short_address(member))).into_iter().chain(cells))
+                });
+
+                let widths = Some(Constraint::Length(MEMBER_WIDTH))
+                    .into_iter()
+                    .chain((0..num_rounds).map(|_| Constraint::Length(ROUND_WIDTH)))
+                    .collect::<Vec<_>>();
+                f.render_widget(Table::new(rows).header(header).block(block).widths(&widths), chunks[1]);
+            }
+            Err(error) => {
+                let text = format!("Unable to retrieve the committee for round {current_round} - {error}");
+                f.render_widget(Paragraph::new(text).block(block), chunks[1]);
+            }
+        }
+
+        /* Legend */
+
+        let legend = Spans::from(vec![
+            Span::styled("●", Style::default().fg(Color::Green)),
+            Span::raw(" committed  "),
+            Span::styled("●", Style::default().fg(Color::White)),
+            Span::raw(" certified  "),
+            Span::styled("✗", Style::default().fg(Color::Red)),
+            Span::raw(" missing  "),
+            Span::styled("●", Style::default().add_modifier(Modifier::REVERSED | Modifier::BOLD)),
+            Span::raw(" leader"),
+        ]);
+        f.render_widget(
+            Paragraph::new(legend).block(Block::default().borders(Borders::ALL).title("Legend")),
+            chunks[2],
+        );
+    }
+
+    /// Returns the authors of the certificates in the given round, with `true` if the certificate was committed,
+    /// and the leader of the round, if it is an even round.
+    fn round_column<N: Network>(&self, bft: &BFT<N>, round: u64) -> (HashMap<Address<N>, bool>, Option<Address<N>>) {
+        let certificates = bft
+            .storage()
+            .get_certificates_for_round(round)
+            .iter()
+            .map(|certificate| (certificate.author(), bft.is_recently_committed(round, certificate.certificate_id())))
+            .collect();
+        let leader = match round % 2 == 0 {
+            true => bft
+                .ledger()
+                .get_previous_committee_for_round(round)
+                .and_then(|committee| committee.get_leader(round))
+                .ok(),
+            false => None,
+        };
+        (certificates, leader)
+    }
+}
+
+/// Returns the given address, shortened to fit the column of the committee members.
+fn short_address<N: Network>(address: &Address<N>) -> String {
+    let address = address.to_string();
+    match address.len() > 16 {
+        true => format!("{}…{}", &address[..10], &address[address.len() - 6..]),
+        false => address,
+    }
+}
@@ -12,63 +12,347 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
-use std::collections::VecDeque;
+use crossterm::event::{KeyCode, KeyEvent};
+use std::{
+    collections::{BTreeSet, VecDeque},
+    ops::Bound,
+};
 use tokio::sync::mpsc;
 use tui::{
     backend::Backend,
     layout::{Constraint, Direction, Layout, Rect},
+    style::{Color, Modifier, Style},
+    text::{Span, Spans},
     widgets::{Block, Borders, Paragraph},
     Frame,
 };
 
+/// The maximum number of log lines kept for scroll-back.
+const MAX_LOG_LINES: usize = 10_000;
+
+/// The level of a log line.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord)]
+enum Level {
+    Error,
+    Warn,
+    Info,
+    Debug,
+    Trace,
+}
+
+impl Level {
+    /// The levels, from the least to the most verbose.
+    const ALL: [Self; 5] = [Self::Error, Self::Warn, Self::Info, Self::Debug, Self::Trace];
+
+    /// Returns the level of the given token, if it is a level.
+    fn parse(token: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|level| level.as_str() == token)
+    }
+
+    /// Returns the level as it is printed in the logs.
+    const fn as_str(&self) -> &'static str {
+        match self {
+            Self::Error => "ERROR",
+            Self::Warn => "WARN",
+            Self::Info => "INFO",
+            Self::Debug => "DEBUG",
+            Self::Trace => "TRACE",
+        }
+    }
+
+    /// Returns the next more verbose level, wrapping around to the least verbose level.
+    fn next(&self) -> Self {
+        Self::ALL[(*self as usize + 1) % Self::ALL.len()]
+    }
+}
+
+/// A line of the logs.
+#[derive(Clone, Debug, PartialEq, Eq)]
+struct LogLine {
+    /// The level of the log, if known.
+    level: Option<Level>,
+    /// The module which emitted the log, if known.
+    module: Option<String>,
+    /// The text of the line.
+    text: String,
+}
+
+impl LogLine {
+    /// Parses the given log into lines, where the continuation lines inherit the level and module of the first line.
+    ///
+    /// A log is formatted as `{timestamp} {level} {spans}: {module}: {message}`, where the spans are optional.
+    fn parse(log: &str) -> Vec<Self> {
+        let mut tokens = log.split_whitespace().skip(1);
+        let level = tokens.next().and_then(Level::parse);
+        let module = tokens
+            .find(|token| {
+                token.len() > 1
+                    && token.ends_with(':')
+                    && token.chars().all(|c| c.is_ascii_alphanumeric() || c == '_' || c == ':')
+            })
+            .map(|token| token.trim_end_matches(':').to_string());
+
+        log.lines().map(|line| Self { level, module: module.clone(), text: line.to_string() }).collect()
+    }
+}
+
 pub(crate) struct Logs {
     log_receiver: mpsc::Receiver<Vec<u8>>,
-    log_cache: VecDeque<String>,
-    log_limit: usize,
+    /// The log lines, up to `MAX_LOG_LINES`.
+    log_cache: VecDeque<LogLine>,
+    /// The modules which emitted the logs.
+    modules: BTreeSet<String>,
+    /// The number of lines the view is scrolled up from the latest line.
+    scroll: usize,
+    /// If `true`, the view stays on the same lines as new logs arrive.
+    paused: bool,
+    /// The most verbose level shown.
+    level: Level,
+    /// The module shown, or all modules if `None`.
+    module: Option<String>,
+    /// The search, whose matches are highlighted.
+    search: String,
+    /// If `true`, the keys are typed into the search.
+    is_searching: bool,
+    /// The number of lines in the view, as of the last draw.
+    height: usize,
 }
 
 impl Logs {
     pub(crate) fn new(log_receiver: mpsc::Receiver<Vec<u8>>) -> Self {
-        let log_limit = 128; // an arbitrary number fitting the testing terminal room
+        Self {
+            log_receiver,
+            log_cache: VecDeque::with_capacity(MAX_LOG_LINES),
+            modules: Default::default(),
+            scroll: 0,
+            paused: false,
+            level: Level::Trace,
+            module: None,
+            search: String::new(),
+            is_searching: false,
+            height: 0,
+        }
+    }
 
-        Self { log_receiver, log_cache: VecDeque::with_capacity(log_limit), log_limit }
+    /// Receives the new logs, keeping the view on the same lines if it is paused or scrolled up.
+    pub(crate) fn update(&mut self) {
+        while let Ok(log) = self.log_receiver.try_recv() {
+            for line in LogLine::parse(&String::from_utf8_lossy(&log)) {
+                if (self.paused || self.scroll > 0) && self.is_shown(&line) {
+                    self.scroll += 1;
+                }
+                if let Some(module) = &line.module {
+                    if !self.modules.contains(module) {
+                        self.modules.insert(module.clone());
+                    }
+                }
+                if self.log_cache.len() == MAX_LOG_LINES {
+                    self.log_cache.pop_front();
+                }
+                self.log_cache.push_back(line);
+            }
+        }
+    }
+
+    /// Handles the given key, returning `true` if it was used by the logs page.
+    pub(crate) fn handle_key(&mut self, key: KeyEvent) -> bool {
+        // Type the key into the search.
+        if self.is_searching {
+            match key.code {
+                KeyCode::Char(c) => self.search.push(c),
+                KeyCode::Backspace => {
+                    self.search.pop();
+                }
+                KeyCode::Enter => self.is_searching = false,
+                KeyCode::Esc => {
+                    self.search.clear();
+                    self.is_searching = false;
+                }
+                _ => return false,
+            }
+            return true;
+        }
+
+        let page = self.height.max(1);
+        match key.code {
+            KeyCode::Up => self.scroll_to(self.scroll.saturating_add(1)),
+            KeyCode::Down => self.scroll_to(self.scroll.saturating_sub(1)),
+            KeyCode::PageUp => self.scroll_to(self.scroll.saturating_add(page)),
+            KeyCode::PageDown => self.scroll_to(self.scroll.saturating_sub(page)),
+            KeyCode::Home => self.scroll_to(usize::MAX),
+            KeyCode::End => self.scroll_to(0),
+            KeyCode::Char(' ') | KeyCode::Char('p') => {
+                // Resume from the latest line.
+                if self.paused {
+                    self.scroll = 0;
+                }
+                self.paused = !self.paused;
+            }
+            KeyCode::Char('l') => {
+                self.level = self.level.next();
+                self.scroll = 0;
+            }
+            KeyCode::Char('m') => {
+                self.module = self.next_module();
+                self.scroll = 0;
+            }
+            KeyCode::Char('/') => {
+                self.search.clear();
+                self.is_searching = true;
+            }
+            KeyCode::Char('n') => self.find_match(true),
+            KeyCode::Char('N') => self.find_match(false),
+            KeyCode::Esc if !self.search.is_empty() => self.search.clear(),
+            _ => return false,
+        }
+        true
     }
 
     pub(crate) fn draw<B: Backend>(&mut self, f: &mut Frame<B>, area: Rect) {
         // Initialize the layout of the page.
         let chunks = Layout::default()
             .direction(Direction::Vertical)
-            .constraints([Constraint::Percentage(100)].as_ref())
+            .constraints([Constraint::Min(0), Constraint::Length(3)].as_ref())
             .split(area);
 
-        let mut new_logs = Vec::new();
-        while let Ok(log) = self.log_receiver.try_recv() {
-            new_logs.push(match String::from_utf8(log) {
-                Ok(log) => log,
-                _ => String::new(),
-            });
-        }
+        // Determine the lines in the view.
+        self.height = usize::from(chunks[0].height.saturating_sub(2));
+        let num_lines = self.log_cache.iter().filter(|line| self.is_shown(line)).count();
+        self.scroll = self.scroll.min(num_lines.saturating_sub(self.height));
+        let lines = self.shown_lines();
+        let end = lines.len() - self.scroll;
+        let start = end.saturating_sub(self.height);
+        let text = lines[start..end].iter().map(|line| self.highlight(line)).collect::<Vec<_>>();
 
-        let all_logs = self.log_cache.len() + new_logs.len();
-        if all_logs > self.log_limit {
-            let remaining_room = self.log_limit - self.log_cache.len();
-            let overflow = all_logs - self.log_cache.len();
+        let logs = Paragraph::new(text).block(Block::default().borders(Borders::ALL).title(self.title()));
+        f.render_widget(logs, chunks[0]);
 
-            if overflow > self.log_limit {
-                self.log_cache.clear();
-            } else {
-                let missing_room = all_logs - remaining_room;
-                for _ in 0..missing_room {
-                    self.log_cache.pop_front();
-                }
+        let help = match self.is_searching {
+            true => format!("Search: {}_  (Enter to confirm, Esc to clear)", self.search),
+            false => "↑/↓/PgUp/PgDn/Home/End scroll, p pause, l level, m module, / search, n/N previous/next match"
+                .to_string(),
+        };
+        let help = Paragraph::new(help).block(Block::default().borders(Borders::ALL).title("Help"));
+        f.render_widget(help, chunks[1]);
+    }
+}
+
+impl Logs {
+    /// Returns `true` if the given line passes the level and module filters.
+    fn is_shown(&self, line: &LogLine) -> bool {
+        line.level.map_or(true, |level| level <= self.level)
+            && self.module.as_ref().map_or(true, |module| line.module.as_ref() == Some(module))
+    }
+
+    /// Returns the lines which pass the level and module filters.
+    fn shown_lines(&self) -> Vec<&LogLine> {
+        self.log_cache.iter().filter(|line| self.is_shown(line)).collect()
+    }
+
+    /// Scrolls the view to the given number of lines from the latest line.
+    fn scroll_to(&mut self, scroll: usize) {
+        // Note: The scroll is bounded by the number of lines on the next draw.
+        self.scroll = scroll.min(self.log_cache.len());
+    }
+
+    /// Returns the module after the shown one, where `None` shows all modules.
+    fn next_module(&self) -> Option<String> {
+        match &self.module {
+            None => self.modules.iter().next().cloned(),
+            Some(module) => {
+                self.modules.range::<String, _>((Bound::Excluded(module), Bound::Unbounded)).next().cloned()
             }
+        }
+    }
+
+    /// Scrolls the view to the previous (older) or next (newer) line matching the search.
+    fn find_match(&mut self, previous: bool) {
+        if self.search.is_empty() {
+            return;
+        }
+        let lines = self.shown_lines();
+        // The index of the last line in the view.
+        let current = lines.len().saturating_sub(self.scroll + 1);
+        let is_match = |(_, line): &(usize, &&LogLine)| line.text.contains(&self.search);
+        let found = match previous {
+            true => lines.iter().enumerate().take(current).rev().find(is_match),
+            false => lines.iter().enumerate().skip(current + 1).find(is_match),
+        };
+        if let Some((index, _)) = found {
+            self.scroll = lines.len() - 1 - index;
+        }
+    }
+
+    /// Returns the given line, with the matches of the search highlighted, and colored by its level.
+    fn highlight<'a>(&self, line: &'a LogLine) -> Spans<'a> {
+        let style = match line.level {
+            Some(Level::Error) => Style::default().fg(Color::Red),
+            Some(Level::Warn) => Style::default().fg(Color::Yellow),
+            _ => Style::default(),
         };
+        if self.search.is_empty() {
+            return Spans::from(Span::styled(line.text.as_str(), style));
+        }
 
-        self.log_cache.extend(new_logs.into_iter().take(self.log_limit));
+        let highlight = Style::default().fg(Color::Black).bg(Color::Yellow).add_modifier(Modifier::BOLD);
+        let mut spans = Vec::new();
+        let mut rest = line.text.as_str();
+        while let Some(index) = rest.find(&self.search) {
+            let (before, after) = rest.split_at(index);
+            let (found, after) = after.split_at(self.search.len());
+            spans.push(Span::styled(before, style));
+            spans.push(Span::styled(found, highlight));
+            rest = after;
+        }
+        spans.push(Span::styled(rest, style));
+        Spans::from(spans)
+    }
 
-        let combined_logs = self.log_cache.iter().map(|s| s.as_str()).collect::<String>();
+    /// Returns the title of the logs, with the filters and the state of the view.
+    fn title(&self) -> String {
+        let mut title =
+            format!("Logs [level: {}] [module: {}]", self.level.as_str(), self.module.as_deref().unwrap_or("all"));
+        if !self.search.is_empty() {
+            title += &format!(" [search: {}]", self.search);
+        }
+        if self.paused {
+            title += " [paused]";
+        } else if self.scroll > 0 {
+            title += &format!(" [{} lines up]", self.scroll);
+        }
+        title
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_log_line() {
+        let log = "2023-10-18T12:00:00.000000Z  WARN snarkos_node_narwhal::gateway: Dropping '1.2.3.4:5000'\n";
+        let lines = LogLine::parse(log);
+        assert_eq!(lines.len(), 1);
+        assert_eq!(lines[0].level, Some(Level::Warn));
+        assert_eq!(lines[0].module.as_deref(), Some("snarkos_node_narwhal::gateway"));
+
+        // The spans are skipped, and the continuation lines inherit the level and module.
+        let log =
+            "2023-10-18T12:00:00.000000Z  INFO propose_batch{round=5}: snarkos_node_narwhal::primary: \n\nProposing\n";
+        let lines = LogLine::parse(log);
+        assert_eq!(lines.len(), 3);
+        assert!(lines.iter().all(|line| line.level == Some(Level::Info)));
+        assert!(lines.iter().all(|line| line.module.as_deref() == Some("snarkos_node_narwhal::primary")));
+
+        // A log without a level is kept as is.
+        let lines = LogLine::parse("Welcome");
+        assert_eq!(lines, vec![LogLine { level: None, module: None, text: "Welcome".to_string() }]);
+    }
 
-        let combined_logs = Paragraph::new(combined_logs).block(Block::default().borders(Borders::ALL).title("Logs"));
-        f.render_widget(combined_logs, chunks[0]);
+    #[test]
+    fn test_level_next() {
+        assert_eq!(Level::Error.next(), Level::Warn);
+        assert_eq!(Level::Trace.next(), Level::Error);
+        assert!(Level::Info < Level::Debug);
     }
 }
@@ -12,10 +12,13 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+mod consensus;
+pub(crate) use consensus::Consensus;
+
 mod logs;
 pub(crate) use logs::Logs;
 
 mod overview;
 pub(crate) use overview::Overview;
 
-pub(crate) const PAGES: [&str; 2] = [" Overview ", " Logs "];
+pub(crate) const PAGES: [&str; 3] = [" Overview ", " Logs ", " Consensus "];
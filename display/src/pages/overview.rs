@@ -18,35 +18,109 @@ use snarkvm::prelude::Network;
 use tui::{
     backend::Backend,
     layout::{Constraint, Direction, Layout, Rect},
-    style::{Color, Style},
-    text::Span,
-    widgets::{canvas::Canvas, Block, Borders},
+    style::{Color, Modifier, Style},
+    text::{Span, Spans},
+    widgets::{canvas::Canvas, Block, Borders, Cell, Paragraph, Row, Table},
     Frame,
 };
 
 pub(crate) struct Overview;
 
 impl Overview {
-    pub(crate) fn draw<B: Backend, N: Network>(&self, f: &mut Frame<B>, area: Rect, _node: &Node<N>) {
+    pub(crate) fn draw<B: Backend, N: Network>(&self, f: &mut Frame<B>, area: Rect, node: &Node<N>) {
         // Initialize the layout of the page.
         let chunks = Layout::default()
             .direction(Direction::Vertical)
-            .constraints([Constraint::Percentage(10), Constraint::Percentage(70), Constraint::Max(2)].as_ref())
+            .constraints(
+                [Constraint::Length(4), Constraint::Length(4), Constraint::Min(0), Constraint::Max(2)].as_ref(),
+            )
             .split(area);
 
-        let canvas = Canvas::default().block(Block::default().borders(Borders::ALL).title("Block")).paint(|_ctx| {
-            // ctx.draw(&ball);
-        });
-        f.render_widget(canvas, chunks[0]);
-
-        let canvas = Canvas::default().block(Block::default().borders(Borders::ALL).title("Peers")).paint(|_ctx| {
-            // ctx.draw(&ball);
-        });
-        f.render_widget(canvas, chunks[1]);
+        f.render_widget(self.draw_block(node), chunks[0]);
+        f.render_widget(self.draw_consensus(node), chunks[1]);
+        f.render_widget(self.draw_peers(node), chunks[2]);
 
         let canvas = Canvas::default().block(Block::default().borders(Borders::ALL).title("Help")).paint(|ctx| {
             ctx.print(0f64, 0f64, Span::styled("Press ESC to quit", Style::default().fg(Color::White)));
         });
-        f.render_widget(canvas, chunks[2]);
+        f.render_widget(canvas, chunks[3]);
+    }
+
+    /// Returns the panel with the latest block and the sync status.
+    fn draw_block<N: Network>(&self, node: &Node<N>) -> Paragraph<'static> {
+        let (height, hash) = match node.latest_block() {
+            Some((height, Some(hash))) => (height.to_string(), hash.to_string()),
+            Some((height, None)) => (height.to_string(), "N/A".to_string()),
+            None => ("N/A".to_string(), "N/A".to_string()),
+        };
+        let status = match node.is_block_synced() {
+            true => Span::styled("Synced", Style::default().fg(Color::Green)),
+            false => Span::styled("Syncing", Style::default().fg(Color::Yellow)),
+        };
+
+        let lines = vec![
+            Spans::from(vec![label("Height: "), Span::raw(height), label("   Status: "), status]),
+            Spans::from(vec![label("Hash: "), Span::raw(hash)]),
+        ];
+        Paragraph::new(lines).block(Block::default().borders(Borders::ALL).title("Block"))
+    }
+
+    /// Returns the panel with the Narwhal round, the leader, and the memory pool of a validator.
+    fn draw_consensus<N: Network>(&self, node: &Node<N>) -> Paragraph<'static> {
+        let lines = match node.consensus() {
+            Some(consensus) => {
+                let bft = consensus.bft();
+                let leader = bft.leader().map_or_else(|| "N/A".to_string(), |leader| leader.to_string());
+                vec![
+                    Spans::from(vec![
+                        label("Round: "),
+                        Span::raw(bft.primary().current_round().to_string()),
+                        label("   Leader: "),
+                        Span::raw(leader),
+                    ]),
+                    Spans::from(vec![
+                        label("Memory pool: "),
+                        Span::raw(format!(
+                            "{} transmissions ({} solutions, {} transactions)",
+                            consensus.num_unconfirmed_transmissions(),
+                            consensus.num_unconfirmed_solutions(),
+                            consensus.num_unconfirmed_transactions()
+                        )),
+                    ]),
+                ]
+            }
+            None => vec![Spans::from(Span::raw(format!("Not available for a {} node", node.node_type())))],
+        };
+        Paragraph::new(lines).block(Block::default().borders(Borders::ALL).title("Consensus"))
     }
+
+    /// Returns the table of the connected peers.
+    fn draw_peers<N: Network>(&self, node: &Node<N>) -> Table<'static> {
+        let mut peers = node.router().get_connected_peers();
+        peers.sort_by_key(|peer| peer.ip());
+
+        let header = Row::new(["Address", "Type", "Version", "Last Seen"])
+            .style(Style::default().fg(Color::Yellow).add_modifier(Modifier::BOLD));
+        let rows = peers.iter().map(|peer| {
+            Row::new([
+                Cell::from(peer.ip().to_string()),
+                Cell::from(peer.node_type().to_string()),
+                Cell::from(peer.version().to_string()),
+                Cell::from(format!("{}s ago", peer.last_seen().elapsed().as_secs())),
+            ])
+        });
+
+        let title = format!("Peers ({})", peers.len());
+        Table::new(rows).header(header).block(Block::default().borders(Borders::ALL).title(title)).widths(&[
+            Constraint::Length(24),
+            Constraint::Length(12),
+            Constraint::Length(10),
+            Constraint::Min(10),
+        ])
+    }
+}
+
+/// Returns the given label, styled for the panels.
+fn label(text: &'static str) -> Span<'static> {
+    Span::styled(text, Style::default().fg(Color::Cyan))
 }
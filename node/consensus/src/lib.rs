@@ -49,6 +49,7 @@ use tokio::{
     sync::{oneshot, OnceCell},
     task::JoinHandle,
 };
+use tracing::{field, instrument, Instrument, Span};
 
 #[derive(Clone)]
 pub struct Consensus<N: Network> {
@@ -61,7 +62,7 @@ pub struct Consensus<N: Network> {
     /// The unconfirmed solutions queue.
     solutions_queue: Arc<Mutex<IndexMap<PuzzleCommitment<N>, ProverSolution<N>>>>,
     /// The unconfirmed transactions queue.
-    transactions_queue: Arc<Mutex<IndexMap<N::TransactionID, Transaction<N>>>>,
+    transactions_queue: Arc<Mutex<IndexMap<N::TransactionID, (Transaction<N>, Span)>>>,
     /// The spawned handles.
     handles: Arc<Mutex<Vec<JoinHandle<()>>>>,
 }
@@ -203,9 +204,12 @@ impl<N: Network> Consensus<N> {
     }
 
     /// Adds the given unconfirmed transaction to the memory pool.
+    #[instrument(level = "trace", skip_all, fields(transaction_id = %transaction.id()))]
     pub async fn add_unconfirmed_transaction(&self, transaction: Transaction<N>) -> Result<()> {
+        // Initialize the span of the transaction, which is linked to its batch, certificate, and commit.
+        let span = trace_span!("unconfirmed_transaction", transaction_id = %transaction.id());
         // Add the transaction to the memory pool.
-        self.transactions_queue.lock().insert(transaction.id(), transaction);
+        self.transactions_queue.lock().insert(transaction.id(), (transaction, span));
 
         // If the memory pool of this node is full, return early.
         let num_unconfirmed = self.num_unconfirmed_transmissions();
@@ -224,13 +228,13 @@ impl<N: Network> Consensus<N> {
             queue.drain(..num_transactions).collect::<Vec<_>>()
         };
         // Iterate over the transactions.
-        for (_, transaction) in transactions.into_iter() {
+        for (_, (transaction, span)) in transactions.into_iter() {
             // Initialize a callback sender and receiver.
             let (callback, callback_receiver) = oneshot::channel();
             // Send the transaction to the primary.
             self.primary_sender()
                 .tx_unconfirmed_transaction
-                .send((transaction.id(), Data::Object(transaction), callback))
+                .send((transaction.id(), Data::Object(transaction), span, callback))
                 .await?;
             // Ignore the result for the transactions.
             let _ = callback_receiver.await?;
@@ -247,8 +251,9 @@ impl<N: Network> Consensus<N> {
         // Process the committed subdag and transmissions from the BFT.
         let self_ = self.clone();
         self.spawn(async move {
-            while let Some((committed_subdag, transmissions, callback)) = rx_consensus_subdag.recv().await {
-                self_.process_bft_subdag(committed_subdag, transmissions, callback).await;
+            while let Some((committed_subdag, transmissions, span, callback)) = rx_consensus_subdag.recv().await {
+                // Process the subdag within the span of its commit.
+                self_.process_bft_subdag(committed_subdag, transmissions, callback).instrument(span).await;
             }
         });
     }
@@ -274,6 +279,7 @@ impl<N: Network> Consensus<N> {
     }
 
     /// Attempts to advance to the next block.
+    #[instrument(level = "trace", skip_all, fields(height = field::Empty, transactions = field::Empty))]
     fn try_advance_to_next_block(
         &self,
         subdag: Subdag<N>,
@@ -281,10 +287,15 @@ impl<N: Network> Consensus<N> {
     ) -> Result<()> {
         // Create the candidate next block.
         let next_block = self.ledger.prepare_advance_to_next_quorum_block(subdag, transmissions)?;
+        // Record the block in the span.
+        let span = Span::current();
+        span.record("height", next_block.height());
+        span.record("transactions", next_block.transactions().len());
         // Check that the block is well-formed.
         self.ledger.check_next_block(&next_block)?;
         // Advance to the next block.
         self.ledger.advance_to_next_block(&next_block)?;
+        debug!(height = next_block.height(), "Advanced to block {} ({})", next_block.height(), next_block.hash());
         Ok(())
     }
 
@@ -316,7 +327,12 @@ impl<N: Network> Consensus<N> {
             }
             (TransmissionID::Transaction(transaction_id), Transmission::Transaction(transaction)) => {
                 // Send the transaction to the primary.
-                self.primary_sender().tx_unconfirmed_transaction.send((transaction_id, transaction, callback)).await?;
+                // Note: The reinserted transaction is not traced, as its span is closed once its commit is processed.
+                let span = Span::none();
+                self.primary_sender()
+                    .tx_unconfirmed_transaction
+                    .send((transaction_id, transaction, span, callback))
+                    .await?;
             }
             _ => bail!("Mismatching `(transmission_id, transmission)` pair in consensus"),
         }
@@ -0,0 +1,455 @@
+// Copyright (C) 2019-2023 Aleo Systems Inc.
+// This file is part of the snarkOS library.
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at:
+// http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+#![forbid(unsafe_code)]
+
+#[macro_use]
+extern crate tracing;
+
+use snarkvm::prelude::{
+    block::{Block, Input, Output, Transaction},
+    store::ConsensusStorage,
+    Address,
+    Argument,
+    FromBytes,
+    Future,
+    Identifier,
+    Ledger,
+    Literal,
+    Network,
+    Owner,
+    Plaintext,
+    ProgramID,
+    ToBytes,
+};
+
+use anyhow::{bail, ensure, Result};
+use rocksdb::{Direction, IteratorMode, WriteBatch, DB};
+use serde::Serialize;
+use std::{
+    marker::PhantomData,
+    path::{Path, PathBuf},
+    sync::Arc,
+    time::Duration,
+};
+
+/// The interval between checks for new blocks.
+const INDEX_INTERVAL: Duration = Duration::from_secs(5);
+/// The maximum number of blocks indexed in one batch.
+const MAX_BLOCKS_PER_BATCH: u32 = 1_000;
+/// The maximum number of transactions in a page.
+pub const MAX_PAGE_SIZE: usize = 100;
+
+/// The key of the next block height to index.
+const NEXT_HEIGHT_KEY: &[u8] = b"m/next_height";
+/// The prefix of the address index, as `(address, position) => transaction ID`.
+const ADDRESS_PREFIX: u8 = b'a';
+/// The prefix of the program index, as `(program ID, position) => transaction ID`.
+const PROGRAM_PREFIX: u8 = b'p';
+/// The prefix of the function index, as `(program ID, function name, position) => transaction ID`.
+const FUNCTION_PREFIX: u8 = b'f';
+
+/// Returns the path to the index of the ledger, which is stored alongside the ledger.
+pub fn indexer_dir(network: u16, dev: Option<u16>) -> PathBuf {
+    let mut path = aleo_std::aleo_ledger_dir(network, dev).into_os_string();
+    path.push("-index");
+    path.into()
+}
+
+/// A transaction in the index.
+#[derive(Clone, Serialize)]
+#[serde(bound = "N: Network")]
+pub struct IndexedTransaction<N: Network> {
+    /// The transaction ID.
+    pub transaction_id: N::TransactionID,
+    /// The height of the block containing the transaction.
+    pub height: u32,
+}
+
+/// A page of transactions in the index, from the newest to the oldest.
+#[derive(Clone, Serialize)]
+#[serde(bound = "N: Network")]
+pub struct TransactionPage<N: Network> {
+    /// The transactions.
+    pub transactions: Vec<IndexedTransaction<N>>,
+    /// The cursor of the next page, if there are more transactions.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub next_cursor: Option<u64>,
+}
+
+/// An index of the transactions in the ledger, by address and by program.
+///
+/// The addresses of a transaction are its public inputs and outputs, the owners of its public records,
+/// and the owner of its deployment. Note: The caller of a transition is not revealed by the transition,
+/// so it is only indexed when it is a public input or output.
+#[derive(Clone)]
+pub struct Indexer<N: Network> {
+    /// The database.
+    db: Arc<DB>,
+    /// PhantomData.
+    _phantom: PhantomData<N>,
+}
+
+impl<N: Network> Indexer<N> {
+    /// Opens the index of the ledger.
+    pub fn open(dev: Option<u16>) -> Result<Self> {
+        Self::open_at(&indexer_dir(N::ID, dev))
+    }
+
+    /// Opens the index in the given directory.
+    fn open_at(path: &Path) -> Result<Self> {
+        Ok(Self { db: Arc::new(DB::open_default(path)?), _phantom: PhantomData })
+    }
+
+    /// Indexes the blocks in the ledger, as they are added. This method never returns.
+    pub async fn run<C: ConsensusStorage<N>>(self, ledger: Ledger<N, C>) {
+        info!("Starting the indexer...");
+        loop {
+            let (indexer, ledger) = (self.clone(), ledger.clone());
+            match tokio::task::spawn_blocking(move || indexer.index_blocks(&ledger)).await {
+                // If there are more blocks to index, continue immediately.
+                Ok(Ok(true)) => continue,
+                Ok(Ok(false)) => (),
+                Ok(Err(error)) => warn!("Failed to index the ledger - {error}"),
+                Err(error) => warn!("Failed to index the ledger - {error}"),
+            }
+            tokio::time::sleep(INDEX_INTERVAL).await;
+        }
+    }
+
+    /// Returns the transactions of the given address, starting before the given cursor.
+    pub fn address_transactions(
+        &self,
+        address: &Address<N>,
+        cursor: Option<u64>,
+        limit: usize,
+    ) -> Result<TransactionPage<N>> {
+        self.page(&address_prefix(address), cursor, limit)
+    }
+
+    /// Returns the transactions of the given program, and optionally of the given function,
+    /// starting before the given cursor.
+    pub fn program_transactions(
+        &self,
+        program_id: &ProgramID<N>,
+        function_name: Option<&Identifier<N>>,
+        cursor: Option<u64>,
+        limit: usize,
+    ) -> Result<TransactionPage<N>> {
+        self.page(&program_prefix(program_id, function_name), cursor, limit)
+    }
+}
+
+impl<N: Network> Indexer<N> {
+    /// Returns the next block height to index.
+    fn next_height(&self) -> Result<u32> {
+        match self.db.get(NEXT_HEIGHT_KEY)? {
+            Some(bytes) => Ok(u32::from_le_bytes(bytes.as_slice().try_into()?)),
+            None => Ok(0),
+        }
+    }
+
+    /// Indexes the next batch of blocks in the ledger, returning `true` if there are more blocks to index.
+    fn index_blocks<C: ConsensusStorage<N>>(&self, ledger: &Ledger<N, C>) -> Result<bool> {
+        let next_height = self.next_height()?;
+        let latest_height = ledger.latest_height();
+        if next_height > latest_height.saturating_add(1) {
+            bail!("The index is ahead of the ledger (at block {next_height}), run `snarkos clean` to reset it")
+        }
+
+        // Index the next batch of blocks.
+        let end_height = latest_height.min(next_height.saturating_add(MAX_BLOCKS_PER_BATCH - 1));
+        for height in next_height..=end_height {
+            self.index_block(&ledger.get_block(height)?)?;
+        }
+        Ok(end_height < latest_height)
+    }
+
+    /// Indexes the transactions in the given block.
+    fn index_block(&self, block: &Block<N>) -> Result<()> {
+        let mut batch = WriteBatch::default();
+
+        for (index, confirmed) in block.transactions().iter().enumerate() {
+            let position = position(block.height(), u32::try_from(index)?);
+            Self::index_transaction(&mut batch, position, confirmed.transaction())?;
+        }
+
+        // Update the next block height, in the same batch.
+        batch.put(NEXT_HEIGHT_KEY, block.height().saturating_add(1).to_le_bytes());
+        self.db.write(batch)?;
+        Ok(())
+    }
+
+    /// Indexes the given transaction at the given position, in the given batch.
+    fn index_transaction(batch: &mut WriteBatch, position: u64, transaction: &Transaction<N>) -> Result<()> {
+        let transaction_id = transaction.id().to_bytes_le()?;
+
+        // Index the programs and functions of the transaction, and collect its addresses.
+        let mut addresses = Vec::new();
+        if let Transaction::Deploy(_, owner, deployment, _) = transaction {
+            addresses.push(owner.address());
+            batch.put(key(&program_prefix(deployment.program_id(), None), position), &transaction_id);
+        }
+        for transition in transaction.transitions() {
+            let program_id = transition.program_id();
+            batch.put(key(&program_prefix(program_id, None), position), &transaction_id);
+            batch.put(key(&program_prefix(program_id, Some(transition.function_name())), position), &transaction_id);
+
+            for input in transition.inputs() {
+                if let Input::Constant(_, Some(plaintext)) | Input::Public(_, Some(plaintext)) = input {
+                    collect_addresses(plaintext, &mut addresses);
+                }
+            }
+            for output in transition.outputs() {
+                match output {
+                    Output::Constant(_, Some(plaintext)) | Output::Public(_, Some(plaintext)) => {
+                        collect_addresses(plaintext, &mut addresses)
+                    }
+                    Output::Record(_, _, Some(record)) => {
+                        if let Owner::Public(address) = record.owner() {
+                            addresses.push(*address);
+                        }
+                    }
+                    // Note: The caller and recipient of a public transfer are in the arguments of its future.
+                    Output::Future(_, Some(future)) => collect_future_addresses(future, &mut addresses),
+                    _ => (),
+                }
+            }
+        }
+
+        // Index the addresses of the transaction.
+        for address in addresses {
+            batch.put(key(&address_prefix(&address), position), &transaction_id);
+        }
+        Ok(())
+    }
+
+    /// Returns the page of transactions with the given prefix, from the newest to the oldest.
+    /// Note: The limit must be between 1 and `MAX_PAGE_SIZE`, otherwise an error is returned.
+    fn page(&self, prefix: &[u8], cursor: Option<u64>, limit: usize) -> Result<TransactionPage<N>> {
+        ensure!(limit > 0, "The page size must be at least 1");
+        ensure!(limit <= MAX_PAGE_SIZE, "The page size must be at most {MAX_PAGE_SIZE}");
+
+        // The cursor is the position of the last transaction of the previous page, and is exclusive.
+        let start = match cursor {
+            Some(0) => return Ok(TransactionPage { transactions: vec![], next_cursor: None }),
+            Some(cursor) => cursor - 1,
+            None => u64::MAX,
+        };
+
+        let mut transactions = Vec::with_capacity(limit);
+        let mut next_cursor = None;
+        for entry in self.db.iterator(IteratorMode::From(&key(prefix, start), Direction::Reverse)) {
+            let (entry_key, value) = entry?;
+            if !entry_key.starts_with(prefix) || entry_key.len() != prefix.len() + 8 {
+                break;
+            }
+            let position = u64::from_be_bytes(entry_key[prefix.len()..].try_into()?);
+            // If the page is full, the next page starts from this transaction.
+            if transactions.len() == limit {
+                next_cursor = Some(position.saturating_add(1));
+                break;
+            }
+            let transaction_id = N::TransactionID::from_bytes_le(&value)?;
+            transactions.push(IndexedTransaction { transaction_id, height: (position >> 32) as u32 });
+        }
+        Ok(TransactionPage { transactions, next_cursor })
+    }
+}
+
+/// Returns the position of a transaction, as its block height and its index in the block.
+fn position(height: u32, index: u32) -> u64 {
+    (u64::from(height) << 32) | u64::from(index)
+}
+
+/// Returns the key for the given prefix and position.
+/// Note: The position is big-endian, so the keys are ordered by position.
+fn key(prefix: &[u8], position: u64) -> Vec<u8> {
+    let mut key = prefix.to_vec();
+    key.extend_from_slice(&position.to_be_bytes());
+    key
+}
+
+/// Returns the key prefix for the given address.
+fn address_prefix<N: Network>(address: &Address<N>) -> Vec<u8> {
+    let mut prefix = vec![ADDRESS_PREFIX];
+    prefix.extend_from_slice(address.to_string().as_bytes());
+    prefix.push(0);
+    prefix
+}
+
+/// Returns the key prefix for the given program, and optionally the given function.
+fn program_prefix<N: Network>(program_id: &ProgramID<N>, function_name: Option<&Identifier<N>>) -> Vec<u8> {
+    let mut prefix = match function_name {
+        Some(_) => vec![FUNCTION_PREFIX],
+        None => vec![PROGRAM_PREFIX],
+    };
+    prefix.extend_from_slice(program_id.to_string().as_bytes());
+    prefix.push(0);
+    if let Some(function_name) = function_name {
+        prefix.extend_from_slice(function_name.to_string().as_bytes());
+        prefix.push(0);
+    }
+    prefix
+}
+
+/// Collects the addresses in the given plaintext.
+fn collect_addresses<N: Network>(plaintext: &Plaintext<N>, addresses: &mut Vec<Address<N>>) {
+    match plaintext {
+        Plaintext::Literal(Literal::Address(address), _) => addresses.push(*address),
+        Plaintext::Literal(..) => (),
+        Plaintext::Struct(members, _) => members.values().for_each(|member| collect_addresses(member, addresses)),
+        Plaintext::Array(elements, _) => elements.iter().for_each(|element| collect_addresses(element, addresses)),
+    }
+}
+
+/// Collects the addresses in the arguments of the given future and its nested futures.
+fn collect_future_addresses<N: Network>(future: &Future<N>, addresses: &mut Vec<Address<N>>) {
+    for argument in future.arguments() {
+        match argument {
+            Argument::Plaintext(plaintext) => collect_addresses(plaintext, addresses),
+            Argument::Future(future) => collect_future_addresses(future, addresses),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use snarkvm::{
+        circuit::AleoV0,
+        prelude::{
+            block::Execution,
+            store::{helpers::memory::ConsensusMemory, ConsensusStore},
+            Field,
+            PrivateKey,
+            Testnet3,
+            Uniform,
+            VM,
+        },
+    };
+    use std::str::FromStr;
+
+    type CurrentNetwork = Testnet3;
+
+    #[test]
+    fn test_page() {
+        let rng = &mut rand::thread_rng();
+
+        let directory = std::env::temp_dir().join(format!("snarkos-indexer-test-{}", std::process::id()));
+        let indexer = Indexer::<CurrentNetwork>::open_at(&directory).unwrap();
+
+        // Index a transaction of an address in each of 5 blocks.
+        let address = Address::try_from(PrivateKey::<CurrentNetwork>::new(rng).unwrap()).unwrap();
+        let other = Address::try_from(PrivateKey::<CurrentNetwork>::new(rng).unwrap()).unwrap();
+        let transaction_ids = (0..5u32)
+            .map(|height| {
+                let transaction_id = <CurrentNetwork as Network>::TransactionID::from(Field::rand(rng));
+                let value = transaction_id.to_bytes_le().unwrap();
+                indexer.db.put(key(&address_prefix(&address), position(height, 1)), &value).unwrap();
+                indexer.db.put(key(&address_prefix(&other), position(height, 0)), &value).unwrap();
+                transaction_id
+            })
+            .collect::<Vec<_>>();
+
+        // Ensure the pages are returned from the newest to the oldest.
+        let page = indexer.address_transactions(&address, None, 2).unwrap();
+        assert_eq!(page.transactions.iter().map(|t| t.height).collect::<Vec<_>>(), vec![4, 3]);
+        assert_eq!(page.transactions[0].transaction_id, transaction_ids[4]);
+        let page = indexer.address_transactions(&address, page.next_cursor, 2).unwrap();
+        assert_eq!(page.transactions.iter().map(|t| t.height).collect::<Vec<_>>(), vec![2, 1]);
+        let page = indexer.address_transactions(&address, page.next_cursor, 2).unwrap();
+        assert_eq!(page.transactions.iter().map(|t| t.height).collect::<Vec<_>>(), vec![0]);
+        assert_eq!(page.next_cursor, None);
+
+        // Ensure the page size is bounded.
+        assert!(indexer.address_transactions(&address, None, 0).is_err());
+        assert!(indexer.address_transactions(&address, None, MAX_PAGE_SIZE + 1).is_err());
+        assert_eq!(indexer.address_transactions(&address, None, MAX_PAGE_SIZE).unwrap().transactions.len(), 5);
+
+        drop(indexer);
+        std::fs::remove_dir_all(directory).unwrap();
+    }
+
+    #[test]
+    fn test_index_block() {
+        let directory = std::env::temp_dir().join(format!("snarkos-indexer-block-test-{}", std::process::id()));
+        let indexer = Indexer::<CurrentNetwork>::open_at(&directory).unwrap();
+        assert_eq!(indexer.next_height().unwrap(), 0);
+
+        // Index the genesis block.
+        let block = Block::<CurrentNetwork>::from_bytes_le(CurrentNetwork::genesis_bytes()).unwrap();
+        indexer.index_block(&block).unwrap();
+        assert_eq!(indexer.next_height().unwrap(), 1);
+
+        // Ensure the transactions are indexed by program and by function, from the newest to the oldest.
+        let expected =
+            block.transactions().iter().rev().map(|confirmed| confirmed.transaction().id()).collect::<Vec<_>>();
+        assert!(!expected.is_empty());
+        let program_id = ProgramID::from_str("credits.aleo").unwrap();
+        let function_name = block.transitions().next().unwrap().function_name();
+        for function_name in [None, Some(function_name)] {
+            let page = indexer.program_transactions(&program_id, function_name, None, MAX_PAGE_SIZE).unwrap();
+            assert_eq!(page.transactions.iter().map(|t| t.transaction_id).collect::<Vec<_>>(), expected);
+            assert!(page.transactions.iter().all(|t| t.height == 0));
+            assert_eq!(page.next_cursor, None);
+        }
+
+        // Ensure other functions and programs are not indexed.
+        let other_function = Identifier::from_str("transfer_private").unwrap();
+        assert!(
+            indexer.program_transactions(&program_id, Some(&other_function), None, 1).unwrap().transactions.is_empty()
+        );
+        let other_program = ProgramID::from_str("other.aleo").unwrap();
+        assert!(indexer.program_transactions(&other_program, None, None, 1).unwrap().transactions.is_empty());
+
+        drop(indexer);
+        std::fs::remove_dir_all(directory).unwrap();
+    }
+
+    #[test]
+    fn test_index_public_transfer() {
+        let rng = &mut rand::thread_rng();
+
+        let directory = std::env::temp_dir().join(format!("snarkos-indexer-transfer-test-{}", std::process::id()));
+        let indexer = Indexer::<CurrentNetwork>::open_at(&directory).unwrap();
+
+        // Run a public transfer, without proving it.
+        let private_key = PrivateKey::<CurrentNetwork>::new(rng).unwrap();
+        let caller = Address::try_from(&private_key).unwrap();
+        let recipient = Address::try_from(PrivateKey::<CurrentNetwork>::new(rng).unwrap()).unwrap();
+        let store = ConsensusStore::<CurrentNetwork, ConsensusMemory<CurrentNetwork>>::open(None).unwrap();
+        let vm = VM::from(store).unwrap();
+        let inputs = [recipient.to_string(), "1u64".to_string()];
+        let authorization = vm.authorize(&private_key, "credits.aleo", "transfer_public", inputs.iter(), rng).unwrap();
+        let (_, trace) = vm.process().read().execute::<AleoV0>(authorization).unwrap();
+        let execution = Execution::from(trace.transitions().iter().cloned(), Default::default(), None).unwrap();
+        let transaction = Transaction::from_execution(execution, None).unwrap();
+
+        // Index the transaction.
+        let mut batch = WriteBatch::default();
+        Indexer::<CurrentNetwork>::index_transaction(&mut batch, position(1, 0), &transaction).unwrap();
+        indexer.db.write(batch).unwrap();
+
+        // Ensure the transaction is indexed for the recipient, and for the caller, which is only in the future.
+        for address in [caller, recipient] {
+            let page = indexer.address_transactions(&address, None, MAX_PAGE_SIZE).unwrap();
+            assert_eq!(page.transactions.iter().map(|t| t.transaction_id).collect::<Vec<_>>(), vec![transaction.id()]);
+            assert_eq!(page.transactions[0].height, 1);
+        }
+
+        drop(indexer);
+        std::fs::remove_dir_all(directory).unwrap();
+    }
+}
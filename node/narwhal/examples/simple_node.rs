@@ -202,7 +202,7 @@ fn consensus_handler(receiver: ConsensusReceiver<CurrentNetwork>) {
     let ConsensusReceiver { mut rx_consensus_subdag } = receiver;
 
     tokio::task::spawn(async move {
-        while let Some((subdag, transmissions, callback)) = rx_consensus_subdag.recv().await {
+        while let Some((subdag, transmissions, _span, callback)) = rx_consensus_subdag.recv().await {
             // Determine the amount of time to sleep for the subdag.
             let subdag_ms = subdag.values().flatten().count();
             // Determine the amount of time to sleep for the transmissions.
@@ -15,7 +15,7 @@
 use crate::LedgerService;
 use snarkvm::{
     ledger::{
-        block::{Block, Transaction},
+        block::{Block, ConfirmedTransaction, Transaction},
         coinbase::{ProverSolution, PuzzleCommitment},
         committee::Committee,
         narwhal::{Data, Subdag, Transmission, TransmissionID},
@@ -23,21 +23,26 @@ use snarkvm::{
         Ledger,
     },
     prelude::{bail, Field, Network, Result},
+    synthesizer::program::FinalizeGlobalState,
 };
 
 use indexmap::IndexMap;
+use parking_lot::Mutex;
 use snarkvm::prelude::narwhal::BatchCertificate;
 use std::{fmt, ops::Range};
 
 /// A core ledger service.
 pub struct CoreLedgerService<N: Network, C: ConsensusStorage<N>> {
     ledger: Ledger<N, C>,
+    /// The lock held while a block is prepared, checked, or added, or a transaction is speculated on.
+    /// Note: The VM does not speculate while another atomic batch is in progress in the finalize store.
+    block_lock: Mutex<()>,
 }
 
 impl<N: Network, C: ConsensusStorage<N>> CoreLedgerService<N, C> {
     /// Initializes a new core ledger service.
     pub fn new(ledger: Ledger<N, C>) -> Self {
-        Self { ledger }
+        Self { ledger, block_lock: Default::default() }
     }
 }
 
@@ -207,8 +212,26 @@ impl<N: Network, C: ConsensusStorage<N>> LedgerService<N> for CoreLedgerService<
         self.ledger.check_transaction_basic(&transaction, None)
     }
 
+    /// Speculates on the given transaction on top of the latest block, without modifying the ledger.
+    fn speculate_transaction(&self, transaction: &Transaction<N>) -> Result<Option<ConfirmedTransaction<N>>> {
+        let _lock = self.block_lock.lock();
+        // Construct the finalize state of the next block.
+        let latest_block = self.ledger.latest_block();
+        let state = FinalizeGlobalState::new::<N>(
+            latest_block.round().saturating_add(1),
+            latest_block.height().saturating_add(1),
+            latest_block.cumulative_weight(),
+            latest_block.cumulative_proof_target(),
+            latest_block.hash(),
+        )?;
+        // Perform a dry-run of finalize over the transaction, which is aborted if it cannot be confirmed.
+        let (transactions, _) = self.ledger.vm().speculate(state, &[], None, [transaction].into_iter())?;
+        Ok(transactions.iter().next().cloned())
+    }
+
     /// Checks the given block is valid next block.
     fn check_next_block(&self, block: &Block<N>) -> Result<()> {
+        let _lock = self.block_lock.lock();
         self.ledger.check_next_block(block)
     }
 
@@ -219,12 +242,14 @@ impl<N: Network, C: ConsensusStorage<N>> LedgerService<N> for CoreLedgerService<
         subdag: Subdag<N>,
         transmissions: IndexMap<TransmissionID<N>, Transmission<N>>,
     ) -> Result<Block<N>> {
+        let _lock = self.block_lock.lock();
         self.ledger.prepare_advance_to_next_quorum_block(subdag, transmissions)
     }
 
     /// Adds the given block as the next block in the ledger.
     #[cfg(feature = "ledger-write")]
     fn advance_to_next_block(&self, block: &Block<N>) -> Result<()> {
+        let _lock = self.block_lock.lock();
         self.ledger.advance_to_next_block(block)?;
         tracing::info!("\n\nAdvanced to block {} at round {} - {}\n", block.height(), block.round(), block.hash());
         Ok(())
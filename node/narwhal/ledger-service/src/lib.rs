@@ -32,6 +32,9 @@ pub mod prover;
 #[cfg(feature = "prover")]
 pub use prover::*;
 
+pub mod mappings;
+pub use mappings::*;
+
 pub mod traits;
 pub use traits::*;
 
@@ -0,0 +1,111 @@
+// Copyright (C) 2019-2023 Aleo Systems Inc.
+// This file is part of the snarkOS library.
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at:
+// http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use snarkvm::{
+    ledger::{
+        block::{Input, Output, Transition},
+        store::{helpers::memory::FinalizeMemory, FinalizeStore},
+    },
+    prelude::{bail, Argument, Field, Future, Identifier, Network, Plaintext, ProgramID, Result, Value},
+    synthesizer::program::{FinalizeOperation, FinalizeStoreTrait},
+};
+
+use indexmap::IndexMap;
+
+/// Resolves the mappings and keys of finalize operations, which only commit to their mapping IDs and key IDs.
+///
+/// The candidate mappings and keys are initialized and inserted in an empty in-memory finalize store,
+/// so that their IDs are derived by the finalize store itself, as in the ledger.
+#[derive(Clone, Debug)]
+pub struct MappingResolver<N: Network> {
+    /// The candidate mappings, by mapping ID.
+    mappings: IndexMap<Field<N>, (ProgramID<N>, Identifier<N>)>,
+    /// The candidate keys, by key ID.
+    keys: IndexMap<Field<N>, Plaintext<N>>,
+}
+
+impl<N: Network> MappingResolver<N> {
+    /// Initializes a new resolver, for the given candidate keys in each of the given candidate mappings.
+    pub fn new(
+        mappings: impl IntoIterator<Item = (ProgramID<N>, Identifier<N>)>,
+        keys: &[&Plaintext<N>],
+    ) -> Result<Self> {
+        let store = FinalizeStore::<N, FinalizeMemory<N>>::open(None)?;
+        let mut resolver = Self { mappings: IndexMap::new(), keys: IndexMap::new() };
+        for (program_id, mapping_name) in mappings {
+            // Skip the mappings which are already initialized.
+            if resolver.mappings.values().any(|mapping| *mapping == (program_id, mapping_name)) {
+                continue;
+            }
+            let FinalizeOperation::InitializeMapping(mapping_id) =
+                store.initialize_mapping(&program_id, &mapping_name)?
+            else {
+                bail!("Failed to derive the mapping ID of '{program_id}/{mapping_name}'")
+            };
+            resolver.mappings.insert(mapping_id, (program_id, mapping_name));
+
+            for key in keys {
+                // Note: The key ID does not depend on the value, so the key is stored as its own value.
+                let value = Value::Plaintext((*key).clone());
+                let FinalizeOperation::UpdateKeyValue(_, _, key_id, _) =
+                    store.update_key_value(&program_id, &mapping_name, (*key).clone(), value)?
+                else {
+                    bail!("Failed to derive the key ID of '{key}' in '{program_id}/{mapping_name}'")
+                };
+                resolver.keys.insert(key_id, (*key).clone());
+            }
+        }
+        Ok(resolver)
+    }
+
+    /// Returns the candidate mapping with the given mapping ID, if there is one.
+    pub fn mapping(&self, mapping_id: &Field<N>) -> Option<&(ProgramID<N>, Identifier<N>)> {
+        self.mappings.get(mapping_id)
+    }
+
+    /// Returns the candidate key with the given key ID, if there is one.
+    pub fn key(&self, key_id: &Field<N>) -> Option<&Plaintext<N>> {
+        self.keys.get(key_id)
+    }
+}
+
+/// Returns the public inputs and outputs of the given transitions, including the finalize inputs of their futures,
+/// which are the candidate keys of their finalize operations.
+pub fn candidate_keys<'a, N: Network>(transitions: impl Iterator<Item = &'a Transition<N>>) -> Vec<&'a Plaintext<N>> {
+    let mut candidates = Vec::new();
+    for transition in transitions {
+        candidates.extend(transition.inputs().iter().filter_map(|input| match input {
+            Input::Constant(_, plaintext) | Input::Public(_, plaintext) => plaintext.as_ref(),
+            _ => None,
+        }));
+        for output in transition.outputs() {
+            match output {
+                Output::Constant(_, Some(plaintext)) | Output::Public(_, Some(plaintext)) => candidates.push(plaintext),
+                Output::Future(_, Some(future)) => future_arguments(future, &mut candidates),
+                _ => (),
+            }
+        }
+    }
+    candidates
+}
+
+/// Collects the plaintext arguments of the given future and its nested futures.
+fn future_arguments<'a, N: Network>(future: &'a Future<N>, candidates: &mut Vec<&'a Plaintext<N>>) {
+    for argument in future.arguments() {
+        match argument {
+            Argument::Plaintext(plaintext) => candidates.push(plaintext),
+            Argument::Future(future) => future_arguments(future, candidates),
+        }
+    }
+}
@@ -15,7 +15,7 @@
 use crate::{fmt_id, LedgerService};
 use snarkvm::{
     ledger::{
-        block::{Block, Transaction},
+        block::{Block, ConfirmedTransaction, Transaction},
         coinbase::{ProverSolution, PuzzleCommitment},
         committee::Committee,
         narwhal::{BatchCertificate, Data, Subdag, Transmission, TransmissionID},
@@ -163,6 +163,11 @@ impl<N: Network> LedgerService<N> for MockLedgerService<N> {
         Ok(())
     }
 
+    /// Speculates on the given transaction on top of the latest block, without modifying the ledger.
+    fn speculate_transaction(&self, _transaction: &Transaction<N>) -> Result<Option<ConfirmedTransaction<N>>> {
+        unreachable!("MockLedgerService does not support speculate_transaction")
+    }
+
     /// Checks the given block is valid next block.
     fn check_next_block(&self, _block: &Block<N>) -> Result<()> {
         Ok(())
@@ -15,7 +15,7 @@
 use crate::LedgerService;
 use snarkvm::{
     ledger::{
-        block::{Block, Transaction},
+        block::{Block, ConfirmedTransaction, Transaction},
         coinbase::{ProverSolution, PuzzleCommitment},
         committee::Committee,
         narwhal::{BatchCertificate, Data, Subdag, Transmission, TransmissionID},
@@ -142,6 +142,11 @@ impl<N: Network> LedgerService<N> for ProverLedgerService<N> {
         Ok(())
     }
 
+    /// Speculates on the given transaction on top of the latest block, without modifying the ledger.
+    fn speculate_transaction(&self, transaction: &Transaction<N>) -> Result<Option<ConfirmedTransaction<N>>> {
+        bail!("Cannot speculate on transaction '{}' in prover", transaction.id())
+    }
+
     /// Checks the given block is valid next block.
     fn check_next_block(&self, _block: &Block<N>) -> Result<()> {
         Ok(())
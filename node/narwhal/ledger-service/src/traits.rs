@@ -14,7 +14,7 @@
 
 use snarkvm::{
     ledger::{
-        block::{Block, Transaction},
+        block::{Block, ConfirmedTransaction, Transaction},
         coinbase::{ProverSolution, PuzzleCommitment},
         committee::Committee,
         narwhal::{BatchCertificate, Data, Subdag, Transmission, TransmissionID},
@@ -92,6 +92,10 @@ pub trait LedgerService<N: Network>: Debug + Send + Sync {
         transaction: Data<Transaction<N>>,
     ) -> Result<()>;
 
+    /// Speculates on the given transaction on top of the latest block, without modifying the ledger.
+    /// Returns the confirmed transaction, or `None` if the transaction would be aborted.
+    fn speculate_transaction(&self, transaction: &Transaction<N>) -> Result<Option<ConfirmedTransaction<N>>>;
+
     /// Checks the given block is valid next block.
     fn check_next_block(&self, block: &Block<N>) -> Result<()>;
 
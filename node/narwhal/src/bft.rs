@@ -55,6 +55,7 @@ use tokio::{
     sync::{oneshot, Mutex as TMutex, OnceCell},
     task::JoinHandle,
 };
+use tracing::{instrument, Span};
 
 #[derive(Clone)]
 pub struct BFT<N: Network> {
@@ -141,6 +142,16 @@ impl<N: Network> BFT<N> {
     pub const fn leader_certificate(&self) -> &Arc<RwLock<Option<BatchCertificate<N>>>> {
         &self.leader_certificate
     }
+
+    /// Returns the last committed round.
+    pub fn last_committed_round(&self) -> u64 {
+        self.dag.read().last_committed_round()
+    }
+
+    /// Returns `true` if the given certificate ID was recently committed in the given round.
+    pub fn is_recently_committed(&self, round: u64, certificate_id: Field<N>) -> bool {
+        self.dag.read().is_recently_committed(round, certificate_id)
+    }
 }
 
 impl<N: Network> BFT<N> {
@@ -202,7 +213,12 @@ impl<N: Network> BFT<N> {
         // Log the leader election.
         if current_round % 2 == 0 {
             if let Some(leader_certificate) = self.leader_certificate.read().as_ref() {
-                info!("\n\nRound {current_round} elected a leader - {}\n", leader_certificate.author());
+                info!(
+                    round = current_round,
+                    certificate_id = %leader_certificate.certificate_id(),
+                    "\n\nRound {current_round} elected a leader - {}\n",
+                    leader_certificate.author()
+                );
             }
         }
 
@@ -425,6 +441,11 @@ impl<N: Network> BFT<N> {
     }
 
     /// Commits the leader certificate, and all previous leader certificates since the last committed round.
+    #[instrument(
+        level = "trace",
+        skip_all,
+        fields(round = leader_certificate.round(), certificate_id = %leader_certificate.certificate_id())
+    )]
     async fn commit_leader_certificate<const ALLOW_LEDGER_ACCESS: bool, const IS_SYNCING: bool>(
         &self,
         leader_certificate: BatchCertificate<N>,
@@ -466,23 +487,31 @@ impl<N: Network> BFT<N> {
             // Construct the subdag.
             let subdag = Subdag::from(commit_subdag)?;
             info!(
+                round = leader_round,
                 "\n\nCommitting a subdag from round {leader_round} with {} transmissions: {:?}\n",
                 transmissions.len(),
                 subdag.iter().map(|(round, certificates)| (round, certificates.len())).collect::<Vec<_>>()
             );
+            // Link the commit to the spans of its transactions.
+            self.primary.link_transaction_spans(transmissions.keys());
+            // Retrieve the transmission IDs, to close the spans of the transactions once they are committed.
+            let transmission_ids = transmissions.keys().copied().collect::<Vec<_>>();
             // Trigger consensus.
             if let Some(consensus_sender) = self.consensus_sender.get() {
                 // Retrieve the anchor round.
                 let anchor_round = subdag.anchor_round();
                 // Initialize a callback sender and receiver.
                 let (callback_sender, callback_receiver) = oneshot::channel();
-                // Send the subdag and transmissions to consensus.
-                consensus_sender.tx_consensus_subdag.send((subdag, transmissions, callback_sender)).await?;
+                // Send the subdag and transmissions to consensus, so the block advance is traced within the commit.
+                let span = Span::current();
+                consensus_sender.tx_consensus_subdag.send((subdag, transmissions, span, callback_sender)).await?;
                 // Await the callback to continue.
                 if let Err(e) = callback_receiver.await {
                     error!("BFT failed to advance the subdag for round {anchor_round} - {e}");
                 }
             }
+            // Close the spans of the committed transactions.
+            self.primary.remove_transaction_spans(&transmission_ids);
         }
         Ok(())
     }
@@ -65,6 +65,7 @@ use tokio::{
 };
 use tokio_stream::StreamExt;
 use tokio_util::codec::Framed;
+use tracing::instrument;
 
 /// TODO (howardwu): Remove me and switch to dynamic.
 const MAX_COMMITTEE_SIZE: u16 = 100;
@@ -443,6 +444,7 @@ impl<N: Network> Gateway<N> {
     }
 
     /// Handles the inbound event from the peer.
+    #[instrument(level = "trace", skip_all, fields(peer = %peer_addr, event = %event.name()))]
     async fn inbound(&self, peer_addr: SocketAddr, event: Event<N>) -> Result<()> {
         // Retrieve the listener IP for the peer.
         let Some(peer_ip) = self.resolver.get_listener(peer_addr) else {
@@ -37,19 +37,26 @@ use snarkvm::{
 use indexmap::IndexMap;
 use std::net::SocketAddr;
 use tokio::sync::{mpsc, oneshot};
+use tracing::Span;
 
 const MAX_CHANNEL_SIZE: usize = 8192;
 
+/// The committed subdag and transmissions, with the span of the commit, and the callback for the block advance.
+pub type ConsensusSubdag<N> =
+    (Subdag<N>, IndexMap<TransmissionID<N>, Transmission<N>>, Span, oneshot::Sender<Result<()>>);
+
+/// The unconfirmed transaction, with the span it is traced in, and the callback for the memory pool.
+pub type UnconfirmedTransaction<N> =
+    (<N as Network>::TransactionID, Data<Transaction<N>>, Span, oneshot::Sender<Result<()>>);
+
 #[derive(Debug)]
 pub struct ConsensusSender<N: Network> {
-    pub tx_consensus_subdag:
-        mpsc::Sender<(Subdag<N>, IndexMap<TransmissionID<N>, Transmission<N>>, oneshot::Sender<Result<()>>)>,
+    pub tx_consensus_subdag: mpsc::Sender<ConsensusSubdag<N>>,
 }
 
 #[derive(Debug)]
 pub struct ConsensusReceiver<N: Network> {
-    pub rx_consensus_subdag:
-        mpsc::Receiver<(Subdag<N>, IndexMap<TransmissionID<N>, Transmission<N>>, oneshot::Sender<Result<()>>)>,
+    pub rx_consensus_subdag: mpsc::Receiver<ConsensusSubdag<N>>,
 }
 
 /// Initializes the consensus channels.
@@ -130,7 +137,7 @@ pub struct PrimarySender<N: Network> {
     pub tx_batch_certified: mpsc::Sender<(SocketAddr, Data<BatchCertificate<N>>)>,
     pub tx_unconfirmed_solution:
         mpsc::Sender<(PuzzleCommitment<N>, Data<ProverSolution<N>>, oneshot::Sender<Result<()>>)>,
-    pub tx_unconfirmed_transaction: mpsc::Sender<(N::TransactionID, Data<Transaction<N>>, oneshot::Sender<Result<()>>)>,
+    pub tx_unconfirmed_transaction: mpsc::Sender<UnconfirmedTransaction<N>>,
 }
 
 #[derive(Debug)]
@@ -140,8 +147,7 @@ pub struct PrimaryReceiver<N: Network> {
     pub rx_batch_certified: mpsc::Receiver<(SocketAddr, Data<BatchCertificate<N>>)>,
     pub rx_unconfirmed_solution:
         mpsc::Receiver<(PuzzleCommitment<N>, Data<ProverSolution<N>>, oneshot::Sender<Result<()>>)>,
-    pub rx_unconfirmed_transaction:
-        mpsc::Receiver<(N::TransactionID, Data<Transaction<N>>, oneshot::Sender<Result<()>>)>,
+    pub rx_unconfirmed_transaction: mpsc::Receiver<UnconfirmedTransaction<N>>,
 }
 
 /// Initializes the primary channels.
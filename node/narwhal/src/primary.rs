@@ -31,6 +31,7 @@ use crate::{
     Transport,
     Worker,
     MAX_BATCH_DELAY,
+    MAX_TRANSACTION_SPANS,
     MAX_TRANSMISSIONS_PER_BATCH,
     MAX_WORKERS,
     PRIMARY_PING_INTERVAL,
@@ -64,6 +65,7 @@ use tokio::{
     sync::{Mutex as TMutex, OnceCell},
     task::{self, JoinHandle},
 };
+use tracing::{instrument, Span};
 
 /// A helper type for an optional proposed batch.
 pub type ProposedBatch<N> = RwLock<Option<Proposal<N>>>;
@@ -84,6 +86,8 @@ pub struct Primary<N: Network> {
     bft_sender: Arc<OnceCell<BFTSender<N>>>,
     /// The batch proposal, if the primary is currently proposing a batch.
     proposed_batch: Arc<ProposedBatch<N>>,
+    /// The spans of the unconfirmed transactions, which are linked to their batch, certificate, and commit.
+    transaction_spans: Arc<Mutex<IndexMap<N::TransactionID, Span>>>,
     /// The spawned handles.
     handles: Arc<Mutex<Vec<JoinHandle<()>>>>,
     /// The primary lock.
@@ -113,6 +117,7 @@ impl<N: Network> Primary<N> {
             workers: Arc::from(vec![]),
             bft_sender: Default::default(),
             proposed_batch: Default::default(),
+            transaction_spans: Default::default(),
             handles: Default::default(),
             lock: Default::default(),
         })
@@ -230,6 +235,48 @@ impl<N: Network> Primary<N> {
     }
 }
 
+impl<N: Network> Primary<N> {
+    /// Inserts the span of the given unconfirmed transaction, evicting the oldest span if the limit is reached.
+    fn insert_transaction_span(&self, transaction_id: N::TransactionID, span: Span) {
+        // Skip the transaction if its span is disabled.
+        if span.is_none() {
+            return;
+        }
+        let mut transaction_spans = self.transaction_spans.lock();
+        transaction_spans.insert(transaction_id, span);
+        // Evict the oldest spans, so that transactions which are never committed do not keep their span open.
+        while transaction_spans.len() > MAX_TRANSACTION_SPANS {
+            transaction_spans.shift_remove_index(0);
+        }
+    }
+
+    /// Links the current span to the spans of the unconfirmed transactions in the given transmissions.
+    pub(crate) fn link_transaction_spans<'a>(&self, transmission_ids: impl IntoIterator<Item = &'a TransmissionID<N>>) {
+        let current_span = Span::current();
+        let transaction_spans = self.transaction_spans.lock();
+        for transmission_id in transmission_ids {
+            if let TransmissionID::Transaction(transaction_id) = transmission_id {
+                if let Some(span) = transaction_spans.get(transaction_id) {
+                    current_span.follows_from(span);
+                }
+            }
+        }
+    }
+
+    /// Removes the spans of the unconfirmed transactions in the given transmissions, closing the spans.
+    pub(crate) fn remove_transaction_spans<'a>(
+        &self,
+        transmission_ids: impl IntoIterator<Item = &'a TransmissionID<N>>,
+    ) {
+        let mut transaction_spans = self.transaction_spans.lock();
+        for transmission_id in transmission_ids {
+            if let TransmissionID::Transaction(transaction_id) = transmission_id {
+                transaction_spans.shift_remove(transaction_id);
+            }
+        }
+    }
+}
+
 impl<N: Network> Primary<N> {
     /// Returns the unconfirmed transmission IDs.
     pub fn unconfirmed_transmission_ids(&self) -> impl '_ + Iterator<Item = TransmissionID<N>> {
@@ -260,6 +307,7 @@ impl<N: Network> Primary<N> {
     /// 2. Sign the batch.
     /// 3. Set the batch proposal in the primary.
     /// 4. Broadcast the batch header to all validators for signing.
+    #[instrument(level = "trace", skip_all, fields(round = self.current_round()))]
     pub async fn propose_batch(&self) -> Result<()> {
         // Check if the proposed batch has expired, and clear it if it has expired.
         self.check_proposed_batch_for_expiration().await?;
@@ -276,7 +324,12 @@ impl<N: Network> Primary<N> {
                 match self.gateway.resolver().get_peer_ip_for_address(address) {
                     // Broadcast the batch to all validators for signing.
                     Some(peer_ip) => {
-                        debug!("Resending batch proposal for round {} to peer '{peer_ip}'", proposal.round());
+                        debug!(
+                            peer = %peer_ip,
+                            round = proposal.round(),
+                            "Resending batch proposal for round {} to peer '{peer_ip}'",
+                            proposal.round()
+                        );
                         // Broadcast the event.
                         let self_ = self.clone();
                         let event_ = event.clone();
@@ -348,6 +401,8 @@ impl<N: Network> Primary<N> {
             true => info!("Proposing a batch with {} transmissions for round {round}...", transmissions.len()),
             false => return Ok(()),
         }
+        // Link the proposal to the spans of its transactions.
+        self.link_transaction_spans(transmissions.keys());
 
         /* Proceeding to sign & propose the batch. */
 
@@ -382,6 +437,7 @@ impl<N: Network> Primary<N> {
     ///
     /// If our primary is ahead of the peer, we will not sign the batch.
     /// If our primary is behind the peer, but within GC range, we will sync up to the peer's round, and then sign the batch.
+    #[instrument(level = "trace", skip_all, fields(peer = %peer_ip, round = batch_propose.round))]
     async fn process_batch_propose_from_peer(&self, peer_ip: SocketAddr, batch_propose: BatchPropose<N>) -> Result<()> {
         let BatchPropose { round: batch_round, batch_header } = batch_propose;
 
@@ -443,7 +499,7 @@ impl<N: Network> Primary<N> {
             let event = Event::BatchSignature(BatchSignature::new(batch_id, signature, timestamp));
             // Send the batch signature to the peer.
             if self_.gateway.send(peer_ip, event).await.is_some() {
-                debug!("Signed a batch for round {batch_round} from '{peer_ip}'");
+                debug!(peer = %peer_ip, round = batch_round, "Signed a batch for round {batch_round} from '{peer_ip}'");
             }
         });
         Ok(())
@@ -457,6 +513,7 @@ impl<N: Network> Primary<N> {
     /// 3. Store the signature.
     /// 4. Certify the batch if enough signatures have been received.
     /// 5. Broadcast the batch certificate to all validators.
+    #[instrument(level = "trace", skip_all, fields(peer = %peer_ip, batch_id = %batch_signature.batch_id))]
     async fn process_batch_signature_from_peer(
         &self,
         peer_ip: SocketAddr,
@@ -508,7 +565,12 @@ impl<N: Network> Primary<N> {
                         Some(signer) => proposal.add_signature(signer, signature, timestamp, &previous_committee)?,
                         None => bail!("Signature is from a disconnected peer"),
                     };
-                    info!("Received a batch signature for round {} from '{peer_ip}'", proposal.round());
+                    info!(
+                        peer = %peer_ip,
+                        round = proposal.round(),
+                        "Received a batch signature for round {} from '{peer_ip}'",
+                        proposal.round()
+                    );
                     // Check if the batch is ready to be certified.
                     if !proposal.is_quorum_threshold_reached(&previous_committee) {
                         // If the batch is not ready to be certified, return early.
@@ -547,6 +609,11 @@ impl<N: Network> Primary<N> {
     /// 1. Stores the given batch certificate, after ensuring it is valid.
     /// 2. If there are enough certificates to reach quorum threshold for the current round,
     ///  then proceed to advance to the next round.
+    #[instrument(
+        level = "trace",
+        skip_all,
+        fields(peer = %peer_ip, round = certificate.round(), certificate_id = %certificate.certificate_id())
+    )]
     async fn process_batch_certificate_from_peer(
         &self,
         peer_ip: SocketAddr,
@@ -755,7 +822,7 @@ impl<N: Network> Primary<N> {
         // Process the unconfirmed transactions.
         let self_ = self.clone();
         self.spawn(async move {
-            while let Some((transaction_id, transaction, callback)) = rx_unconfirmed_transaction.recv().await {
+            while let Some((transaction_id, transaction, span, callback)) = rx_unconfirmed_transaction.recv().await {
                 // Compute the worker ID.
                 let Ok(worker_id) = assign_to_worker::<N>(&transaction_id, self_.num_workers()) else {
                     error!("Unable to determine the worker ID for the unconfirmed transaction");
@@ -767,6 +834,10 @@ impl<N: Network> Primary<N> {
                     let worker = &self_.workers[worker_id as usize];
                     // Process the unconfirmed transaction.
                     let result = worker.process_unconfirmed_transaction(transaction_id, transaction).await;
+                    // Keep the span of the transaction, to link it to its batch, certificate, and commit.
+                    if result.is_ok() {
+                        self_.insert_transaction_span(transaction_id, span);
+                    }
                     // Send the result to the callback.
                     callback.send(result).ok();
                 });
@@ -854,15 +925,23 @@ impl<N: Network> Primary<N> {
     }
 
     /// Stores the certified batch and broadcasts it to all validators, returning the certificate.
+    #[instrument(level = "trace", skip_all, fields(round = proposal.round()))]
     async fn store_and_broadcast_certificate(&self, proposal: &Proposal<N>, committee: &Committee<N>) -> Result<()> {
         // Create the batch certificate and transmissions.
         let (certificate, transmissions) = proposal.to_certificate(committee)?;
+        // Link the certificate to the spans of its transactions.
+        self.link_transaction_spans(certificate.transmission_ids());
         // Convert the transmissions into a HashMap.
         // Note: Do not change the `Proposal` to use a HashMap. The ordering there is necessary for safety.
         let transmissions = transmissions.into_iter().collect::<HashMap<_, _>>();
         // Store the certified batch.
         self.storage.insert_certificate(certificate.clone(), transmissions)?;
-        debug!("Stored a batch certificate for round {}", certificate.round());
+        debug!(
+            round = certificate.round(),
+            certificate_id = %certificate.certificate_id(),
+            "Stored a batch certificate for round {}",
+            certificate.round()
+        );
         // If a BFT sender was provided, send the certificate to the BFT.
         if let Some(bft_sender) = self.bft_sender.get() {
             // Await the callback to continue.
@@ -876,7 +955,11 @@ impl<N: Network> Primary<N> {
         // Log the certified batch.
         let num_transmissions = certificate.transmission_ids().len();
         let round = certificate.round();
-        info!("\n\nOur batch with {num_transmissions} transmissions for round {round} was certified!\n");
+        info!(
+            round,
+            certificate_id = %certificate.certificate_id(),
+            "\n\nOur batch with {num_transmissions} transmissions for round {round} was certified!\n"
+        );
         // Increment to the next round.
         self.try_increment_to_the_next_round(round + 1).await
     }
@@ -941,7 +1024,12 @@ impl<N: Network> Primary<N> {
         if !self.storage.contains_certificate(certificate.certificate_id()) {
             // Store the batch certificate.
             self.storage.insert_certificate(certificate.clone(), missing_transmissions)?;
-            debug!("Stored a batch certificate for round {batch_round} from '{peer_ip}'");
+            debug!(
+                peer = %peer_ip,
+                round = batch_round,
+                certificate_id = %certificate.certificate_id(),
+                "Stored a batch certificate for round {batch_round} from '{peer_ip}'"
+            );
             // If a BFT sender was provided, send the round and certificate to the BFT.
             if let Some(bft_sender) = self.bft_sender.get() {
                 // Send the certificate to the BFT.
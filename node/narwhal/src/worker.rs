@@ -457,7 +457,7 @@ mod tests {
     use snarkvm::{
         console::{network::Network, types::Field},
         ledger::{
-            block::Block,
+            block::{Block, ConfirmedTransaction},
             committee::Committee,
             narwhal::{BatchCertificate, Subdag, Transmission, TransmissionID},
         },
@@ -510,6 +510,7 @@ mod tests {
                 transaction_id: N::TransactionID,
                 transaction: Data<Transaction<N>>,
             ) -> Result<()>;
+            fn speculate_transaction(&self, transaction: &Transaction<N>) -> Result<Option<ConfirmedTransaction<N>>>;
             fn check_next_block(&self, block: &Block<N>) -> Result<()>;
             fn prepare_advance_to_next_quorum_block(
                 &self,
@@ -0,0 +1,313 @@
+// Copyright (C) 2019-2023 Aleo Systems Inc.
+// This file is part of the snarkOS library.
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at:
+// http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use super::*;
+
+use std::net::IpAddr;
+use tokio::runtime::Runtime;
+
+/// A blocking client for the REST API of a node, which runs the requests of a `RestClient`
+/// on its own runtime.
+///
+/// Note: The methods of this client must not be called from within an async runtime.
+pub struct RestClient<N: Network> {
+    /// The async client.
+    client: super::RestClient<N>,
+    /// The runtime of the client.
+    runtime: Runtime,
+}
+
+impl<N: Network> RestClient<N> {
+    /// Initializes a new client for the node at the given base URL, such as `http://127.0.0.1:3033`.
+    pub fn new(base_url: &str) -> Result<Self> {
+        let runtime = tokio::runtime::Builder::new_current_thread().enable_all().build()?;
+        Ok(Self { client: super::RestClient::new(base_url)?, runtime })
+    }
+
+    /// Sets the JSON Web Token of the node, which is printed by the node on startup.
+    pub fn with_token(mut self, token: &str) -> Self {
+        self.client = self.client.with_token(token);
+        self
+    }
+
+    /// Returns the base URL of the node.
+    pub const fn base_url(&self) -> &Url {
+        self.client.base_url()
+    }
+
+    // GET /testnet3/latest/height
+    pub fn latest_height(&self) -> Result<u32> {
+        self.runtime.block_on(self.client.latest_height())
+    }
+
+    // GET /testnet3/latest/hash
+    pub fn latest_hash(&self) -> Result<N::BlockHash> {
+        self.runtime.block_on(self.client.latest_hash())
+    }
+
+    // GET /testnet3/latest/block
+    pub fn latest_block(&self) -> Result<Block<N>> {
+        self.runtime.block_on(self.client.latest_block())
+    }
+
+    // GET /testnet3/latest/stateRoot
+    pub fn latest_state_root(&self) -> Result<N::StateRoot> {
+        self.runtime.block_on(self.client.latest_state_root())
+    }
+
+    // GET /testnet3/latest/committee
+    pub fn latest_committee(&self) -> Result<Committee<N>> {
+        self.runtime.block_on(self.client.latest_committee())
+    }
+
+    // GET /testnet3/block/{height}
+    pub fn get_block(&self, height: u32) -> Result<Block<N>> {
+        self.runtime.block_on(self.client.get_block(height))
+    }
+
+    // GET /testnet3/block/{blockHash}
+    pub fn get_block_by_hash(&self, hash: &N::BlockHash) -> Result<Block<N>> {
+        self.runtime.block_on(self.client.get_block_by_hash(hash))
+    }
+
+    // GET /testnet3/block/{height}/transactions
+    pub fn get_block_transactions(&self, height: u32) -> Result<Transactions<N>> {
+        self.runtime.block_on(self.client.get_block_transactions(height))
+    }
+
+    // GET /testnet3/blocks?start={start_height}&end={end_height}
+    pub fn get_blocks(&self, start_height: u32, end_height: u32) -> Result<Vec<Block<N>>> {
+        self.runtime.block_on(self.client.get_blocks(start_height, end_height))
+    }
+
+    // GET /testnet3/height/{blockHash}
+    pub fn get_height(&self, hash: &N::BlockHash) -> Result<u32> {
+        self.runtime.block_on(self.client.get_height(hash))
+    }
+
+    // GET /testnet3/transaction/{transactionID}
+    pub fn get_transaction(&self, transaction_id: &N::TransactionID) -> Result<Transaction<N>> {
+        self.runtime.block_on(self.client.get_transaction(transaction_id))
+    }
+
+    // POST /testnet3/transaction/broadcast
+    pub fn broadcast_transaction(&self, transaction: &Transaction<N>) -> Result<N::TransactionID> {
+        self.runtime.block_on(self.client.broadcast_transaction(transaction))
+    }
+
+    // POST /testnet3/transaction/estimateFee
+    pub fn estimate_fee(&self, request: &FeeRequest<N>) -> Result<FeeEstimate> {
+        self.runtime.block_on(self.client.estimate_fee(request))
+    }
+
+    // POST /testnet3/transaction/simulate
+    pub fn simulate_transaction(&self, transaction: &Transaction<N>) -> Result<Simulation<N>> {
+        self.runtime.block_on(self.client.simulate_transaction(transaction))
+    }
+
+    // GET /testnet3/find/blockHash/{transactionID}
+    pub fn find_block_hash(&self, transaction_id: &N::TransactionID) -> Result<Option<N::BlockHash>> {
+        self.runtime.block_on(self.client.find_block_hash(transaction_id))
+    }
+
+    // GET /testnet3/find/transactionID/deployment/{programID}
+    pub fn find_transaction_id_from_program_id(&self, program_id: &ProgramID<N>) -> Result<Option<N::TransactionID>> {
+        self.runtime.block_on(self.client.find_transaction_id_from_program_id(program_id))
+    }
+
+    // GET /testnet3/find/transactionID/{transitionID}
+    pub fn find_transaction_id_from_transition_id(
+        &self,
+        transition_id: &N::TransitionID,
+    ) -> Result<Option<N::TransactionID>> {
+        self.runtime.block_on(self.client.find_transaction_id_from_transition_id(transition_id))
+    }
+
+    // GET /testnet3/find/transitionID/{inputOrOutputID}
+    pub fn find_transition_id(&self, input_or_output_id: &Field<N>) -> Result<N::TransitionID> {
+        self.runtime.block_on(self.client.find_transition_id(input_or_output_id))
+    }
+
+    // GET /testnet3/peers/count
+    pub fn get_peers_count(&self) -> Result<usize> {
+        self.runtime.block_on(self.client.get_peers_count())
+    }
+
+    // GET /testnet3/peers/all
+    pub fn get_peers_all(&self) -> Result<Vec<SocketAddr>> {
+        self.runtime.block_on(self.client.get_peers_all())
+    }
+
+    // GET /testnet3/peers/all/metrics
+    pub fn get_peers_all_metrics(&self) -> Result<Vec<(SocketAddr, NodeType)>> {
+        self.runtime.block_on(self.client.get_peers_all_metrics())
+    }
+
+    // GET /testnet3/programs?prefix={prefix}&cursor={cursor}&limit={limit}
+    pub fn get_programs(
+        &self,
+        prefix: Option<&str>,
+        cursor: Option<usize>,
+        limit: Option<usize>,
+    ) -> Result<ProgramList<N>> {
+        self.runtime.block_on(self.client.get_programs(prefix, cursor, limit))
+    }
+
+    // GET /testnet3/program/{programID}
+    pub fn get_program(&self, program_id: &ProgramID<N>) -> Result<Program<N>> {
+        self.runtime.block_on(self.client.get_program(program_id))
+    }
+
+    // GET /testnet3/program/{programID}, returning `None` if the program is not deployed.
+    pub fn find_program(&self, program_id: &ProgramID<N>) -> Result<Option<Program<N>>> {
+        self.runtime.block_on(self.client.find_program(program_id))
+    }
+
+    // GET /testnet3/program/{programID}/abi
+    pub fn get_program_abi(&self, program_id: &ProgramID<N>) -> Result<ProgramAbi<N>> {
+        self.runtime.block_on(self.client.get_program_abi(program_id))
+    }
+
+    // GET /testnet3/program/{programID}/mappings
+    pub fn get_mapping_names(&self, program_id: &ProgramID<N>) -> Result<Option<Vec<Identifier<N>>>> {
+        self.runtime.block_on(self.client.get_mapping_names(program_id))
+    }
+
+    // GET /testnet3/program/{programID}/mapping/{mappingName}?cursor={cursor}&limit={limit}
+    pub fn get_mapping(
+        &self,
+        program_id: &ProgramID<N>,
+        mapping_name: &Identifier<N>,
+        cursor: Option<&Plaintext<N>>,
+        limit: Option<usize>,
+    ) -> Result<MappingEntries<N>> {
+        self.runtime.block_on(self.client.get_mapping(program_id, mapping_name, cursor, limit))
+    }
+
+    // GET /testnet3/program/{programID}/mapping/{mappingName}/{mappingKey}
+    pub fn get_mapping_value(
+        &self,
+        program_id: &ProgramID<N>,
+        mapping_name: &Identifier<N>,
+        key: &Plaintext<N>,
+    ) -> Result<Option<Value<N>>> {
+        self.runtime.block_on(self.client.get_mapping_value(program_id, mapping_name, key))
+    }
+
+    // GET /testnet3/program/{programID}/transactions?function={functionName}&cursor={cursor}&limit={limit}
+    pub fn get_program_transactions(
+        &self,
+        program_id: &ProgramID<N>,
+        function_name: Option<&Identifier<N>>,
+        cursor: Option<u64>,
+        limit: Option<usize>,
+    ) -> Result<TransactionPage<N>> {
+        self.runtime.block_on(self.client.get_program_transactions(program_id, function_name, cursor, limit))
+    }
+
+    // GET /testnet3/address/{address}/transactions?cursor={cursor}&limit={limit}
+    pub fn get_address_transactions(
+        &self,
+        address: &Address<N>,
+        cursor: Option<u64>,
+        limit: Option<usize>,
+    ) -> Result<TransactionPage<N>> {
+        self.runtime.block_on(self.client.get_address_transactions(address, cursor, limit))
+    }
+
+    // GET /testnet3/memoryPool/transmissions
+    pub fn get_memory_pool_transmissions(&self) -> Result<IndexMap<TransmissionID<N>, Transmission<N>>> {
+        self.runtime.block_on(self.client.get_memory_pool_transmissions())
+    }
+
+    // GET /testnet3/memoryPool/solutions
+    pub fn get_memory_pool_solutions(&self) -> Result<IndexMap<PuzzleCommitment<N>, Data<ProverSolution<N>>>> {
+        self.runtime.block_on(self.client.get_memory_pool_solutions())
+    }
+
+    // GET /testnet3/memoryPool/transactions
+    pub fn get_memory_pool_transactions(&self) -> Result<IndexMap<N::TransactionID, Data<Transaction<N>>>> {
+        self.runtime.block_on(self.client.get_memory_pool_transactions())
+    }
+
+    // GET /testnet3/statePath/{commitment}
+    pub fn get_state_path_for_commitment(&self, commitment: &Field<N>) -> Result<StatePath<N>> {
+        self.runtime.block_on(self.client.get_state_path_for_commitment(commitment))
+    }
+
+    // GET /testnet3/committee/latest
+    pub fn get_committee_latest(&self) -> Result<Committee<N>> {
+        self.runtime.block_on(self.client.get_committee_latest())
+    }
+
+    // GET /testnet3/peers/bans
+    pub fn get_peers_bans(&self) -> Result<Vec<IpAddr>> {
+        self.runtime.block_on(self.client.get_peers_bans())
+    }
+
+    // POST /testnet3/peers/connect
+    pub fn connect_peer(&self, peer_ip: SocketAddr) -> Result<bool> {
+        self.runtime.block_on(self.client.connect_peer(peer_ip))
+    }
+
+    // POST /testnet3/peers/disconnect
+    pub fn disconnect_peer(&self, peer_ip: SocketAddr) -> Result<bool> {
+        self.runtime.block_on(self.client.disconnect_peer(peer_ip))
+    }
+
+    // POST /testnet3/peers/ban
+    pub fn ban_peer(&self, ip: IpAddr) -> Result<bool> {
+        self.runtime.block_on(self.client.ban_peer(ip))
+    }
+
+    // POST /testnet3/peers/unban
+    pub fn unban_peer(&self, ip: IpAddr) -> Result<bool> {
+        self.runtime.block_on(self.client.unban_peer(ip))
+    }
+
+    // POST /testnet3/validators/connect
+    pub fn connect_validator(&self, peer_ip: SocketAddr) -> Result<bool> {
+        self.runtime.block_on(self.client.connect_validator(peer_ip))
+    }
+
+    // POST /testnet3/validators/disconnect
+    pub fn disconnect_validator(&self, peer_ip: SocketAddr) -> Result<bool> {
+        self.runtime.block_on(self.client.disconnect_validator(peer_ip))
+    }
+
+    // GET /testnet3/node/address
+    pub fn get_node_address(&self) -> Result<Address<N>> {
+        self.runtime.block_on(self.client.get_node_address())
+    }
+
+    // GET /testnet3/node/logFilter
+    pub fn get_log_filter(&self) -> Result<String> {
+        self.runtime.block_on(self.client.get_log_filter())
+    }
+
+    // POST /testnet3/node/logFilter
+    pub fn set_log_filter(&self, directives: &str) -> Result<String> {
+        self.runtime.block_on(self.client.set_log_filter(directives))
+    }
+
+    // GET /testnet3/openapi.json
+    pub fn get_openapi(&self) -> Result<JsonValue> {
+        self.runtime.block_on(self.client.get_openapi())
+    }
+
+    // POST /rpc
+    pub fn rpc(&self, request: &JsonValue) -> Result<JsonValue> {
+        self.runtime.block_on(self.client.rpc(request))
+    }
+}
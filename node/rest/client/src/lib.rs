@@ -0,0 +1,505 @@
+// Copyright (C) 2019-2023 Aleo Systems Inc.
+// This file is part of the snarkOS library.
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at:
+// http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+#![forbid(unsafe_code)]
+
+pub mod blocking;
+
+pub use snarkos_node_rest_types::*;
+
+use snarkos_node_router_messages::NodeType;
+use snarkvm::{
+    ledger::{
+        block::{Block, Transaction, Transactions},
+        coinbase::{ProverSolution, PuzzleCommitment},
+        committee::Committee,
+        narwhal::{Data, Transmission, TransmissionID},
+    },
+    prelude::{Address, Field, FromBytes, Identifier, Network, Plaintext, Program, ProgramID, StatePath, Value},
+};
+
+use anyhow::{anyhow, bail, Result};
+use indexmap::IndexMap;
+use reqwest::{
+    header::{ACCEPT, CONTENT_TYPE, RETRY_AFTER},
+    Method,
+    RequestBuilder,
+    Response,
+    StatusCode,
+    Url,
+};
+use serde::{de::DeserializeOwned, Serialize};
+use serde_json::Value as JsonValue;
+use std::{
+    fmt,
+    marker::PhantomData,
+    net::{IpAddr, SocketAddr},
+    time::Duration,
+};
+
+/// The maximum number of times a rate limited request is retried.
+const MAX_RETRIES: usize = 5;
+/// The maximum time to wait before retrying a rate limited request.
+const MAX_RETRY_AFTER: Duration = Duration::from_secs(30);
+
+/// A typed client for the REST API of a node, as served by `snarkos_node_rest::Rest`.
+#[derive(Clone)]
+pub struct RestClient<N: Network> {
+    /// The base URL of the node, such as `http://127.0.0.1:3033`.
+    base_url: Url,
+    /// The HTTP client.
+    client: reqwest::Client,
+    /// The JSON Web Token of the node, for the authenticated routes.
+    token: Option<String>,
+    /// PhantomData.
+    _phantom: PhantomData<N>,
+}
+
+impl<N: Network> RestClient<N> {
+    /// Initializes a new client for the node at the given base URL, such as `http://127.0.0.1:3033`.
+    pub fn new(base_url: &str) -> Result<Self> {
+        let base_url = Url::parse(base_url)?;
+        if base_url.cannot_be_a_base() {
+            bail!("'{base_url}' is not a valid base URL");
+        }
+        Ok(Self { base_url, client: reqwest::Client::new(), token: None, _phantom: PhantomData })
+    }
+
+    /// Sets the JSON Web Token of the node, which is printed by the node on startup.
+    pub fn with_token(mut self, token: &str) -> Self {
+        self.token = Some(token.to_string());
+        self
+    }
+
+    /// Returns the base URL of the node.
+    pub const fn base_url(&self) -> &Url {
+        &self.base_url
+    }
+
+    // GET /testnet3/latest/height
+    pub async fn latest_height(&self) -> Result<u32> {
+        self.get(&["latest", "height"]).await
+    }
+
+    // GET /testnet3/latest/hash
+    pub async fn latest_hash(&self) -> Result<N::BlockHash> {
+        self.get(&["latest", "hash"]).await
+    }
+
+    // GET /testnet3/latest/block
+    pub async fn latest_block(&self) -> Result<Block<N>> {
+        self.get(&["latest", "block"]).await
+    }
+
+    // GET /testnet3/latest/stateRoot
+    pub async fn latest_state_root(&self) -> Result<N::StateRoot> {
+        self.get(&["latest", "stateRoot"]).await
+    }
+
+    // GET /testnet3/latest/committee
+    pub async fn latest_committee(&self) -> Result<Committee<N>> {
+        self.get(&["latest", "committee"]).await
+    }
+
+    // GET /testnet3/block/{height}
+    pub async fn get_block(&self, height: u32) -> Result<Block<N>> {
+        Self::send_binary(self.request(Method::GET, self.url(&["block", &height.to_string()])?)).await
+    }
+
+    // GET /testnet3/block/{blockHash}
+    pub async fn get_block_by_hash(&self, hash: &N::BlockHash) -> Result<Block<N>> {
+        Self::send_binary(self.request(Method::GET, self.url(&["block", &hash.to_string()])?)).await
+    }
+
+    // GET /testnet3/block/{height}/transactions
+    pub async fn get_block_transactions(&self, height: u32) -> Result<Transactions<N>> {
+        self.get(&["block", &height.to_string(), "transactions"]).await
+    }
+
+    // GET /testnet3/blocks?start={start_height}&end={end_height}
+    pub async fn get_blocks(&self, start_height: u32, end_height: u32) -> Result<Vec<Block<N>>> {
+        let request =
+            self.request(Method::GET, self.url(&["blocks"])?).query(&[("start", start_height), ("end", end_height)]);
+        Self::send_binary(request).await
+    }
+
+    // GET /testnet3/height/{blockHash}
+    pub async fn get_height(&self, hash: &N::BlockHash) -> Result<u32> {
+        self.get(&["height", &hash.to_string()]).await
+    }
+
+    // GET /testnet3/transaction/{transactionID}
+    pub async fn get_transaction(&self, transaction_id: &N::TransactionID) -> Result<Transaction<N>> {
+        self.get(&["transaction", &transaction_id.to_string()]).await
+    }
+
+    // POST /testnet3/transaction/broadcast
+    pub async fn broadcast_transaction(&self, transaction: &Transaction<N>) -> Result<N::TransactionID> {
+        self.post(&["transaction", "broadcast"], transaction).await
+    }
+
+    // POST /testnet3/transaction/estimateFee
+    pub async fn estimate_fee(&self, request: &FeeRequest<N>) -> Result<FeeEstimate> {
+        self.post(&["transaction", "estimateFee"], request).await
+    }
+
+    // POST /testnet3/transaction/simulate
+    pub async fn simulate_transaction(&self, transaction: &Transaction<N>) -> Result<Simulation<N>> {
+        self.post(&["transaction", "simulate"], transaction).await
+    }
+
+    // GET /testnet3/find/blockHash/{transactionID}
+    pub async fn find_block_hash(&self, transaction_id: &N::TransactionID) -> Result<Option<N::BlockHash>> {
+        self.get(&["find", "blockHash", &transaction_id.to_string()]).await
+    }
+
+    // GET /testnet3/find/transactionID/deployment/{programID}
+    pub async fn find_transaction_id_from_program_id(
+        &self,
+        program_id: &ProgramID<N>,
+    ) -> Result<Option<N::TransactionID>> {
+        self.get(&["find", "transactionID", "deployment", &program_id.to_string()]).await
+    }
+
+    // GET /testnet3/find/transactionID/{transitionID}
+    pub async fn find_transaction_id_from_transition_id(
+        &self,
+        transition_id: &N::TransitionID,
+    ) -> Result<Option<N::TransactionID>> {
+        self.get(&["find", "transactionID", &transition_id.to_string()]).await
+    }
+
+    // GET /testnet3/find/transitionID/{inputOrOutputID}
+    pub async fn find_transition_id(&self, input_or_output_id: &Field<N>) -> Result<N::TransitionID> {
+        self.get(&["find", "transitionID", &input_or_output_id.to_string()]).await
+    }
+
+    // GET /testnet3/peers/count
+    pub async fn get_peers_count(&self) -> Result<usize> {
+        self.get(&["peers", "count"]).await
+    }
+
+    // GET /testnet3/peers/all
+    pub async fn get_peers_all(&self) -> Result<Vec<SocketAddr>> {
+        self.get(&["peers", "all"]).await
+    }
+
+    // GET /testnet3/peers/all/metrics
+    pub async fn get_peers_all_metrics(&self) -> Result<Vec<(SocketAddr, NodeType)>> {
+        self.get(&["peers", "all", "metrics"]).await
+    }
+
+    // GET /testnet3/programs?prefix={prefix}&cursor={cursor}&limit={limit}
+    pub async fn get_programs(
+        &self,
+        prefix: Option<&str>,
+        cursor: Option<usize>,
+        limit: Option<usize>,
+    ) -> Result<ProgramList<N>> {
+        let mut request = self.request(Method::GET, self.url(&["programs"])?);
+        if let Some(prefix) = prefix {
+            request = request.query(&[("prefix", prefix)]);
+        }
+        Self::send(Self::page(request, cursor, limit)).await
+    }
+
+    // GET /testnet3/program/{programID}
+    pub async fn get_program(&self, program_id: &ProgramID<N>) -> Result<Program<N>> {
+        self.get(&["program", &program_id.to_string()]).await
+    }
+
+    // GET /testnet3/program/{programID}, returning `None` if the program is not deployed.
+    pub async fn find_program(&self, program_id: &ProgramID<N>) -> Result<Option<Program<N>>> {
+        match self.get_program(program_id).await {
+            Ok(program) => Ok(Some(program)),
+            Err(error) if error.downcast_ref::<StatusError>().map_or(false, StatusError::is_not_found) => Ok(None),
+            Err(error) => Err(error),
+        }
+    }
+
+    // GET /testnet3/program/{programID}/abi
+    pub async fn get_program_abi(&self, program_id: &ProgramID<N>) -> Result<ProgramAbi<N>> {
+        self.get(&["program", &program_id.to_string(), "abi"]).await
+    }
+
+    // GET /testnet3/program/{programID}/mappings
+    pub async fn get_mapping_names(&self, program_id: &ProgramID<N>) -> Result<Option<Vec<Identifier<N>>>> {
+        self.get(&["program", &program_id.to_string(), "mappings"]).await
+    }
+
+    // GET /testnet3/program/{programID}/mapping/{mappingName}?cursor={cursor}&limit={limit}
+    pub async fn get_mapping(
+        &self,
+        program_id: &ProgramID<N>,
+        mapping_name: &Identifier<N>,
+        cursor: Option<&Plaintext<N>>,
+        limit: Option<usize>,
+    ) -> Result<MappingEntries<N>> {
+        let url = self.url(&["program", &program_id.to_string(), "mapping", &mapping_name.to_string()])?;
+        Self::send(Self::page(self.request(Method::GET, url), cursor, limit)).await
+    }
+
+    // GET /testnet3/program/{programID}/mapping/{mappingName}/{mappingKey}
+    pub async fn get_mapping_value(
+        &self,
+        program_id: &ProgramID<N>,
+        mapping_name: &Identifier<N>,
+        key: &Plaintext<N>,
+    ) -> Result<Option<Value<N>>> {
+        let url =
+            self.url(&["program", &program_id.to_string(), "mapping", &mapping_name.to_string(), &key.to_string()])?;
+        Self::send(self.request(Method::GET, url)).await
+    }
+
+    // GET /testnet3/program/{programID}/transactions?function={functionName}&cursor={cursor}&limit={limit}
+    pub async fn get_program_transactions(
+        &self,
+        program_id: &ProgramID<N>,
+        function_name: Option<&Identifier<N>>,
+        cursor: Option<u64>,
+        limit: Option<usize>,
+    ) -> Result<TransactionPage<N>> {
+        let mut request = self.request(Method::GET, self.url(&["program", &program_id.to_string(), "transactions"])?);
+        if let Some(function_name) = function_name {
+            request = request.query(&[("function", function_name.to_string())]);
+        }
+        Self::send(Self::page(request, cursor, limit)).await
+    }
+
+    // GET /testnet3/address/{address}/transactions?cursor={cursor}&limit={limit}
+    pub async fn get_address_transactions(
+        &self,
+        address: &Address<N>,
+        cursor: Option<u64>,
+        limit: Option<usize>,
+    ) -> Result<TransactionPage<N>> {
+        let request = self.request(Method::GET, self.url(&["address", &address.to_string(), "transactions"])?);
+        Self::send(Self::page(request, cursor, limit)).await
+    }
+
+    // GET /testnet3/memoryPool/transmissions
+    pub async fn get_memory_pool_transmissions(&self) -> Result<IndexMap<TransmissionID<N>, Transmission<N>>> {
+        self.get(&["memoryPool", "transmissions"]).await
+    }
+
+    // GET /testnet3/memoryPool/solutions
+    pub async fn get_memory_pool_solutions(&self) -> Result<IndexMap<PuzzleCommitment<N>, Data<ProverSolution<N>>>> {
+        self.get(&["memoryPool", "solutions"]).await
+    }
+
+    // GET /testnet3/memoryPool/transactions
+    pub async fn get_memory_pool_transactions(&self) -> Result<IndexMap<N::TransactionID, Data<Transaction<N>>>> {
+        self.get(&["memoryPool", "transactions"]).await
+    }
+
+    // GET /testnet3/statePath/{commitment}
+    pub async fn get_state_path_for_commitment(&self, commitment: &Field<N>) -> Result<StatePath<N>> {
+        self.get(&["statePath", &commitment.to_string()]).await
+    }
+
+    // GET /testnet3/committee/latest
+    pub async fn get_committee_latest(&self) -> Result<Committee<N>> {
+        self.get(&["committee", "latest"]).await
+    }
+
+    // GET /testnet3/peers/bans
+    pub async fn get_peers_bans(&self) -> Result<Vec<IpAddr>> {
+        self.get(&["peers", "bans"]).await
+    }
+
+    // POST /testnet3/peers/connect
+    pub async fn connect_peer(&self, peer_ip: SocketAddr) -> Result<bool> {
+        self.post(&["peers", "connect"], &peer_ip).await
+    }
+
+    // POST /testnet3/peers/disconnect
+    pub async fn disconnect_peer(&self, peer_ip: SocketAddr) -> Result<bool> {
+        self.post(&["peers", "disconnect"], &peer_ip).await
+    }
+
+    // POST /testnet3/peers/ban
+    pub async fn ban_peer(&self, ip: IpAddr) -> Result<bool> {
+        self.post(&["peers", "ban"], &ip).await
+    }
+
+    // POST /testnet3/peers/unban
+    pub async fn unban_peer(&self, ip: IpAddr) -> Result<bool> {
+        self.post(&["peers", "unban"], &ip).await
+    }
+
+    // POST /testnet3/validators/connect
+    pub async fn connect_validator(&self, peer_ip: SocketAddr) -> Result<bool> {
+        self.post(&["validators", "connect"], &peer_ip).await
+    }
+
+    // POST /testnet3/validators/disconnect
+    pub async fn disconnect_validator(&self, peer_ip: SocketAddr) -> Result<bool> {
+        self.post(&["validators", "disconnect"], &peer_ip).await
+    }
+
+    // GET /testnet3/node/address
+    pub async fn get_node_address(&self) -> Result<Address<N>> {
+        self.get(&["node", "address"]).await
+    }
+
+    // GET /testnet3/node/logFilter
+    pub async fn get_log_filter(&self) -> Result<String> {
+        self.get(&["node", "logFilter"]).await
+    }
+
+    // POST /testnet3/node/logFilter
+    pub async fn set_log_filter(&self, directives: &str) -> Result<String> {
+        self.post(&["node", "logFilter"], directives).await
+    }
+
+    // GET /testnet3/openapi.json
+    pub async fn get_openapi(&self) -> Result<JsonValue> {
+        self.get(&["openapi.json"]).await
+    }
+
+    // POST /rpc
+    pub async fn rpc(&self, request: &JsonValue) -> Result<JsonValue> {
+        let mut url = self.base_url.clone();
+        url.path_segments_mut().map_err(|_| anyhow!("Invalid base URL"))?.pop_if_empty().push("rpc");
+        Self::send(self.request(Method::POST, url).json(request)).await
+    }
+}
+
+impl<N: Network> RestClient<N> {
+    /// Returns the URL of the given route, with each path segment percent-encoded.
+    fn url(&self, segments: &[&str]) -> Result<Url> {
+        let mut url = self.base_url.clone();
+        url.path_segments_mut()
+            .map_err(|_| anyhow!("Invalid base URL"))?
+            .pop_if_empty()
+            .push("testnet3")
+            .extend(segments);
+        Ok(url)
+    }
+
+    /// Returns a request to the given URL, with the JSON Web Token if it is set.
+    fn request(&self, method: Method, url: Url) -> RequestBuilder {
+        let request = self.client.request(method, url);
+        match &self.token {
+            Some(token) => request.bearer_auth(token),
+            None => request,
+        }
+    }
+
+    /// Sends a GET request to the given route, and deserializes the response.
+    async fn get<T: DeserializeOwned>(&self, segments: &[&str]) -> Result<T> {
+        Self::send(self.request(Method::GET, self.url(segments)?)).await
+    }
+
+    /// Sends a POST request with the given body to the given route, and deserializes the response.
+    async fn post<B: Serialize + ?Sized, T: DeserializeOwned>(&self, segments: &[&str], body: &B) -> Result<T> {
+        Self::send(self.request(Method::POST, self.url(segments)?).json(body)).await
+    }
+
+    /// Adds the pagination parameters to the given request.
+    fn page<C: Serialize>(mut request: RequestBuilder, cursor: Option<C>, limit: Option<usize>) -> RequestBuilder {
+        if let Some(cursor) = cursor {
+            request = request.query(&[("cursor", cursor)]);
+        }
+        if let Some(limit) = limit {
+            request = request.query(&[("limit", limit)]);
+        }
+        request
+    }
+
+    /// Sends the given request, and retries it while the node rate limits it, after the time given by the node.
+    async fn execute(mut request: RequestBuilder) -> Result<Response> {
+        for _ in 0..MAX_RETRIES {
+            // Note: A request with a streaming body cannot be retried.
+            let Some(retry) = request.try_clone() else { break };
+            let response = request.send().await?;
+            if response.status() != StatusCode::TOO_MANY_REQUESTS {
+                return Ok(response);
+            }
+            // Wait for the number of seconds in the `Retry-After` header, or a second if it is missing.
+            let seconds = response.headers().get(RETRY_AFTER).and_then(|value| value.to_str().ok()?.parse().ok());
+            tokio::time::sleep(Duration::from_secs(seconds.unwrap_or(1)).min(MAX_RETRY_AFTER)).await;
+            request = retry;
+        }
+        Ok(request.send().await?)
+    }
+
+    /// Sends the given request, and deserializes the response, or returns the error of the node.
+    async fn send<T: DeserializeOwned>(request: RequestBuilder) -> Result<T> {
+        let response = Self::execute(request).await?;
+        let status = response.status();
+        if !status.is_success() {
+            let message = response.text().await.unwrap_or_default();
+            return Err(StatusError { status: status.as_u16(), message }.into());
+        }
+        Ok(response.json().await?)
+    }
+
+    /// Sends the given request, preferring the binary encoding, and decodes the response in the encoding chosen by the node.
+    async fn send_binary<T: Binary>(request: RequestBuilder) -> Result<T> {
+        let accept = format!("{OCTET_STREAM}, application/json;q=0.9");
+        let response = Self::execute(request.header(ACCEPT, accept)).await?;
+        let status = response.status();
+        if !status.is_success() {
+            let message = response.text().await.unwrap_or_default();
+            return Err(StatusError { status: status.as_u16(), message }.into());
+        }
+        match response.headers().get(CONTENT_TYPE).map_or(false, |content_type| content_type == OCTET_STREAM) {
+            true => T::from_binary(&response.bytes().await?),
+            false => Ok(response.json().await?),
+        }
+    }
+}
+
+/// The error returned when the node responds with an error status.
+#[derive(Debug)]
+pub struct StatusError {
+    /// The status code of the response.
+    pub status: u16,
+    /// The body of the response.
+    pub message: String,
+}
+
+impl StatusError {
+    /// Returns `true` if the requested object does not exist.
+    pub const fn is_not_found(&self) -> bool {
+        self.status == 404
+    }
+}
+
+impl fmt::Display for StatusError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "(status code {}: {:?})", self.status, self.message)
+    }
+}
+
+impl std::error::Error for StatusError {}
+
+/// A response object which is also served in the binary encoding.
+trait Binary: DeserializeOwned {
+    /// Decodes the object from the binary encoding.
+    fn from_binary(bytes: &[u8]) -> Result<Self>;
+}
+
+impl<N: Network> Binary for Block<N> {
+    fn from_binary(bytes: &[u8]) -> Result<Self> {
+        Self::from_bytes_le(bytes)
+    }
+}
+
+impl<N: Network> Binary for Vec<Block<N>> {
+    fn from_binary(bytes: &[u8]) -> Result<Self> {
+        list_from_bytes_le(bytes)
+    }
+}
@@ -17,8 +17,10 @@ use snarkvm::prelude::*;
 use ::time::OffsetDateTime;
 use anyhow::{anyhow, Result};
 use axum::{
+    async_trait,
+    extract::FromRequestParts,
     headers::authorization::{Authorization, Bearer},
-    http::{Request, StatusCode},
+    http::{request::Parts, Request, StatusCode},
     middleware::Next,
     response::{IntoResponse, Response},
     RequestPartsExt,
@@ -70,27 +72,45 @@ impl Claims {
     }
 }
 
+/// An extractor which rejects the request, unless it has a valid JSON Web Token of the node.
+pub struct Authenticated;
+
+#[async_trait]
+impl<S: Send + Sync> FromRequestParts<S> for Authenticated {
+    type Rejection = Response;
+
+    async fn from_request_parts(parts: &mut Parts, _: &S) -> Result<Self, Self::Rejection> {
+        let auth: TypedHeader<Authorization<Bearer>> =
+            parts.extract().await.map_err(|_| StatusCode::UNAUTHORIZED.into_response())?;
+
+        match decode::<Claims>(
+            auth.token(),
+            &DecodingKey::from_secret(jwt_secret()),
+            &Validation::new(Algorithm::HS256),
+        ) {
+            Ok(decoded) => {
+                let claims = decoded.claims;
+                if claims.is_expired() {
+                    return Err((StatusCode::UNAUTHORIZED, "Expired JSON Web Token".to_owned()).into_response());
+                }
+            }
+
+            Err(_) => {
+                return Err(StatusCode::UNAUTHORIZED.into_response());
+            }
+        }
+
+        Ok(Self)
+    }
+}
+
 pub async fn auth_middleware<B>(request: Request<B>, next: Next<B>) -> Result<Response, Response>
 where
     B: Send,
 {
     // Deconstruct the request to extract the auth token.
     let (mut parts, body) = request.into_parts();
-    let auth: TypedHeader<Authorization<Bearer>> =
-        parts.extract().await.map_err(|_| StatusCode::UNAUTHORIZED.into_response())?;
-
-    match decode::<Claims>(auth.token(), &DecodingKey::from_secret(jwt_secret()), &Validation::new(Algorithm::HS256)) {
-        Ok(decoded) => {
-            let claims = decoded.claims;
-            if claims.is_expired() {
-                return Err((StatusCode::UNAUTHORIZED, "Expired JSON Web Token".to_owned()).into_response());
-            }
-        }
-
-        Err(_) => {
-            return Err(StatusCode::UNAUTHORIZED.into_response());
-        }
-    }
+    parts.extract::<Authenticated>().await?;
 
     // Reconstruct the request.
     let request = Request::from_parts(parts, body);
@@ -0,0 +1,103 @@
+// Copyright (C) 2019-2023 Aleo Systems Inc.
+// This file is part of the snarkOS library.
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at:
+// http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use super::RestError;
+use snarkos_node_rest_types::{list_to_bytes_le, OCTET_STREAM};
+use snarkvm::prelude::ToBytes;
+
+use axum::{
+    async_trait,
+    extract::FromRequestParts,
+    http::{
+        header::{ACCEPT, CONTENT_TYPE},
+        request::Parts,
+    },
+    response::{IntoResponse, Response},
+};
+use axum_extra::response::ErasedJson;
+use serde::Serialize;
+use std::{convert::Infallible, marker::PhantomData};
+
+/// The encoding of a response, as negotiated from the `Accept` header of the request.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum ResponseFormat {
+    /// The JSON encoding, which is the default.
+    Json,
+    /// The binary encoding, which is the `ToBytes` encoding of the response object.
+    Binary,
+}
+
+#[async_trait]
+impl<S: Send + Sync> FromRequestParts<S> for ResponseFormat {
+    type Rejection = Infallible;
+
+    /// Returns the first supported media type in the `Accept` header, or JSON if there is none.
+    async fn from_request_parts(parts: &mut Parts, _: &S) -> Result<Self, Self::Rejection> {
+        let accept = parts.headers.get_all(ACCEPT).iter().filter_map(|value| value.to_str().ok());
+        for media_type in accept.flat_map(|value| value.split(',')) {
+            match media_type.split(';').next().unwrap_or_default().trim() {
+                "application/json" => return Ok(Self::Json),
+                OCTET_STREAM => return Ok(Self::Binary),
+                _ => continue,
+            }
+        }
+        Ok(Self::Json)
+    }
+}
+
+impl ResponseFormat {
+    /// Returns the response for the given object.
+    pub(crate) fn respond<T: Serialize + ToBytes>(self, object: T) -> Result<FormattedResponse<T>, RestError> {
+        let response = match self {
+            Self::Json => ErasedJson::pretty(object).into_response(),
+            Self::Binary => binary_response(object.to_bytes_le()?),
+        };
+        Ok(FormattedResponse(response, PhantomData))
+    }
+
+    /// Returns the response for the given list of objects.
+    pub(crate) fn respond_list<T: Serialize + ToBytes>(
+        self,
+        objects: Vec<T>,
+    ) -> Result<FormattedResponse<Vec<T>>, RestError> {
+        let response = match self {
+            Self::Json => ErasedJson::pretty(objects).into_response(),
+            Self::Binary => binary_response(list_to_bytes_le(&objects)?),
+        };
+        Ok(FormattedResponse(response, PhantomData))
+    }
+}
+
+/// A pretty-printed JSON response, whose schema is documented from the type of the object.
+pub struct JsonResponse<T>(pub T);
+
+impl<T: Serialize> IntoResponse for JsonResponse<T> {
+    fn into_response(self) -> Response {
+        ErasedJson::pretty(self.0).into_response()
+    }
+}
+
+/// A response in the negotiated encoding, whose schema is documented from the type of the encoded object.
+pub struct FormattedResponse<T>(Response, PhantomData<fn() -> T>);
+
+impl<T> IntoResponse for FormattedResponse<T> {
+    fn into_response(self) -> Response {
+        self.0
+    }
+}
+
+/// Returns a response with the given bytes.
+fn binary_response(bytes: Vec<u8>) -> Response {
+    ([(CONTENT_TYPE, OCTET_STREAM)], bytes).into_response()
+}
@@ -0,0 +1,63 @@
+// Copyright (C) 2019-2023 Aleo Systems Inc.
+// This file is part of the snarkOS library.
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at:
+// http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use anyhow::{bail, Result};
+use once_cell::sync::OnceCell;
+use parking_lot::RwLock;
+use std::sync::Arc;
+
+/// The log filter of the node instance, if it can be changed at runtime.
+static LOG_FILTER: OnceCell<LogFilter> = OnceCell::new();
+
+/// A handle to the log filter of the node, which changes the log levels at runtime.
+#[derive(Clone)]
+pub struct LogFilter {
+    /// The current directives, such as `info,snarkos_node_narwhal::gateway=trace`.
+    directives: Arc<RwLock<String>>,
+    /// The function which applies the given directives to the logger.
+    apply: Arc<dyn Fn(&str) -> Result<()> + Send + Sync>,
+}
+
+impl LogFilter {
+    /// Initializes a new handle with the current directives, and the function which applies new directives.
+    pub fn new(directives: String, apply: impl Fn(&str) -> Result<()> + Send + Sync + 'static) -> Self {
+        Self { directives: Arc::new(RwLock::new(directives)), apply: Arc::new(apply) }
+    }
+
+    /// Returns the current directives.
+    pub fn directives(&self) -> String {
+        self.directives.read().clone()
+    }
+
+    /// Applies the given directives, replacing the current directives.
+    pub fn set_directives(&self, directives: &str) -> Result<()> {
+        let mut current = self.directives.write();
+        (self.apply)(directives)?;
+        *current = directives.to_string();
+        Ok(())
+    }
+
+    /// Registers the log filter of the node instance, so it can be changed from the REST server.
+    pub fn register(self) -> Result<()> {
+        if LOG_FILTER.set(self).is_err() {
+            bail!("The log filter is already registered");
+        }
+        Ok(())
+    }
+
+    /// Returns the log filter of the node instance, if it is registered.
+    pub fn get() -> Option<&'static Self> {
+        LOG_FILTER.get()
+    }
+}
@@ -17,3 +17,15 @@ pub use auth::*;
 
 mod error;
 pub use error::*;
+
+mod format;
+pub use format::*;
+
+mod log_filter;
+pub use log_filter::*;
+
+mod rate_limit;
+pub use rate_limit::*;
+
+mod tls;
+pub use tls::*;
@@ -0,0 +1,213 @@
+// Copyright (C) 2019-2023 Aleo Systems Inc.
+// This file is part of the snarkOS library.
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at:
+// http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use axum::{
+    extract::{ConnectInfo, MatchedPath, State},
+    http::{header::RETRY_AFTER, Request, StatusCode},
+    middleware::Next,
+    response::{IntoResponse, Response},
+};
+use parking_lot::Mutex;
+use std::{
+    collections::HashMap,
+    net::{IpAddr, SocketAddr},
+    sync::Arc,
+    time::{Duration, Instant},
+};
+use tokio::sync::Semaphore;
+
+/// The number of token buckets at which the idle buckets are pruned.
+const MAX_BUCKETS: usize = 65_536;
+
+/// The routes which are expensive to serve, and are limited separately from the other routes.
+/// Note: The routes are given as registered in the router, with their path parameters.
+const HEAVY_ROUTES: &[&str] = &[
+    "/testnet3/blocks",
+    "/testnet3/transaction/broadcast",
+    "/testnet3/transaction/estimateFee",
+    "/testnet3/transaction/simulate",
+    "/testnet3/program/:id/mapping/:name",
+    "/testnet3/program/:id/transactions",
+    "/testnet3/address/:address/transactions",
+    "/testnet3/memoryPool/transmissions",
+    "/testnet3/memoryPool/solutions",
+    "/testnet3/memoryPool/transactions",
+    "/rpc",
+];
+
+/// The class of a route, which determines its rate limit.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub enum RouteClass {
+    /// A route which reads a single item from the ledger or the node.
+    Standard,
+    /// A route which reads a range of blocks, a page of an index or mapping, or the memory pool,
+    /// or verifies or executes a transaction.
+    Heavy,
+}
+
+impl RouteClass {
+    /// Returns the class of the given route, such as `/testnet3/program/:id/mapping/:name`.
+    pub fn of(route: &str) -> Self {
+        match HEAVY_ROUTES.contains(&route) {
+            true => Self::Heavy,
+            false => Self::Standard,
+        }
+    }
+}
+
+/// A token-bucket quota, where a quota of `0` requests per second is unlimited.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct Quota {
+    /// The number of requests per second.
+    pub per_second: u32,
+    /// The maximum number of requests in a burst.
+    pub burst: u32,
+}
+
+/// The rate limits of the REST server.
+#[derive(Clone, Debug)]
+pub struct RateLimits {
+    /// The quota of each IP for the standard routes.
+    pub standard: Quota,
+    /// The quota of each IP for the heavy routes.
+    pub heavy: Quota,
+    /// The maximum number of heavy requests served concurrently, where `0` is unlimited.
+    pub max_heavy_in_flight: usize,
+    /// The IPs which are exempt from the rate limits.
+    pub allowlist: Vec<IpAddr>,
+}
+
+impl Default for RateLimits {
+    fn default() -> Self {
+        Self {
+            standard: Quota { per_second: 50, burst: 100 },
+            heavy: Quota { per_second: 5, burst: 10 },
+            max_heavy_in_flight: 32,
+            allowlist: vec![],
+        }
+    }
+}
+
+/// A token bucket.
+struct Bucket {
+    /// The number of available tokens.
+    tokens: f64,
+    /// The time at which the tokens were last refilled.
+    refilled: Instant,
+}
+
+/// The rate limiter of the REST server, which tracks a token bucket per IP and route class.
+#[derive(Clone)]
+pub(crate) struct RateLimiter {
+    /// The rate limits.
+    limits: Arc<RateLimits>,
+    /// The token buckets, by IP and route class.
+    buckets: Arc<Mutex<HashMap<(IpAddr, RouteClass), Bucket>>>,
+    /// The permits for the heavy requests in flight, if they are limited.
+    heavy_in_flight: Option<Arc<Semaphore>>,
+}
+
+impl RateLimiter {
+    /// Initializes a new rate limiter with the given limits.
+    pub(crate) fn new(limits: RateLimits) -> Self {
+        let heavy_in_flight = match limits.max_heavy_in_flight {
+            0 => None,
+            max => Some(Arc::new(Semaphore::new(max))),
+        };
+        Self { limits: Arc::new(limits), buckets: Default::default(), heavy_in_flight }
+    }
+
+    /// Takes a token from the bucket of the given IP and route class,
+    /// or returns the time until a token is available.
+    fn check(&self, ip: IpAddr, class: RouteClass, now: Instant) -> Result<(), Duration> {
+        let quota = match class {
+            RouteClass::Standard => self.limits.standard,
+            RouteClass::Heavy => self.limits.heavy,
+        };
+        if quota.per_second == 0 {
+            return Ok(());
+        }
+        let rate = f64::from(quota.per_second);
+        let capacity = f64::from(quota.burst.max(1));
+
+        let mut buckets = self.buckets.lock();
+        // Prune the buckets which are full again, as they are equivalent to a new bucket.
+        if buckets.len() >= MAX_BUCKETS {
+            buckets.retain(|_, bucket| {
+                bucket.tokens + now.saturating_duration_since(bucket.refilled).as_secs_f64() * rate < capacity
+            });
+        }
+
+        // Refill the bucket, and take a token.
+        let bucket = buckets.entry((ip, class)).or_insert(Bucket { tokens: capacity, refilled: now });
+        let elapsed = now.saturating_duration_since(bucket.refilled).as_secs_f64();
+        bucket.tokens = (bucket.tokens + elapsed * rate).min(capacity);
+        bucket.refilled = now;
+        match bucket.tokens >= 1.0 {
+            true => {
+                bucket.tokens -= 1.0;
+                Ok(())
+            }
+            false => Err(Duration::from_secs_f64((1.0 - bucket.tokens) / rate)),
+        }
+    }
+}
+
+/// Rejects the requests of each IP beyond its quota, and the heavy requests beyond the in-flight limit.
+pub(crate) async fn rate_limit_middleware<B>(
+    State(limiter): State<RateLimiter>,
+    ConnectInfo(addr): ConnectInfo<SocketAddr>,
+    request: Request<B>,
+    next: Next<B>,
+) -> Response
+where
+    B: Send,
+{
+    // Serve the trusted IPs without limits.
+    if limiter.limits.allowlist.contains(&addr.ip()) {
+        return next.run(request).await;
+    }
+
+    // Classify the request by its matched route, as the paths of a route differ in their parameters.
+    // Note: A request which matches no route is not served, and is limited as a standard request.
+    let class =
+        request.extensions().get::<MatchedPath>().map_or(RouteClass::Standard, |route| RouteClass::of(route.as_str()));
+    if let Err(wait) = limiter.check(addr.ip(), class, Instant::now()) {
+        debug!("Rate limited '{} {}' from '{addr}'", request.method(), request.uri());
+        return too_many_requests(wait);
+    }
+
+    match (class, &limiter.heavy_in_flight) {
+        (RouteClass::Heavy, Some(heavy_in_flight)) => match heavy_in_flight.clone().try_acquire_owned() {
+            // Hold the permit until the response is ready.
+            Ok(_permit) => next.run(request).await,
+            Err(_) => {
+                debug!(
+                    "Too many heavy requests in flight to serve '{} {}' from '{addr}'",
+                    request.method(),
+                    request.uri()
+                );
+                too_many_requests(Duration::from_secs(1))
+            }
+        },
+        _ => next.run(request).await,
+    }
+}
+
+/// Returns a `429 Too Many Requests` response, with the number of seconds to wait before retrying.
+fn too_many_requests(wait: Duration) -> Response {
+    let seconds = (wait.as_secs() + u64::from(wait.subsec_nanos() > 0)).max(1);
+    (StatusCode::TOO_MANY_REQUESTS, [(RETRY_AFTER, seconds.to_string())], "Too many requests, retry later")
+        .into_response()
+}
@@ -0,0 +1,69 @@
+// Copyright (C) 2019-2023 Aleo Systems Inc.
+// This file is part of the snarkOS library.
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at:
+// http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use axum_server::tls_rustls::RustlsConfig;
+use std::{
+    path::PathBuf,
+    time::{Duration, SystemTime},
+};
+
+/// The interval at which the certificate and key files are checked for changes.
+const RELOAD_INTERVAL: Duration = Duration::from_secs(10);
+
+/// The TLS configuration of the REST server, as PEM files.
+#[derive(Clone, Debug)]
+pub struct TlsConfig {
+    /// The path to the certificate chain.
+    pub cert_path: PathBuf,
+    /// The path to the private key.
+    pub key_path: PathBuf,
+}
+
+impl TlsConfig {
+    /// Initializes a new TLS configuration from the given certificate chain and private key.
+    pub fn new(cert_path: PathBuf, key_path: PathBuf) -> Self {
+        Self { cert_path, key_path }
+    }
+
+    /// Loads the rustls configuration from the certificate chain and private key.
+    pub(crate) async fn load(&self) -> std::io::Result<RustlsConfig> {
+        RustlsConfig::from_pem_file(&self.cert_path, &self.key_path).await
+    }
+
+    /// Reloads the given rustls configuration whenever the certificate chain or private key is modified.
+    pub(crate) async fn watch(self, config: RustlsConfig) {
+        let mut last_modified = self.last_modified();
+        loop {
+            tokio::time::sleep(RELOAD_INTERVAL).await;
+
+            let modified = self.last_modified();
+            if modified == last_modified {
+                continue;
+            }
+            last_modified = modified;
+
+            // Keep serving the previous certificate if the new files are incomplete or invalid.
+            match config.reload_from_pem_file(&self.cert_path, &self.key_path).await {
+                Ok(()) => info!("Reloaded the REST TLS certificate from '{}'", self.cert_path.display()),
+                Err(error) => warn!("Failed to reload the REST TLS certificate - {error}"),
+            }
+        }
+    }
+
+    /// Returns the modification times of the certificate chain and private key.
+    fn last_modified(&self) -> (Option<SystemTime>, Option<SystemTime>) {
+        let modified = |path: &PathBuf| std::fs::metadata(path).and_then(|metadata| metadata.modified()).ok();
+        (modified(&self.cert_path), modified(&self.key_path))
+    }
+}
@@ -20,37 +20,49 @@ extern crate tracing;
 mod helpers;
 pub use helpers::*;
 
+mod openapi;
+use openapi::DocumentedRouter;
+
 mod routes;
+mod rpc;
 
 use snarkos_node_consensus::Consensus;
+use snarkos_node_indexer::Indexer;
+use snarkos_node_narwhal_ledger_service::LedgerService;
 use snarkos_node_router::{
     messages::{Data, Message, UnconfirmedTransaction},
     Routing,
 };
 use snarkvm::{
-    console::{program::ProgramID, types::Field},
+    console::{
+        program::{Identifier, Plaintext, ProgramID, Value},
+        types::Field,
+    },
     prelude::{cfg_into_iter, store::ConsensusStorage, Ledger, Network},
 };
 
-use anyhow::Result;
+use anyhow::{bail, Result};
 use axum::{
     extract::{ConnectInfo, DefaultBodyLimit, Path, Query, State},
     http::{header::CONTENT_TYPE, Method, Request, StatusCode},
     middleware,
     middleware::Next,
     response::Response,
-    routing::{get, post},
     Json,
 };
-use axum_extra::response::ErasedJson;
+use axum_server::tls_rustls::RustlsConfig;
 use parking_lot::Mutex;
 use std::{net::SocketAddr, sync::Arc};
 use tokio::task::JoinHandle;
 use tower_http::{
+    compression::CompressionLayer,
     cors::{Any, CorsLayer},
     trace::TraceLayer,
 };
 
+/// The entries of a mapping, keyed on the latest block hash, the program ID, and the mapping name.
+type MappingSnapshot<N> = (<N as Network>::BlockHash, ProgramID<N>, Identifier<N>, Arc<Vec<(Plaintext<N>, Value<N>)>>);
+
 /// A REST API server for the ledger.
 #[derive(Clone)]
 pub struct Rest<N: Network, C: ConsensusStorage<N>, R: Routing<N>> {
@@ -58,24 +70,56 @@ pub struct Rest<N: Network, C: ConsensusStorage<N>, R: Routing<N>> {
     consensus: Option<Consensus<N>>,
     /// The ledger.
     ledger: Ledger<N, C>,
+    /// The ledger service, which speculates on transactions while no block is being added.
+    ledger_service: Arc<dyn LedgerService<N>>,
+    /// The indexer, if it is enabled.
+    indexer: Option<Indexer<N>>,
     /// The node (routing).
     routing: Arc<R>,
     /// The server handles.
     handles: Arc<Mutex<Vec<JoinHandle<()>>>>,
+    /// The cached priority fee, keyed on the latest block hash and the number of unconfirmed transactions.
+    priority_fee: Arc<tokio::sync::Mutex<Option<(N::BlockHash, usize, u64)>>>,
+    /// The cached entries of the last paged mapping.
+    mapping_snapshot: Arc<Mutex<Option<MappingSnapshot<N>>>>,
 }
 
 impl<N: Network, C: 'static + ConsensusStorage<N>, R: Routing<N>> Rest<N, C, R> {
     /// Initializes a new instance of the server.
-    pub fn start(
+    /// Note: The TLS certificate is loaded before the server is spawned, so an invalid certificate is an error.
+    pub async fn start(
         rest_ip: SocketAddr,
+        rate_limits: RateLimits,
+        tls: Option<TlsConfig>,
         consensus: Option<Consensus<N>>,
         ledger: Ledger<N, C>,
+        ledger_service: Arc<dyn LedgerService<N>>,
+        indexer: Option<Indexer<N>>,
         routing: Arc<R>,
     ) -> Result<Self> {
+        // Load the TLS certificate.
+        let tls = match tls {
+            Some(tls) => match tls.load().await {
+                Ok(config) => Some((tls, config)),
+                Err(error) => {
+                    bail!("Failed to load the REST TLS certificate from '{}' - {error}", tls.cert_path.display())
+                }
+            },
+            None => None,
+        };
         // Initialize the server.
-        let mut server = Self { consensus, ledger, routing, handles: Default::default() };
+        let mut server = Self {
+            consensus,
+            ledger,
+            ledger_service,
+            indexer,
+            routing,
+            handles: Default::default(),
+            priority_fee: Default::default(),
+            mapping_snapshot: Default::default(),
+        };
         // Spawn the server.
-        server.spawn_server(rest_ip);
+        server.spawn_server(rest_ip, rate_limits, tls);
         // Return the server.
         Ok(server)
     }
@@ -94,62 +138,92 @@ impl<N: Network, C: ConsensusStorage<N>, R: Routing<N>> Rest<N, C, R> {
 }
 
 impl<N: Network, C: ConsensusStorage<N>, R: Routing<N>> Rest<N, C, R> {
-    fn spawn_server(&mut self, rest_ip: SocketAddr) {
+    fn spawn_server(&mut self, rest_ip: SocketAddr, rate_limits: RateLimits, tls: Option<(TlsConfig, RustlsConfig)>) {
         let cors = CorsLayer::new()
             .allow_origin(Any)
             .allow_methods([Method::GET, Method::POST, Method::OPTIONS])
             .allow_headers([CONTENT_TYPE]);
 
         let router = {
-            axum::Router::new()
+            DocumentedRouter::new()
 
             // GET ../latest/..
-            .route("/testnet3/latest/height", get(Self::latest_height))
-            .route("/testnet3/latest/hash", get(Self::latest_hash))
-            .route("/testnet3/latest/block", get(Self::latest_block))
-            .route("/testnet3/latest/stateRoot", get(Self::latest_state_root))
-            .route("/testnet3/latest/committee", get(Self::latest_committee))
+            .get("/testnet3/latest/height", Self::latest_height, "Returns the latest block height")
+            .get("/testnet3/latest/hash", Self::latest_hash, "Returns the latest block hash")
+            .get("/testnet3/latest/block", Self::latest_block, "Returns the latest block")
+            .get("/testnet3/latest/stateRoot", Self::latest_state_root, "Returns the latest state root")
+            .get("/testnet3/latest/committee", Self::latest_committee, "Returns the latest committee")
 
             // GET ../block/..
-            .route("/testnet3/block/:height_or_hash", get(Self::get_block))
+            .get("/testnet3/block/:height_or_hash", Self::get_block, "Returns the block of the given height or hash")
             // The path param here is actually only the height, but the name must match the route
             // above, otherwise there'll be a conflict at runtime.
-            .route("/testnet3/block/:height_or_hash/transactions", get(Self::get_block_transactions))
+            .get("/testnet3/block/:height_or_hash/transactions", Self::get_block_transactions, "Returns the transactions of the block of the given height")
 
             // GET and POST ../transaction/..
-            .route("/testnet3/transaction/:id", get(Self::get_transaction))
-            .route("/testnet3/transaction/broadcast", post(Self::transaction_broadcast))
+            .get("/testnet3/transaction/:id", Self::get_transaction, "Returns the transaction of the given ID")
+            .post("/testnet3/transaction/broadcast", Self::transaction_broadcast, "Broadcasts the transaction, and returns its ID")
+            .post("/testnet3/transaction/estimateFee", Self::transaction_estimate_fee, "Estimates the fee of the deployment or execution, which may be unproven")
+            .post("/testnet3/transaction/simulate", Self::transaction_simulate, "Simulates the transaction against the latest state")
 
             // GET ../find/..
-            .route("/testnet3/find/blockHash/:tx_id", get(Self::find_block_hash))
-            .route("/testnet3/find/transactionID/deployment/:program_id", get(Self::find_transaction_id_from_program_id))
-            .route("/testnet3/find/transactionID/:transition_id", get(Self::find_transaction_id_from_transition_id))
-            .route("/testnet3/find/transitionID/:input_or_output_id", get(Self::find_transition_id))
+            .get("/testnet3/find/blockHash/:tx_id", Self::find_block_hash, "Returns the hash of the block containing the transaction")
+            .get("/testnet3/find/transactionID/deployment/:program_id", Self::find_transaction_id_from_program_id, "Returns the ID of the transaction deploying the program")
+            .get("/testnet3/find/transactionID/:transition_id", Self::find_transaction_id_from_transition_id, "Returns the ID of the transaction containing the transition")
+            .get("/testnet3/find/transitionID/:input_or_output_id", Self::find_transition_id, "Returns the ID of the transition containing the input or output")
 
             // GET ../peers/..
-            .route("/testnet3/peers/count", get(Self::get_peers_count))
-            .route("/testnet3/peers/all", get(Self::get_peers_all))
-            .route("/testnet3/peers/all/metrics", get(Self::get_peers_all_metrics))
+            .get("/testnet3/peers/count", Self::get_peers_count, "Returns the number of connected peers")
+            .get("/testnet3/peers/all", Self::get_peers_all, "Returns the addresses of the connected peers")
+            .get("/testnet3/peers/all/metrics", Self::get_peers_all_metrics, "Returns the addresses and node types of the connected peers")
+            .get("/testnet3/peers/bans", Self::get_peers_bans, "Returns the banned IP addresses")
+
+            // POST ../peers/.. and ../validators/.. (authenticated)
+            .post("/testnet3/peers/connect", Self::connect_peer, "Connects to the peer, and returns whether it is connected")
+            .post("/testnet3/peers/disconnect", Self::disconnect_peer, "Disconnects from the peer, and returns whether it was connected")
+            .post("/testnet3/peers/ban", Self::ban_peer, "Bans the IP address until it is unbanned, disconnects its peers, and returns whether any was connected")
+            .post("/testnet3/peers/unban", Self::unban_peer, "Lifts the ban of the IP address, and returns whether it was banned")
+            .post("/testnet3/validators/connect", Self::connect_validator, "Connects to the validator, and returns whether it is connected")
+            .post("/testnet3/validators/disconnect", Self::disconnect_validator, "Disconnects from the validator, and returns whether it was connected")
 
             // GET ../program/..
-            .route("/testnet3/program/:id", get(Self::get_program))
-            .route("/testnet3/program/:id/mappings", get(Self::get_mapping_names))
-            .route("/testnet3/program/:id/mapping/:name/:key", get(Self::get_mapping_value))
+            .get("/testnet3/programs", Self::get_programs, "Returns a page of the deployed program IDs")
+            .get("/testnet3/program/:id", Self::get_program, "Returns the program of the given ID")
+            .get("/testnet3/program/:id/abi", Self::get_program_abi, "Returns the interface of the program")
+            .get("/testnet3/program/:id/mappings", Self::get_mapping_names, "Returns the mapping names of the program")
+            .get("/testnet3/program/:id/mapping/:name", Self::get_mapping, "Returns a page of the entries of the mapping")
+            .get("/testnet3/program/:id/mapping/:name/:key", Self::get_mapping_value, "Returns the value of the key in the mapping")
+            .get("/testnet3/program/:id/transactions", Self::get_program_transactions, "Returns a page of the transactions of the program")
+
+            // GET ../address/..
+            .get("/testnet3/address/:address/transactions", Self::get_address_transactions, "Returns a page of the transactions of the address")
+
+            // POST ../rpc
+            .post("/rpc", Self::rpc, "Calls a JSON-RPC 2.0 method, or a batch of up to 20 methods")
 
             // GET misc endpoints.
-            .route("/testnet3/blocks", get(Self::get_blocks))
-            .route("/testnet3/height/:hash", get(Self::get_height))
-            .route("/testnet3/memoryPool/transmissions", get(Self::get_memory_pool_transmissions))
-            .route("/testnet3/memoryPool/solutions", get(Self::get_memory_pool_solutions))
-            .route("/testnet3/memoryPool/transactions", get(Self::get_memory_pool_transactions))
-            .route("/testnet3/statePath/:commitment", get(Self::get_state_path_for_commitment))
-            .route("/testnet3/committee/latest", get(Self::get_committee_latest))
-            .route("/testnet3/node/address", get(Self::get_node_address))
+            .get("/testnet3/blocks", Self::get_blocks, "Returns the blocks in the given range")
+            .get("/testnet3/height/:hash", Self::get_height, "Returns the height of the block of the given hash")
+            .get("/testnet3/memoryPool/transmissions", Self::get_memory_pool_transmissions, "Returns the unconfirmed transmissions")
+            .get("/testnet3/memoryPool/solutions", Self::get_memory_pool_solutions, "Returns the unconfirmed solutions")
+            .get("/testnet3/memoryPool/transactions", Self::get_memory_pool_transactions, "Returns the unconfirmed transactions")
+            .get("/testnet3/statePath/:commitment", Self::get_state_path_for_commitment, "Returns the state path of the commitment")
+            .get("/testnet3/committee/latest", Self::get_committee_latest, "Returns the latest committee")
+            .get("/testnet3/node/address", Self::get_node_address, "Returns the address of the node")
+            .get("/testnet3/node/logFilter", Self::get_log_filter, "Returns the log filter directives of the node")
+            .post("/testnet3/node/logFilter", Self::set_log_filter, "Changes the log filter directives of the node, such as `info,snarkos_node_narwhal::gateway=trace`")
+
+            // Serve the OpenAPI document of the routes above.
+            .finish()
 
             // Pass in `Rest` to make things convenient.
             .with_state(self.clone())
             // Enable tower-http tracing.
             .layer(TraceLayer::new_for_http())
+            // Compress the responses with gzip or zstd, as accepted by the client.
+            .layer(CompressionLayer::new())
+            // Limit the request rate of each IP, and the heavy requests in flight.
+            .layer(middleware::from_fn_with_state(RateLimiter::new(rate_limits), rate_limit_middleware))
             // Custom logging.
             .layer(middleware::from_fn(log_middleware))
             // Enable CORS.
@@ -160,11 +234,17 @@ impl<N: Network, C: ConsensusStorage<N>, R: Routing<N>> Rest<N, C, R> {
             // .layer(middleware::from_fn(auth_middleware))
         };
 
+        let handles = self.handles.clone();
         self.handles.lock().push(tokio::spawn(async move {
-            axum::Server::bind(&rest_ip)
-                .serve(router.into_make_service_with_connect_info::<SocketAddr>())
-                .await
-                .expect("couldn't start rest server");
+            let service = router.into_make_service_with_connect_info::<SocketAddr>();
+            match tls {
+                // Serve HTTPS, and reload the certificate when it is renewed.
+                Some((tls, config)) => {
+                    handles.lock().push(tokio::spawn(tls.watch(config.clone())));
+                    axum_server::bind_rustls(rest_ip, config).serve(service).await.expect("couldn't start rest server");
+                }
+                None => axum::Server::bind(&rest_ip).serve(service).await.expect("couldn't start rest server"),
+            }
         }))
     }
 }
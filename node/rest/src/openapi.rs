@@ -0,0 +1,512 @@
+// Copyright (C) 2019-2023 Aleo Systems Inc.
+// This file is part of the snarkOS library.
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at:
+// http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::{Authenticated, FormattedResponse, JsonResponse, ResponseFormat};
+use snarkos_node_rest_types::{
+    schema,
+    FeeEstimate,
+    FeeRequest,
+    MappingEntries,
+    ProgramAbi,
+    ProgramList,
+    Simulation,
+    TransactionPage,
+    OCTET_STREAM,
+};
+use snarkos_node_router::messages::NodeType;
+use snarkvm::{
+    ledger::{
+        block::{Block, Transaction, Transactions},
+        coinbase::ProverSolution,
+        committee::Committee,
+        narwhal::{Data, Transmission},
+    },
+    prelude::{Address, FromBytes, Identifier, Network, Program, StatePath, ToBytes, Value},
+};
+
+use axum::{
+    body::{Body, Bytes},
+    extract::{Path, Query, State},
+    handler::Handler,
+    http::header::CONTENT_TYPE,
+    response::Response,
+    routing::{get, on, MethodFilter},
+    Json,
+    Router,
+};
+use indexmap::{IndexMap, IndexSet};
+use schemars::{
+    gen::{SchemaGenerator, SchemaSettings},
+    schema::{Schema, SchemaObject},
+    JsonSchema,
+};
+use serde::{
+    de::{self, DeserializeOwned, Visitor},
+    forward_to_deserialize_any,
+    Deserializer,
+};
+use serde_json::{json, Map, Value as JsonValue};
+use std::{
+    any::type_name,
+    collections::HashMap,
+    future::Future,
+    net::{IpAddr, SocketAddr},
+};
+
+/// The route of the OpenAPI document.
+pub(crate) const OPENAPI_PATH: &str = "/testnet3/openapi.json";
+
+/// A router which documents each route in an OpenAPI 3 document as it is registered.
+///
+/// The parameters and request body of each operation are derived from the extractors of its handler,
+/// the response body from its output, and the operation ID is the name of the handler, so the document
+/// covers every registered route and cannot drift from the handler signatures. The schemas of the request
+/// and response bodies are derived from their types, and are collected in the components of the document.
+pub(crate) struct DocumentedRouter<S> {
+    /// The router.
+    router: Router<S>,
+    /// The documented operations, by path and method.
+    paths: Map<String, JsonValue>,
+    /// The generator of the schemas, which collects the schemas of the named types.
+    generator: SchemaGenerator,
+}
+
+/// A function returning the schema of a request or response body, as given by `schema::<T>`.
+type SchemaFn = fn(&mut SchemaGenerator) -> Schema;
+
+/// Returns the schema of the given type, which refers to the components of the document for named types.
+fn schema<T: JsonSchema>(generator: &mut SchemaGenerator) -> Schema {
+    generator.subschema_for::<T>()
+}
+
+impl<S: Clone + Send + Sync + 'static> DocumentedRouter<S> {
+    /// Initializes a new router, without any routes.
+    pub(crate) fn new() -> Self {
+        Self { router: Router::new(), paths: Map::new(), generator: SchemaSettings::openapi3().into_generator() }
+    }
+
+    /// Registers and documents a GET route, with the given summary.
+    pub(crate) fn get<H, T, D>(self, path: &str, handler: H, summary: &str) -> Self
+    where
+        H: Handler<T, S, Body> + Describe<D>,
+        T: 'static,
+    {
+        self.route(MethodFilter::GET, path, handler, summary)
+    }
+
+    /// Registers and documents a POST route, with the given summary.
+    pub(crate) fn post<H, T, D>(self, path: &str, handler: H, summary: &str) -> Self
+    where
+        H: Handler<T, S, Body> + Describe<D>,
+        T: 'static,
+    {
+        self.route(MethodFilter::POST, path, handler, summary)
+    }
+
+    /// Returns the router, including a route serving the OpenAPI document.
+    pub(crate) fn finish(mut self) -> Router<S> {
+        // Document the route of the OpenAPI document itself.
+        let summary = "Returns the OpenAPI document of the REST API";
+        self.document(MethodFilter::GET, OPENAPI_PATH, "get_openapi", Operation::default(), summary);
+
+        // Construct the OpenAPI document.
+        let document = json!({
+            "openapi": "3.0.3",
+            "info": {
+                "title": "snarkOS REST API",
+                "version": env!("CARGO_PKG_VERSION"),
+            },
+            "paths": self.paths,
+            "components": {
+                "schemas": self.generator.definitions(),
+                "securitySchemes": {
+                    "bearerAuth": { "type": "http", "scheme": "bearer", "bearerFormat": "JWT" },
+                },
+            },
+        });
+        let document = Bytes::from(serde_json::to_vec_pretty(&document).expect("The OpenAPI document is valid JSON"));
+
+        self.router.route(OPENAPI_PATH, get(move || async move { ([(CONTENT_TYPE, "application/json")], document) }))
+    }
+
+    /// Registers and documents a route.
+    fn route<H, T, D>(mut self, filter: MethodFilter, path: &str, handler: H, summary: &str) -> Self
+    where
+        H: Handler<T, S, Body> + Describe<D>,
+        T: 'static,
+    {
+        let mut operation = Operation::default();
+        H::describe(&mut operation);
+        self.document(filter, path, &handler_name::<H>(), operation, summary);
+
+        self.router = self.router.route(path, on(filter, handler));
+        self
+    }
+
+    /// Documents an operation of the given route.
+    fn document(&mut self, filter: MethodFilter, path: &str, operation_id: &str, operation: Operation, summary: &str) {
+        // Document the path parameters, in the order of the path.
+        let mut parameters = Vec::new();
+        let mut openapi_path = String::new();
+        for segment in path.split('/').filter(|segment| !segment.is_empty()) {
+            match segment.strip_prefix(':') {
+                Some(name) => {
+                    openapi_path.push_str(&format!("/{{{name}}}"));
+                    parameters.push(json!({ "name": name, "in": "path", "required": true, "schema": string(name) }));
+                }
+                None => openapi_path.push_str(&format!("/{segment}")),
+            }
+        }
+        // Document the query parameters.
+        parameters
+            .extend(operation.query.iter().map(|name| json!({ "name": name, "in": "query", "schema": string(name) })));
+
+        // Document an untyped response as any JSON value.
+        let response = operation.response.unwrap_or(schema::<AnyJson>);
+        let mut document = json!({
+            "operationId": operation_id,
+            "summary": summary,
+            "responses": {
+                "200": { "description": "OK", "content": { "application/json": { "schema": response(&mut self.generator) } } },
+                "400": { "description": "The parameters or the request body are invalid" },
+                "500": { "description": "The request failed", "content": { "text/plain": { "schema": { "type": "string" } } } },
+            },
+        });
+        if !parameters.is_empty() {
+            document["parameters"] = JsonValue::Array(parameters);
+        }
+        if operation.binary {
+            document["responses"]["200"]["content"][OCTET_STREAM] = json!({
+                "schema": { "type": "string", "format": "binary", "description": "The `ToBytes` encoding" },
+            });
+        }
+        if operation.authenticated {
+            document["security"] = json!([{ "bearerAuth": [] }]);
+            document["responses"]["401"] =
+                json!({ "description": "The JSON Web Token is missing, invalid, or expired" });
+        }
+        if let Some(body) = operation.body {
+            document["requestBody"] = json!({
+                "required": true,
+                "content": { "application/json": { "schema": body(&mut self.generator) } },
+            });
+        }
+
+        let method = if filter == MethodFilter::POST { "post" } else { "get" };
+        if let JsonValue::Object(methods) = self.paths.entry(openapi_path).or_insert_with(|| json!({})) {
+            methods.insert(method.to_string(), document);
+        }
+    }
+}
+
+/// Returns the schema of a path or query parameter, which is parsed from its string encoding.
+fn string(description: &str) -> JsonValue {
+    json!({ "type": "string", "description": description })
+}
+
+/// Any JSON value, such as a JSON-RPC request or response, or the OpenAPI document.
+struct AnyJson;
+
+impl JsonSchema for AnyJson {
+    fn schema_name() -> String {
+        "AnyJson".to_string()
+    }
+
+    fn is_referenceable() -> bool {
+        false
+    }
+
+    /// Returns the empty schema, which accepts any value.
+    fn json_schema(_: &mut SchemaGenerator) -> Schema {
+        SchemaObject::default().into()
+    }
+}
+
+/// A request body, whose schema is documented.
+pub(crate) trait RequestBody {
+    /// The type whose schema documents the request body.
+    type Schema: JsonSchema;
+}
+
+impl RequestBody for IpAddr {
+    type Schema = IpAddr;
+}
+
+impl RequestBody for SocketAddr {
+    type Schema = SocketAddr;
+}
+
+impl RequestBody for String {
+    type Schema = String;
+}
+
+impl<N: Network> RequestBody for Transaction<N> {
+    type Schema = schema::Transaction;
+}
+
+impl<N: Network> RequestBody for FeeRequest<N> {
+    type Schema = FeeRequest<N>;
+}
+
+/// A response body, whose schema is documented.
+pub(crate) trait ResponseBody {
+    /// The type whose schema documents the response body.
+    type Schema: JsonSchema;
+}
+
+macro_rules! impl_response_body {
+    ($($ty:ty => $schema:ty,)*) => {
+        $(
+            impl<N: Network> ResponseBody for $ty {
+                type Schema = $schema;
+            }
+        )*
+    };
+}
+
+impl_response_body! {
+    Address<N> => String,
+    Block<N> => schema::Block,
+    Committee<N> => schema::Committee,
+    Identifier<N> => String,
+    MappingEntries<N> => MappingEntries<N>,
+    Program<N> => String,
+    ProgramAbi<N> => ProgramAbi<N>,
+    ProgramList<N> => ProgramList<N>,
+    ProverSolution<N> => schema::ProverSolution,
+    Simulation<N> => Simulation<N>,
+    StatePath<N> => String,
+    Transaction<N> => schema::Transaction,
+    TransactionPage<N> => TransactionPage<N>,
+    Transactions<N> => schema::Transactions,
+    Transmission<N> => schema::Transmission,
+    Value<N> => String,
+}
+
+impl ResponseBody for bool {
+    type Schema = bool;
+}
+
+impl ResponseBody for u32 {
+    type Schema = u32;
+}
+
+impl ResponseBody for usize {
+    type Schema = usize;
+}
+
+impl ResponseBody for String {
+    type Schema = String;
+}
+
+impl ResponseBody for IpAddr {
+    type Schema = IpAddr;
+}
+
+impl ResponseBody for SocketAddr {
+    type Schema = SocketAddr;
+}
+
+impl ResponseBody for NodeType {
+    type Schema = String;
+}
+
+impl ResponseBody for FeeEstimate {
+    type Schema = FeeEstimate;
+}
+
+impl<T: ResponseBody> ResponseBody for Option<T> {
+    type Schema = Option<T::Schema>;
+}
+
+impl<T: ResponseBody> ResponseBody for Vec<T> {
+    type Schema = Vec<T::Schema>;
+}
+
+impl<T: ResponseBody> ResponseBody for IndexSet<T> {
+    type Schema = Vec<T::Schema>;
+}
+
+impl<A: ResponseBody, B: ResponseBody> ResponseBody for (A, B) {
+    type Schema = (A::Schema, B::Schema);
+}
+
+/// The keys are IDs, which are encoded as strings.
+impl<K, V: ResponseBody> ResponseBody for IndexMap<K, V> {
+    type Schema = HashMap<String, V::Schema>;
+}
+
+impl<T: ResponseBody + FromBytes + ToBytes + Send + 'static> ResponseBody for Data<T> {
+    type Schema = schema::Data<T::Schema>;
+}
+
+/// The query parameters, request body, and response body of an operation, as derived from its handler.
+#[derive(Default)]
+pub(crate) struct Operation {
+    /// The names of the query parameters.
+    query: Vec<&'static str>,
+    /// The schema of the request body.
+    body: Option<SchemaFn>,
+    /// The schema of the response body, or `None` if the response is untyped.
+    response: Option<SchemaFn>,
+    /// Whether the response is also served in the binary encoding.
+    binary: bool,
+    /// Whether the request requires the JSON Web Token of the node.
+    authenticated: bool,
+}
+
+/// A handler whose extractors and response can be documented.
+pub(crate) trait Describe<T> {
+    /// Documents the extractors and the response of the handler.
+    fn describe(operation: &mut Operation);
+}
+
+/// An extractor which can be documented.
+pub(crate) trait DescribeExtractor {
+    /// Documents the extractor.
+    fn describe(operation: &mut Operation);
+}
+
+macro_rules! impl_describe {
+    ($($ty:ident),*) => {
+        impl<F, Fut, $($ty,)*> Describe<($($ty,)*)> for F
+        where
+            F: FnOnce($($ty,)*) -> Fut,
+            Fut: Future,
+            Fut::Output: DescribeResponse,
+            $($ty: DescribeExtractor,)*
+        {
+            fn describe(operation: &mut Operation) {
+                $($ty::describe(operation);)*
+                <Fut::Output as DescribeResponse>::describe(operation);
+            }
+        }
+    };
+}
+
+impl_describe!();
+impl_describe!(T1);
+impl_describe!(T1, T2);
+impl_describe!(T1, T2, T3);
+
+impl<S> DescribeExtractor for State<S> {
+    /// The state is not part of the request.
+    fn describe(_: &mut Operation) {}
+}
+
+impl<T> DescribeExtractor for Path<T> {
+    /// The path parameters are documented from the path of the route.
+    fn describe(_: &mut Operation) {}
+}
+
+impl<T: DeserializeOwned> DescribeExtractor for Query<T> {
+    /// Documents the fields of the query object as query parameters.
+    fn describe(operation: &mut Operation) {
+        let mut fields = FieldNames(&[]);
+        let _ = T::deserialize(&mut fields);
+        operation.query.extend(fields.0);
+    }
+}
+
+impl<T: RequestBody> DescribeExtractor for Json<T> {
+    /// Documents the schema of the request body.
+    fn describe(operation: &mut Operation) {
+        operation.body = Some(schema::<T::Schema>);
+    }
+}
+
+impl DescribeExtractor for Authenticated {
+    /// Documents the JSON Web Token of the request.
+    fn describe(operation: &mut Operation) {
+        operation.authenticated = true;
+    }
+}
+
+impl DescribeExtractor for ResponseFormat {
+    /// Documents the binary encoding of the response.
+    fn describe(operation: &mut Operation) {
+        operation.binary = true;
+    }
+}
+
+impl DescribeExtractor for Bytes {
+    /// Documents a request body, which the handler parses itself.
+    fn describe(operation: &mut Operation) {
+        operation.body = Some(schema::<AnyJson>);
+    }
+}
+
+/// The output of a handler, whose response body can be documented.
+pub(crate) trait DescribeResponse {
+    /// Documents the response body.
+    fn describe(operation: &mut Operation);
+}
+
+impl<T: ResponseBody> DescribeResponse for JsonResponse<T> {
+    /// Documents the schema of the response body.
+    fn describe(operation: &mut Operation) {
+        operation.response = Some(schema::<T::Schema>);
+    }
+}
+
+impl<T: ResponseBody> DescribeResponse for FormattedResponse<T> {
+    /// Documents the schema of the JSON encoding of the response body.
+    fn describe(operation: &mut Operation) {
+        operation.response = Some(schema::<T::Schema>);
+    }
+}
+
+impl<T: DescribeResponse, E> DescribeResponse for Result<T, E> {
+    /// Documents the response body of a success, as the errors are documented for every operation.
+    fn describe(operation: &mut Operation) {
+        T::describe(operation);
+    }
+}
+
+impl DescribeResponse for Response {
+    /// An untyped response, such as a JSON-RPC response, is documented as any JSON value.
+    fn describe(_: &mut Operation) {}
+}
+
+/// A deserializer which records the field names of a struct, and deserializes nothing.
+struct FieldNames(&'static [&'static str]);
+
+impl<'de> Deserializer<'de> for &mut FieldNames {
+    type Error = de::value::Error;
+
+    forward_to_deserialize_any! {
+        bool i8 i16 i32 i64 i128 u8 u16 u32 u64 u128 f32 f64 char str string bytes byte_buf option unit
+        unit_struct newtype_struct seq tuple tuple_struct map enum identifier ignored_any
+    }
+
+    fn deserialize_any<V: Visitor<'de>>(self, _: V) -> Result<V::Value, Self::Error> {
+        Err(de::Error::custom("Only the field names of a struct are recorded"))
+    }
+
+    fn deserialize_struct<V: Visitor<'de>>(
+        self,
+        _: &'static str,
+        fields: &'static [&'static str],
+        _: V,
+    ) -> Result<V::Value, Self::Error> {
+        self.0 = fields;
+        Err(de::Error::custom("Only the field names of a struct are recorded"))
+    }
+}
+
+/// Returns the name of the given handler, such as `get_block`.
+fn handler_name<H>() -> String {
+    let name = type_name::<H>();
+    name.rsplit("::").next().unwrap_or(name).to_string()
+}
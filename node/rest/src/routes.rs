@@ -13,11 +13,46 @@
 // limitations under the License.
 
 use super::*;
-use snarkvm::prelude::{block::Transaction, Identifier, Plaintext};
-
-use indexmap::IndexMap;
+use snarkos_node_router::messages::NodeType;
+use snarkvm::{
+    ledger::{
+        coinbase::{ProverSolution, PuzzleCommitment},
+        committee::Committee,
+        narwhal::{self, Transmission, TransmissionID},
+    },
+    prelude::{
+        block::{Block, Transaction, Transactions},
+        deployment_cost,
+        execution_cost,
+        Address,
+        Identifier,
+        Plaintext,
+        Program,
+        StatePath,
+        Value,
+    },
+    synthesizer::program::FinalizeOperation,
+};
+
+use snarkos_node_indexer::MAX_PAGE_SIZE;
+use snarkos_node_narwhal_ledger_service::{candidate_keys, MappingResolver};
+use snarkos_node_rest_types::{
+    FeeEstimate,
+    FeeRequest,
+    MappingChange,
+    MappingEntries,
+    ProgramAbi,
+    ProgramList,
+    Simulation,
+    SimulationStatus,
+    TransactionPage,
+};
+
+use axum::response::ErrorResponse;
+use indexmap::{IndexMap, IndexSet};
 use rayon::prelude::*;
 use serde::{Deserialize, Serialize};
+use std::net::IpAddr;
 
 /// The `get_blocks` query object.
 #[derive(Deserialize, Serialize)]
@@ -28,30 +63,63 @@ pub(crate) struct BlockRange {
     end: u32,
 }
 
+/// The `get_programs` query object.
+#[derive(Deserialize, Serialize)]
+pub(crate) struct ProgramsPage {
+    /// The prefix of the program IDs, such as `token` to match `token.aleo` and `token_v2.aleo`.
+    prefix: Option<String>,
+    /// The index of the first program, from the `next_cursor` of the previous page.
+    #[serde(default)]
+    cursor: usize,
+    /// The maximum number of programs.
+    limit: Option<usize>,
+}
+
+/// The `get_mapping` query object.
+#[derive(Deserialize, Serialize)]
+#[serde(bound = "N: Network")]
+pub(crate) struct MappingPage<N: Network> {
+    /// The last key of the previous page, from its `next_cursor`.
+    cursor: Option<Plaintext<N>>,
+    /// The maximum number of entries.
+    limit: Option<usize>,
+}
+
+/// The `get_address_transactions` and `get_program_transactions` query object.
+#[derive(Deserialize, Serialize)]
+pub(crate) struct TransactionsPage<N: Network> {
+    /// The function name, to only return the transactions of a program calling the function.
+    function: Option<Identifier<N>>,
+    /// The cursor, from the `next_cursor` of the previous page.
+    cursor: Option<u64>,
+    /// The maximum number of transactions.
+    limit: Option<usize>,
+}
+
 impl<N: Network, C: ConsensusStorage<N>, R: Routing<N>> Rest<N, C, R> {
     // GET /testnet3/latest/height
-    pub(crate) async fn latest_height(State(rest): State<Self>) -> ErasedJson {
-        ErasedJson::pretty(rest.ledger.latest_height())
+    pub(crate) async fn latest_height(State(rest): State<Self>) -> JsonResponse<u32> {
+        JsonResponse(rest.ledger.latest_height())
     }
 
     // GET /testnet3/latest/hash
-    pub(crate) async fn latest_hash(State(rest): State<Self>) -> ErasedJson {
-        ErasedJson::pretty(rest.ledger.latest_hash())
+    pub(crate) async fn latest_hash(State(rest): State<Self>) -> JsonResponse<String> {
+        JsonResponse(rest.ledger.latest_hash().to_string())
     }
 
     // GET /testnet3/latest/block
-    pub(crate) async fn latest_block(State(rest): State<Self>) -> ErasedJson {
-        ErasedJson::pretty(rest.ledger.latest_block())
+    pub(crate) async fn latest_block(State(rest): State<Self>) -> JsonResponse<Block<N>> {
+        JsonResponse(rest.ledger.latest_block())
     }
 
     // GET /testnet3/latest/stateRoot
-    pub(crate) async fn latest_state_root(State(rest): State<Self>) -> ErasedJson {
-        ErasedJson::pretty(rest.ledger.latest_state_root())
+    pub(crate) async fn latest_state_root(State(rest): State<Self>) -> JsonResponse<String> {
+        JsonResponse(rest.ledger.latest_state_root().to_string())
     }
 
     // GET /testnet3/latest/committee
-    pub(crate) async fn latest_committee(State(rest): State<Self>) -> Result<ErasedJson, RestError> {
-        Ok(ErasedJson::pretty(rest.ledger.latest_committee()?))
+    pub(crate) async fn latest_committee(State(rest): State<Self>) -> Result<JsonResponse<Committee<N>>, RestError> {
+        Ok(JsonResponse(rest.ledger.latest_committee()?))
     }
 
     // GET /testnet3/block/{height}
@@ -59,7 +127,8 @@ impl<N: Network, C: ConsensusStorage<N>, R: Routing<N>> Rest<N, C, R> {
     pub(crate) async fn get_block(
         State(rest): State<Self>,
         Path(height_or_hash): Path<String>,
-    ) -> Result<ErasedJson, RestError> {
+        format: ResponseFormat,
+    ) -> Result<FormattedResponse<Block<N>>, RestError> {
         // Manually parse the height or the height or the hash, axum doesn't support different types
         // for the same path param.
         let block = if let Ok(height) = height_or_hash.parse::<u32>() {
@@ -72,85 +141,68 @@ impl<N: Network, C: ConsensusStorage<N>, R: Routing<N>> Rest<N, C, R> {
             rest.ledger.get_block_by_hash(&hash)?
         };
 
-        Ok(ErasedJson::pretty(block))
+        format.respond(block)
     }
 
     // GET /testnet3/blocks?start={start_height}&end={end_height}
     pub(crate) async fn get_blocks(
         State(rest): State<Self>,
         Query(block_range): Query<BlockRange>,
-    ) -> Result<ErasedJson, RestError> {
-        let start_height = block_range.start;
-        let end_height = block_range.end;
-
-        const MAX_BLOCK_RANGE: u32 = 50;
-
-        // Ensure the end height is greater than the start height.
-        if start_height > end_height {
-            return Err(RestError("Invalid block range".to_string()));
-        }
-
-        // Ensure the block range is bounded.
-        if end_height - start_height > MAX_BLOCK_RANGE {
-            return Err(RestError(format!(
-                "Cannot request more than {MAX_BLOCK_RANGE} blocks per call (requested {})",
-                end_height - start_height
-            )));
-        }
-
-        let blocks = cfg_into_iter!((start_height..end_height))
-            .map(|height| rest.ledger.get_block(height))
-            .collect::<Result<Vec<_>, _>>()?;
-
-        Ok(ErasedJson::pretty(blocks))
+        format: ResponseFormat,
+    ) -> Result<FormattedResponse<Vec<Block<N>>>, RestError> {
+        format.respond_list(rest.blocks(block_range.start, block_range.end)?)
     }
 
     // GET /testnet3/height/{blockHash}
     pub(crate) async fn get_height(
         State(rest): State<Self>,
         Path(hash): Path<N::BlockHash>,
-    ) -> Result<ErasedJson, RestError> {
-        Ok(ErasedJson::pretty(rest.ledger.get_height(&hash)?))
+    ) -> Result<JsonResponse<u32>, RestError> {
+        Ok(JsonResponse(rest.ledger.get_height(&hash)?))
     }
 
     // GET /testnet3/block/{height}/transactions
     pub(crate) async fn get_block_transactions(
         State(rest): State<Self>,
         Path(height): Path<u32>,
-    ) -> Result<ErasedJson, RestError> {
-        Ok(ErasedJson::pretty(rest.ledger.get_transactions(height)?))
+    ) -> Result<JsonResponse<Transactions<N>>, RestError> {
+        Ok(JsonResponse(rest.ledger.get_transactions(height)?))
     }
 
     // GET /testnet3/transaction/{transactionID}
     pub(crate) async fn get_transaction(
         State(rest): State<Self>,
         Path(tx_id): Path<N::TransactionID>,
-    ) -> Result<ErasedJson, RestError> {
-        Ok(ErasedJson::pretty(rest.ledger.get_transaction(tx_id)?))
+    ) -> Result<JsonResponse<Transaction<N>>, RestError> {
+        Ok(JsonResponse(rest.ledger.get_transaction(tx_id)?))
     }
 
     // GET /testnet3/memoryPool/transmissions
-    pub(crate) async fn get_memory_pool_transmissions(State(rest): State<Self>) -> Result<ErasedJson, RestError> {
+    pub(crate) async fn get_memory_pool_transmissions(
+        State(rest): State<Self>,
+    ) -> Result<JsonResponse<IndexMap<TransmissionID<N>, Transmission<N>>>, RestError> {
         match rest.consensus {
-            Some(consensus) => {
-                Ok(ErasedJson::pretty(consensus.unconfirmed_transmissions().collect::<IndexMap<_, _>>()))
-            }
+            Some(consensus) => Ok(JsonResponse(consensus.unconfirmed_transmissions().collect::<IndexMap<_, _>>())),
             None => Err(RestError("Route isn't available for this node type".to_string())),
         }
     }
 
     // GET /testnet3/memoryPool/solutions
-    pub(crate) async fn get_memory_pool_solutions(State(rest): State<Self>) -> Result<ErasedJson, RestError> {
+    pub(crate) async fn get_memory_pool_solutions(
+        State(rest): State<Self>,
+    ) -> Result<JsonResponse<IndexMap<PuzzleCommitment<N>, narwhal::Data<ProverSolution<N>>>>, RestError> {
         match rest.consensus {
-            Some(consensus) => Ok(ErasedJson::pretty(consensus.unconfirmed_solutions().collect::<IndexMap<_, _>>())),
+            Some(consensus) => Ok(JsonResponse(consensus.unconfirmed_solutions().collect::<IndexMap<_, _>>())),
             None => Err(RestError("Route isn't available for this node type".to_string())),
         }
     }
 
     // GET /testnet3/memoryPool/transactions
-    pub(crate) async fn get_memory_pool_transactions(State(rest): State<Self>) -> Result<ErasedJson, RestError> {
+    pub(crate) async fn get_memory_pool_transactions(
+        State(rest): State<Self>,
+    ) -> Result<JsonResponse<IndexMap<N::TransactionID, narwhal::Data<Transaction<N>>>>, RestError> {
         match rest.consensus {
-            Some(consensus) => Ok(ErasedJson::pretty(consensus.unconfirmed_transactions().collect::<IndexMap<_, _>>())),
+            Some(consensus) => Ok(JsonResponse(consensus.unconfirmed_transactions().collect::<IndexMap<_, _>>())),
             None => Err(RestError("Route isn't available for this node type".to_string())),
         }
     }
@@ -159,98 +211,298 @@ impl<N: Network, C: ConsensusStorage<N>, R: Routing<N>> Rest<N, C, R> {
     pub(crate) async fn get_program(
         State(rest): State<Self>,
         Path(id): Path<ProgramID<N>>,
-    ) -> Result<ErasedJson, RestError> {
-        Ok(ErasedJson::pretty(rest.ledger.get_program(id)?))
+    ) -> Result<JsonResponse<Program<N>>, ErrorResponse> {
+        // Respond with a 404 if the program is not deployed, so that clients can tell it apart from other errors.
+        match rest.ledger.vm().transaction_store().get_program(&id).map_err(RestError::from)? {
+            Some(program) => Ok(JsonResponse(program)),
+            None => Err((StatusCode::NOT_FOUND, format!("The program '{id}' is not deployed")).into()),
+        }
+    }
+
+    // GET /testnet3/programs?prefix={prefix}&cursor={cursor}&limit={limit}
+    pub(crate) async fn get_programs(
+        State(rest): State<Self>,
+        Query(page): Query<ProgramsPage>,
+    ) -> Result<JsonResponse<ProgramList<N>>, RestError> {
+        Ok(JsonResponse(rest.programs(page.prefix.unwrap_or_default(), page.cursor, page.limit)?))
+    }
+
+    // GET /testnet3/program/{programID}/abi
+    pub(crate) async fn get_program_abi(
+        State(rest): State<Self>,
+        Path(id): Path<ProgramID<N>>,
+    ) -> Result<JsonResponse<ProgramAbi<N>>, RestError> {
+        Ok(JsonResponse(ProgramAbi::from(&rest.ledger.get_program(id)?)))
     }
 
     // GET /testnet3/program/{programID}/mappings
     pub(crate) async fn get_mapping_names(
         State(rest): State<Self>,
         Path(id): Path<ProgramID<N>>,
-    ) -> Result<ErasedJson, RestError> {
-        Ok(ErasedJson::pretty(rest.ledger.vm().finalize_store().get_mapping_names_confirmed(&id)?))
+    ) -> Result<JsonResponse<Option<IndexSet<Identifier<N>>>>, RestError> {
+        Ok(JsonResponse(rest.ledger.vm().finalize_store().get_mapping_names_confirmed(&id)?))
+    }
+
+    // GET /testnet3/program/{programID}/mapping/{mappingName}?cursor={cursor}&limit={limit}
+    pub(crate) async fn get_mapping(
+        State(rest): State<Self>,
+        Path((id, name)): Path<(ProgramID<N>, Identifier<N>)>,
+        Query(page): Query<MappingPage<N>>,
+    ) -> Result<JsonResponse<MappingEntries<N>>, RestError> {
+        Ok(JsonResponse(rest.mapping_entries(&id, &name, page.cursor.as_ref(), page.limit)?))
     }
 
     // GET /testnet3/program/{programID}/mapping/{mappingName}/{mappingKey}
     pub(crate) async fn get_mapping_value(
         State(rest): State<Self>,
         Path((id, name, key)): Path<(ProgramID<N>, Identifier<N>, Plaintext<N>)>,
-    ) -> Result<ErasedJson, RestError> {
-        Ok(ErasedJson::pretty(rest.ledger.vm().finalize_store().get_value_confirmed(&id, &name, &key)?))
+    ) -> Result<JsonResponse<Option<Value<N>>>, RestError> {
+        Ok(JsonResponse(rest.ledger.vm().finalize_store().get_value_confirmed(&id, &name, &key)?))
+    }
+
+    // GET /testnet3/program/{programID}/transactions?function={functionName}&cursor={cursor}&limit={limit}
+    pub(crate) async fn get_program_transactions(
+        State(rest): State<Self>,
+        Path(id): Path<ProgramID<N>>,
+        Query(page): Query<TransactionsPage<N>>,
+    ) -> Result<JsonResponse<TransactionPage<N>>, RestError> {
+        let indexer = rest.indexer()?;
+        let limit = page.limit.unwrap_or(MAX_PAGE_SIZE);
+        Ok(JsonResponse(indexer.program_transactions(&id, page.function.as_ref(), page.cursor, limit)?))
+    }
+
+    // GET /testnet3/address/{address}/transactions?cursor={cursor}&limit={limit}
+    pub(crate) async fn get_address_transactions(
+        State(rest): State<Self>,
+        Path(address): Path<Address<N>>,
+        Query(page): Query<TransactionsPage<N>>,
+    ) -> Result<JsonResponse<TransactionPage<N>>, RestError> {
+        let indexer = rest.indexer()?;
+        let limit = page.limit.unwrap_or(MAX_PAGE_SIZE);
+        Ok(JsonResponse(indexer.address_transactions(&address, page.cursor, limit)?))
     }
 
     // GET /testnet3/statePath/{commitment}
     pub(crate) async fn get_state_path_for_commitment(
         State(rest): State<Self>,
         Path(commitment): Path<Field<N>>,
-    ) -> Result<ErasedJson, RestError> {
-        Ok(ErasedJson::pretty(rest.ledger.get_state_path_for_commitment(&commitment)?))
+    ) -> Result<JsonResponse<StatePath<N>>, RestError> {
+        Ok(JsonResponse(rest.ledger.get_state_path_for_commitment(&commitment)?))
     }
 
     // GET /testnet3/committee/latest
-    pub(crate) async fn get_committee_latest(State(rest): State<Self>) -> Result<ErasedJson, RestError> {
-        Ok(ErasedJson::pretty(rest.ledger.latest_committee()?))
+    pub(crate) async fn get_committee_latest(
+        State(rest): State<Self>,
+    ) -> Result<JsonResponse<Committee<N>>, RestError> {
+        Ok(JsonResponse(rest.ledger.latest_committee()?))
     }
 
     // GET /testnet3/peers/count
-    pub(crate) async fn get_peers_count(State(rest): State<Self>) -> ErasedJson {
-        ErasedJson::pretty(rest.routing.router().number_of_connected_peers())
+    pub(crate) async fn get_peers_count(State(rest): State<Self>) -> JsonResponse<usize> {
+        JsonResponse(rest.routing.router().number_of_connected_peers())
     }
 
     // GET /testnet3/peers/all
-    pub(crate) async fn get_peers_all(State(rest): State<Self>) -> ErasedJson {
-        ErasedJson::pretty(rest.routing.router().connected_peers())
+    pub(crate) async fn get_peers_all(State(rest): State<Self>) -> JsonResponse<Vec<SocketAddr>> {
+        JsonResponse(rest.routing.router().connected_peers())
     }
 
     // GET /testnet3/peers/all/metrics
-    pub(crate) async fn get_peers_all_metrics(State(rest): State<Self>) -> ErasedJson {
-        ErasedJson::pretty(rest.routing.router().connected_metrics())
+    pub(crate) async fn get_peers_all_metrics(State(rest): State<Self>) -> JsonResponse<Vec<(SocketAddr, NodeType)>> {
+        JsonResponse(rest.routing.router().connected_metrics())
+    }
+
+    // POST /testnet3/peers/connect
+    pub(crate) async fn connect_peer(
+        State(rest): State<Self>,
+        _: Authenticated,
+        Json(peer_ip): Json<SocketAddr>,
+    ) -> Result<JsonResponse<bool>, RestError> {
+        match rest.routing.router().connect(peer_ip) {
+            Some(handle) => Ok(JsonResponse(handle.await.map_err(|error| RestError(error.to_string()))?)),
+            None => Err(RestError(format!("Connecting to '{peer_ip}' is not allowed"))),
+        }
+    }
+
+    // POST /testnet3/peers/disconnect
+    pub(crate) async fn disconnect_peer(
+        State(rest): State<Self>,
+        _: Authenticated,
+        Json(peer_ip): Json<SocketAddr>,
+    ) -> Result<JsonResponse<bool>, RestError> {
+        let disconnected =
+            rest.routing.router().disconnect(peer_ip).await.map_err(|error| RestError(error.to_string()))?;
+        Ok(JsonResponse(disconnected))
+    }
+
+    // POST /testnet3/peers/ban
+    pub(crate) async fn ban_peer(
+        State(rest): State<Self>,
+        _: Authenticated,
+        Json(ip): Json<IpAddr>,
+    ) -> Result<JsonResponse<bool>, RestError> {
+        // Ban the IP address, so it cannot connect until it is unbanned, and disconnect its peers.
+        let router = rest.routing.router();
+        let mut disconnected = false;
+        for peer_ip in router.insert_banned_ip(ip) {
+            disconnected |= router.disconnect(peer_ip).await.map_err(|error| RestError(error.to_string()))?;
+        }
+        Ok(JsonResponse(disconnected))
+    }
+
+    // POST /testnet3/peers/unban
+    pub(crate) async fn unban_peer(
+        State(rest): State<Self>,
+        _: Authenticated,
+        Json(ip): Json<IpAddr>,
+    ) -> JsonResponse<bool> {
+        JsonResponse(rest.routing.router().remove_banned_ip(ip))
+    }
+
+    // GET /testnet3/peers/bans
+    pub(crate) async fn get_peers_bans(State(rest): State<Self>, _: Authenticated) -> JsonResponse<Vec<IpAddr>> {
+        JsonResponse(rest.routing.router().banned_ips())
+    }
+
+    // POST /testnet3/validators/connect
+    pub(crate) async fn connect_validator(
+        State(rest): State<Self>,
+        _: Authenticated,
+        Json(peer_ip): Json<SocketAddr>,
+    ) -> Result<JsonResponse<bool>, RestError> {
+        let gateway = rest.consensus()?.bft().primary().gateway();
+        match gateway.connect(peer_ip) {
+            Some(handle) => {
+                handle.await.map_err(|error| RestError(error.to_string()))?;
+                Ok(JsonResponse(gateway.is_connected_ip(peer_ip)))
+            }
+            None => Err(RestError(format!("Connecting to '{peer_ip}' is not allowed"))),
+        }
+    }
+
+    // POST /testnet3/validators/disconnect
+    pub(crate) async fn disconnect_validator(
+        State(rest): State<Self>,
+        _: Authenticated,
+        Json(peer_ip): Json<SocketAddr>,
+    ) -> Result<JsonResponse<bool>, RestError> {
+        let gateway = rest.consensus()?.bft().primary().gateway();
+        let was_connected = gateway.is_connected_ip(peer_ip);
+        gateway.disconnect(peer_ip).await.map_err(|error| RestError(error.to_string()))?;
+        Ok(JsonResponse(was_connected))
+    }
+
+    // GET /testnet3/node/logFilter
+    pub(crate) async fn get_log_filter(_: Authenticated) -> Result<JsonResponse<String>, RestError> {
+        Ok(JsonResponse(Self::log_filter()?.directives()))
+    }
+
+    // POST /testnet3/node/logFilter
+    pub(crate) async fn set_log_filter(
+        _: Authenticated,
+        Json(directives): Json<String>,
+    ) -> Result<JsonResponse<String>, RestError> {
+        let log_filter = Self::log_filter()?;
+        log_filter.set_directives(&directives)?;
+        info!("Changed the log filter to '{directives}'");
+        Ok(JsonResponse(log_filter.directives()))
     }
 
     // GET /testnet3/node/address
-    pub(crate) async fn get_node_address(State(rest): State<Self>) -> ErasedJson {
-        ErasedJson::pretty(rest.routing.router().address())
+    pub(crate) async fn get_node_address(State(rest): State<Self>) -> JsonResponse<Address<N>> {
+        JsonResponse(rest.routing.router().address())
     }
 
     // GET /testnet3/find/blockHash/{transactionID}
     pub(crate) async fn find_block_hash(
         State(rest): State<Self>,
         Path(tx_id): Path<N::TransactionID>,
-    ) -> Result<ErasedJson, RestError> {
-        Ok(ErasedJson::pretty(rest.ledger.find_block_hash(&tx_id)?))
+    ) -> Result<JsonResponse<Option<String>>, RestError> {
+        Ok(JsonResponse(rest.ledger.find_block_hash(&tx_id)?.map(|hash| hash.to_string())))
     }
 
     // GET /testnet3/find/transactionID/deployment/{programID}
     pub(crate) async fn find_transaction_id_from_program_id(
         State(rest): State<Self>,
         Path(program_id): Path<ProgramID<N>>,
-    ) -> Result<ErasedJson, RestError> {
-        Ok(ErasedJson::pretty(rest.ledger.find_transaction_id_from_program_id(&program_id)?))
+    ) -> Result<JsonResponse<Option<String>>, RestError> {
+        Ok(JsonResponse(rest.ledger.find_transaction_id_from_program_id(&program_id)?.map(|id| id.to_string())))
     }
 
     // GET /testnet3/find/transactionID/{transitionID}
     pub(crate) async fn find_transaction_id_from_transition_id(
         State(rest): State<Self>,
         Path(transition_id): Path<N::TransitionID>,
-    ) -> Result<ErasedJson, RestError> {
-        Ok(ErasedJson::pretty(rest.ledger.find_transaction_id_from_transition_id(&transition_id)?))
+    ) -> Result<JsonResponse<Option<String>>, RestError> {
+        Ok(JsonResponse(rest.ledger.find_transaction_id_from_transition_id(&transition_id)?.map(|id| id.to_string())))
     }
 
     // GET /testnet3/find/transitionID/{inputOrOutputID}
     pub(crate) async fn find_transition_id(
         State(rest): State<Self>,
         Path(input_or_output_id): Path<Field<N>>,
-    ) -> Result<ErasedJson, RestError> {
-        Ok(ErasedJson::pretty(rest.ledger.find_transition_id(&input_or_output_id)?))
+    ) -> Result<JsonResponse<String>, ErrorResponse> {
+        // Respond with a 404 if the ID is not found, so that clients can tell an unspent serial number apart.
+        let transition_store = rest.ledger.vm().transition_store();
+        if !transition_store.contains_input_id(&input_or_output_id).map_err(RestError::from)?
+            && !transition_store.contains_output_id(&input_or_output_id).map_err(RestError::from)?
+        {
+            return Err((StatusCode::NOT_FOUND, format!("No transition contains '{input_or_output_id}'")).into());
+        }
+        let transition_id = rest.ledger.find_transition_id(&input_or_output_id).map_err(RestError::from)?;
+        Ok(JsonResponse(transition_id.to_string()))
     }
 
     // POST /testnet3/transaction/broadcast
     pub(crate) async fn transaction_broadcast(
         State(rest): State<Self>,
         Json(tx): Json<Transaction<N>>,
-    ) -> Result<ErasedJson, RestError> {
+    ) -> Result<JsonResponse<String>, RestError> {
+        Ok(JsonResponse(rest.broadcast_transaction(tx).await?.to_string()))
+    }
+
+    // POST /testnet3/transaction/estimateFee
+    pub(crate) async fn transaction_estimate_fee(
+        State(rest): State<Self>,
+        Json(request): Json<FeeRequest<N>>,
+    ) -> Result<JsonResponse<FeeEstimate>, RestError> {
+        // Compute the base fee.
+        let (base_fee, storage_cost, namespace_cost, finalize_cost) = match request {
+            FeeRequest::Deployment(deployment) => {
+                let (base_fee, (storage_cost, namespace_cost)) = deployment_cost(&deployment)?;
+                (base_fee, storage_cost, Some(namespace_cost), None)
+            }
+            FeeRequest::Execution(execution) => {
+                let (base_fee, (storage_cost, finalize_cost)) = execution_cost(rest.ledger.vm(), &execution)?;
+                (base_fee, storage_cost, None, Some(finalize_cost))
+            }
+        };
+
+        // Suggest a priority fee, based on the memory pool.
+        let priority_fee = rest.suggested_priority_fee().await?;
+
+        Ok(JsonResponse(FeeEstimate { base_fee, storage_cost, namespace_cost, finalize_cost, priority_fee }))
+    }
+
+    // POST /testnet3/transaction/simulate
+    pub(crate) async fn transaction_simulate(
+        State(rest): State<Self>,
+        Json(tx): Json<Transaction<N>>,
+    ) -> Result<JsonResponse<Simulation<N>>, RestError> {
+        // Note: The transaction is not added to the memory pool, nor broadcast.
+        // Note: The checks and finalize are too expensive for the async runtime, so they run on a blocking thread.
+        let simulation =
+            tokio::task::spawn_blocking(move || rest.simulate(&tx)).await.map_err(anyhow::Error::from)??;
+        Ok(JsonResponse(simulation))
+    }
+}
+
+impl<N: Network, C: ConsensusStorage<N>, R: Routing<N>> Rest<N, C, R> {
+    /// Adds the given transaction to the memory pool, if the consensus module is enabled,
+    /// and broadcasts it to the peers. Returns the transaction ID.
+    pub(crate) async fn broadcast_transaction(&self, tx: Transaction<N>) -> Result<N::TransactionID> {
         // If the consensus module is enabled, add the unconfirmed transaction to the memory pool.
-        if let Some(consensus) = rest.consensus {
+        if let Some(consensus) = &self.consensus {
             // Add the unconfirmed transaction to the memory pool.
             consensus.add_unconfirmed_transaction(tx.clone()).await?;
         }
@@ -263,8 +515,256 @@ impl<N: Network, C: ConsensusStorage<N>, R: Routing<N>> Rest<N, C, R> {
         });
 
         // Broadcast the transaction.
-        rest.routing.propagate(message, &[]);
+        self.routing.propagate(message, &[]);
 
-        Ok(ErasedJson::pretty(tx_id))
+        Ok(tx_id)
+    }
+
+    /// Returns the blocks in the given range, which is inclusive of the start and exclusive of the end.
+    pub(crate) fn blocks(&self, start_height: u32, end_height: u32) -> Result<Vec<Block<N>>, RestError> {
+        const MAX_BLOCK_RANGE: u32 = 50;
+
+        // Ensure the end height is greater than the start height.
+        if start_height > end_height {
+            return Err(RestError("Invalid block range".to_string()));
+        }
+
+        // Ensure the block range is bounded.
+        if end_height - start_height > MAX_BLOCK_RANGE {
+            return Err(RestError(format!(
+                "Cannot request more than {MAX_BLOCK_RANGE} blocks per call (requested {})",
+                end_height - start_height
+            )));
+        }
+
+        Ok(cfg_into_iter!((start_height..end_height))
+            .map(|height| self.ledger.get_block(height))
+            .collect::<Result<Vec<_>, _>>()?)
+    }
+
+    /// Returns the page of deployed program IDs with the given prefix, in alphabetical order.
+    pub(crate) fn programs(
+        &self,
+        prefix: String,
+        cursor: usize,
+        limit: Option<usize>,
+    ) -> Result<ProgramList<N>, RestError> {
+        const MAX_PROGRAMS: usize = 100;
+
+        // Ensure the page is bounded.
+        let limit = limit.unwrap_or(MAX_PROGRAMS);
+        if limit > MAX_PROGRAMS {
+            return Err(RestError(format!("Cannot request more than {MAX_PROGRAMS} programs per call")));
+        }
+
+        // Retrieve the matching program IDs, in alphabetical order.
+        let mut program_ids = self
+            .ledger
+            .program_ids()
+            .map(|program_id| (program_id.to_string(), *program_id))
+            .filter(|(name, _)| name.starts_with(&prefix))
+            .collect::<Vec<_>>();
+        program_ids.sort_unstable_by(|(a, _), (b, _)| a.cmp(b));
+
+        // Retrieve the page of program IDs.
+        let next_cursor = cursor.saturating_add(limit);
+        let next_cursor = (next_cursor < program_ids.len()).then_some(next_cursor);
+        let programs = program_ids.into_iter().skip(cursor).take(limit).map(|(_, program_id)| program_id).collect();
+
+        Ok(ProgramList { programs, next_cursor })
+    }
+
+    /// Returns the page of entries of the given mapping.
+    pub(crate) fn mapping_entries(
+        &self,
+        id: &ProgramID<N>,
+        name: &Identifier<N>,
+        cursor: Option<&Plaintext<N>>,
+        limit: Option<usize>,
+    ) -> Result<MappingEntries<N>, RestError> {
+        const MAX_MAPPING_ENTRIES: usize = 100;
+
+        // Ensure the page is bounded.
+        let limit = limit.unwrap_or(MAX_MAPPING_ENTRIES);
+        if limit > MAX_MAPPING_ENTRIES {
+            return Err(RestError(format!("Cannot request more than {MAX_MAPPING_ENTRIES} entries per call")));
+        }
+
+        // Retrieve the page of entries, which starts after the last key of the previous page.
+        let snapshot = self.mapping_snapshot(id, name)?;
+        let start = match cursor {
+            Some(cursor) => match snapshot.iter().position(|(key, _)| key == cursor) {
+                Some(index) => index.saturating_add(1),
+                None => return Err(RestError(format!("The cursor '{cursor}' is not a key of mapping '{id}/{name}'"))),
+            },
+            None => 0,
+        };
+        let entries: Vec<_> = snapshot.iter().skip(start).take(limit).cloned().collect();
+        let next_cursor = match start.saturating_add(entries.len()) < snapshot.len() {
+            true => entries.last().map(|(key, _)| key.clone()),
+            false => None,
+        };
+
+        Ok(MappingEntries { entries, next_cursor })
+    }
+
+    /// Returns the entries of the given mapping as of the latest block.
+    /// Note: The finalize store cannot iterate over a mapping from a key, so the entries are loaded
+    /// once per block, and cached for the following pages of the mapping.
+    fn mapping_snapshot(
+        &self,
+        id: &ProgramID<N>,
+        name: &Identifier<N>,
+    ) -> Result<Arc<Vec<(Plaintext<N>, Value<N>)>>, RestError> {
+        // Note: The cache is locked while the entries are loaded, so that concurrent pages load them only once.
+        let mut cache = self.mapping_snapshot.lock();
+        let latest_hash = self.ledger.latest_hash();
+        if let Some((hash, program_id, mapping_name, entries)) = &*cache {
+            if *hash == latest_hash && program_id == id && mapping_name == name {
+                return Ok(entries.clone());
+            }
+        }
+        let entries = Arc::new(self.ledger.vm().finalize_store().get_mapping_confirmed(id, name)?);
+        *cache = Some((latest_hash, *id, *name, entries.clone()));
+        Ok(entries)
+    }
+
+    /// Returns the consensus module, or an error if this node is not a validator.
+    pub(crate) fn consensus(&self) -> Result<&Consensus<N>, RestError> {
+        self.consensus.as_ref().ok_or_else(|| RestError("Route isn't available for this node type".to_string()))
+    }
+
+    /// Returns the log filter, or an error if it cannot be changed on this node.
+    fn log_filter() -> Result<&'static LogFilter, RestError> {
+        LogFilter::get().ok_or_else(|| RestError("The log filter cannot be changed on this node".to_string()))
+    }
+
+    /// Returns the indexer, or an error if it is not enabled.
+    fn indexer(&self) -> Result<&Indexer<N>, RestError> {
+        self.indexer.as_ref().ok_or_else(|| RestError("The indexer is not enabled on this node".to_string()))
+    }
+
+    /// Returns the median priority fee of the unconfirmed transactions in the memory pool, in microcredits.
+    /// The fee is cached until a block is added, or the number of unconfirmed transactions changes.
+    async fn suggested_priority_fee(&self) -> Result<u64> {
+        let consensus = match &self.consensus {
+            Some(consensus) => consensus,
+            None => return Ok(0),
+        };
+
+        // Return the cached fee, if the latest block and the memory pool are unchanged.
+        // Note: The cache is locked during the computation, so that concurrent requests compute it only once.
+        let mut cache = self.priority_fee.lock().await;
+        let (latest_hash, num_transactions) = (self.ledger.latest_hash(), consensus.num_unconfirmed_transactions());
+        if let Some((hash, num, priority_fee)) = &*cache {
+            if *hash == latest_hash && *num == num_transactions {
+                return Ok(*priority_fee);
+            }
+        }
+
+        // Compute the priority fee of each unconfirmed transaction, as the amount paid over its base fee.
+        let mut priority_fees = Vec::new();
+        for (_, transaction) in consensus.unconfirmed_transactions().collect::<Vec<_>>() {
+            let transaction = transaction.deserialize().await?;
+            let base_fee = match &transaction {
+                Transaction::Deploy(_, _, deployment, _) => deployment_cost(deployment).map(|(base_fee, _)| base_fee),
+                Transaction::Execute(_, execution, _) => {
+                    execution_cost(self.ledger.vm(), execution).map(|(base_fee, _)| base_fee)
+                }
+                Transaction::Fee(..) => continue,
+            };
+            // Skip the transactions whose base fee cannot be computed, such as those calling undeployed programs.
+            if let Ok(base_fee) = base_fee {
+                priority_fees.push((*transaction.fee_amount()?).saturating_sub(base_fee));
+            }
+        }
+
+        // Cache and return the median priority fee.
+        priority_fees.sort_unstable();
+        let priority_fee = priority_fees.get(priority_fees.len() / 2).copied().unwrap_or(0);
+        *cache = Some((latest_hash, num_transactions, priority_fee));
+        Ok(priority_fee)
+    }
+
+    /// Simulates the given transaction against the latest state, without modifying it.
+    fn simulate(&self, transaction: &Transaction<N>) -> Result<Simulation<N>> {
+        // Retrieve the fee paid by the transaction.
+        let fee = *transaction.fee_amount()?;
+
+        // Ensure the transaction passes the checks of the memory pool.
+        if let Err(error) = self.ledger.check_transaction_basic(transaction, None) {
+            let error = Some(error.to_string());
+            return Ok(Simulation { valid: false, status: None, error, fee, changes: vec![] });
+        }
+
+        // Speculate on the transaction, while no block is being added.
+        // Note: The finalize operations of a rejected transaction are not returned by the VM.
+        let (status, operations) = match self.ledger_service.speculate_transaction(transaction)? {
+            Some(confirmed) if confirmed.is_accepted() => {
+                (SimulationStatus::Accepted, confirmed.finalize_operations().cloned().unwrap_or_default())
+            }
+            Some(_) => (SimulationStatus::Rejected, vec![]),
+            None => (SimulationStatus::Aborted, vec![]),
+        };
+        let changes = self.mapping_changes(transaction, &operations)?;
+        Ok(Simulation { valid: true, status: Some(status), error: None, fee, changes })
+    }
+
+    /// Returns the mapping changes of the given finalize operations, resolving the mappings and keys where possible.
+    fn mapping_changes(
+        &self,
+        transaction: &Transaction<N>,
+        operations: &[FinalizeOperation<N>],
+    ) -> Result<Vec<MappingChange<N>>> {
+        let finalize_store = self.ledger.vm().finalize_store();
+
+        // Collect the mappings of the programs in the transaction, including a deployed program.
+        let mut names = Vec::new();
+        if let Transaction::Deploy(_, _, deployment, _) = transaction {
+            let program_id = *deployment.program_id();
+            names.extend(deployment.program().mappings().keys().map(|name| (program_id, *name)));
+        }
+        for transition in transaction.transitions() {
+            if let Some(mapping_names) = finalize_store.get_mapping_names_confirmed(transition.program_id())? {
+                names.extend(mapping_names.into_iter().map(|name| (*transition.program_id(), name)));
+            }
+        }
+        // Collect the public inputs and outputs of the transaction, which are the candidate keys.
+        let candidates = candidate_keys(transaction.transitions());
+        let resolver = MappingResolver::new(names, &candidates)?;
+
+        operations
+            .iter()
+            .map(|operation| {
+                let (mapping_id, key_id) = match operation {
+                    FinalizeOperation::InsertKeyValue(mapping_id, key_id, _)
+                    | FinalizeOperation::UpdateKeyValue(mapping_id, _, key_id, _) => (mapping_id, Some(key_id)),
+                    FinalizeOperation::InitializeMapping(mapping_id)
+                    | FinalizeOperation::RemoveKeyValue(mapping_id, _)
+                    | FinalizeOperation::ReplaceMapping(mapping_id)
+                    | FinalizeOperation::RemoveMapping(mapping_id) => (mapping_id, None),
+                };
+                // Resolve the mapping and the key.
+                let (program_id, mapping) = match resolver.mapping(mapping_id) {
+                    Some((program_id, mapping)) => (Some(*program_id), Some(*mapping)),
+                    None => (None, None),
+                };
+                let key = key_id.and_then(|key_id| resolver.key(key_id)).cloned();
+                // Retrieve the value of the key in the latest state.
+                let previous_value = match (&program_id, &mapping, &key) {
+                    (Some(program_id), Some(mapping), Some(key)) => {
+                        finalize_store.get_value_confirmed(program_id, mapping, key)?
+                    }
+                    _ => None,
+                };
+                Ok::<_, anyhow::Error>(MappingChange {
+                    operation: operation.clone(),
+                    program_id,
+                    mapping,
+                    key,
+                    previous_value,
+                })
+            })
+            .collect()
     }
 }
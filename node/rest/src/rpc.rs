@@ -0,0 +1,315 @@
+// Copyright (C) 2019-2023 Aleo Systems Inc.
+// This file is part of the snarkOS library.
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at:
+// http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use super::*;
+use snarkvm::prelude::{block::Transaction, Identifier, Plaintext};
+
+use axum::{
+    body::Bytes,
+    response::{IntoResponse, Response},
+};
+use indexmap::IndexMap;
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+use serde_json::Value as JsonValue;
+
+/// The JSON-RPC version.
+const JSONRPC_VERSION: &str = "2.0";
+/// The maximum number of requests in a batch.
+const MAX_BATCH_SIZE: usize = 20;
+
+/// The error code for invalid JSON.
+const PARSE_ERROR: i64 = -32700;
+/// The error code for a JSON value which is not a valid request object.
+const INVALID_REQUEST: i64 = -32600;
+/// The error code for an unknown method.
+const METHOD_NOT_FOUND: i64 = -32601;
+/// The error code for invalid method parameters.
+const INVALID_PARAMS: i64 = -32602;
+/// The error code for a result which could not be serialized.
+const INTERNAL_ERROR: i64 = -32603;
+/// The error code for a `RestError`, such as a missing block or transaction.
+const SERVER_ERROR: i64 = -32000;
+
+/// A JSON-RPC request object.
+#[derive(Deserialize)]
+struct RpcRequest {
+    /// The JSON-RPC version, which must be `2.0`.
+    jsonrpc: String,
+    /// The method name.
+    method: String,
+    /// The method parameters, by position.
+    #[serde(default)]
+    params: JsonValue,
+    /// The request ID, which is absent for a notification.
+    id: Option<JsonValue>,
+}
+
+/// The parameters of `getPrograms`, by position or by name.
+#[derive(Deserialize)]
+struct ProgramsParams {
+    /// The prefix of the program IDs.
+    #[serde(default)]
+    prefix: Option<String>,
+    /// The index of the first program, from the `next_cursor` of the previous page.
+    #[serde(default)]
+    cursor: usize,
+    /// The maximum number of programs.
+    #[serde(default)]
+    limit: Option<usize>,
+}
+
+/// The parameters of `getMapping`, by position or by name.
+#[derive(Deserialize)]
+#[serde(bound = "N: Network")]
+struct MappingParams<N: Network> {
+    /// The program ID.
+    program_id: ProgramID<N>,
+    /// The mapping name.
+    mapping_name: Identifier<N>,
+    /// The last key of the previous page, from its `next_cursor`.
+    #[serde(default)]
+    cursor: Option<Plaintext<N>>,
+    /// The maximum number of entries.
+    #[serde(default)]
+    limit: Option<usize>,
+}
+
+/// The parameters of `getMappingValue`, by position or by name.
+#[derive(Deserialize)]
+#[serde(bound = "N: Network")]
+struct MappingValueParams<N: Network> {
+    /// The program ID.
+    program_id: ProgramID<N>,
+    /// The mapping name.
+    mapping_name: Identifier<N>,
+    /// The mapping key.
+    key: Plaintext<N>,
+}
+
+/// A JSON-RPC response object.
+#[derive(Serialize)]
+struct RpcResponse {
+    /// The JSON-RPC version.
+    jsonrpc: &'static str,
+    /// The result of the method, if it succeeded.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    result: Option<JsonValue>,
+    /// The error of the method, if it failed.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    error: Option<RpcError>,
+    /// The request ID, or `null` if it could not be determined.
+    id: JsonValue,
+}
+
+impl RpcResponse {
+    /// Initializes a new response for the given request ID and result.
+    fn new(id: JsonValue, result: Result<JsonValue, RpcError>) -> Self {
+        match result {
+            Ok(result) => Self { jsonrpc: JSONRPC_VERSION, result: Some(result), error: None, id },
+            Err(error) => Self { jsonrpc: JSONRPC_VERSION, result: None, error: Some(error), id },
+        }
+    }
+}
+
+/// A JSON-RPC error object.
+#[derive(Serialize)]
+pub(crate) struct RpcError {
+    /// The error code.
+    code: i64,
+    /// The error message.
+    message: String,
+}
+
+impl RpcError {
+    /// Initializes a new error with the given code and message.
+    fn new(code: i64, message: impl ToString) -> Self {
+        Self { code, message: message.to_string() }
+    }
+}
+
+impl From<RestError> for RpcError {
+    fn from(error: RestError) -> Self {
+        Self::new(SERVER_ERROR, error.0)
+    }
+}
+
+impl From<anyhow::Error> for RpcError {
+    fn from(error: anyhow::Error) -> Self {
+        RestError::from(error).into()
+    }
+}
+
+/// Deserializes the parameters of a method, where missing parameters are an empty list.
+fn params<T: DeserializeOwned>(params: JsonValue) -> Result<T, RpcError> {
+    let params = match params {
+        JsonValue::Null => JsonValue::Array(vec![]),
+        params => params,
+    };
+    serde_json::from_value(params).map_err(|error| RpcError::new(INVALID_PARAMS, error))
+}
+
+/// Serializes the result of a method.
+fn result<T: Serialize>(result: T) -> Result<JsonValue, RpcError> {
+    serde_json::to_value(result).map_err(|error| RpcError::new(INTERNAL_ERROR, error))
+}
+
+impl<N: Network, C: ConsensusStorage<N>, R: Routing<N>> Rest<N, C, R> {
+    // POST /rpc
+    pub(crate) async fn rpc(State(rest): State<Self>, body: Bytes) -> Response {
+        // Parse the request, or the batch of requests.
+        let request = match serde_json::from_slice::<JsonValue>(&body) {
+            Ok(request) => request,
+            Err(error) => {
+                return Json(RpcResponse::new(JsonValue::Null, Err(RpcError::new(PARSE_ERROR, error)))).into_response();
+            }
+        };
+
+        match request {
+            JsonValue::Array(requests) if requests.is_empty() => {
+                let error = RpcError::new(INVALID_REQUEST, "The batch is empty");
+                Json(RpcResponse::new(JsonValue::Null, Err(error))).into_response()
+            }
+            JsonValue::Array(requests) if requests.len() > MAX_BATCH_SIZE => {
+                let error = RpcError::new(INVALID_REQUEST, format!("The batch exceeds {MAX_BATCH_SIZE} requests"));
+                Json(RpcResponse::new(JsonValue::Null, Err(error))).into_response()
+            }
+            JsonValue::Array(requests) => {
+                let mut responses = Vec::with_capacity(requests.len());
+                for request in requests {
+                    responses.extend(rest.handle_rpc_request(request).await);
+                }
+                // A batch of notifications has no response.
+                match responses.is_empty() {
+                    true => StatusCode::NO_CONTENT.into_response(),
+                    false => Json(responses).into_response(),
+                }
+            }
+            request => match rest.handle_rpc_request(request).await {
+                Some(response) => Json(response).into_response(),
+                None => StatusCode::NO_CONTENT.into_response(),
+            },
+        }
+    }
+
+    /// Handles a single request, and returns the response, unless the request is a notification.
+    async fn handle_rpc_request(&self, request: JsonValue) -> Option<RpcResponse> {
+        // Retrieve the request ID, to respond to an invalid request where possible.
+        let id = request.get("id").cloned().unwrap_or(JsonValue::Null);
+        let request = match serde_json::from_value::<RpcRequest>(request) {
+            Ok(request) if request.jsonrpc == JSONRPC_VERSION => request,
+            Ok(_) => return Some(RpcResponse::new(id, Err(RpcError::new(INVALID_REQUEST, "Expected JSON-RPC 2.0")))),
+            Err(error) => return Some(RpcResponse::new(id, Err(RpcError::new(INVALID_REQUEST, error)))),
+        };
+
+        let result = self.call_rpc_method(&request.method, request.params).await;
+        request.id.map(|id| RpcResponse::new(id, result))
+    }
+
+    /// Calls the given method, with the same behavior as the corresponding REST route.
+    async fn call_rpc_method(&self, method: &str, params: JsonValue) -> Result<JsonValue, RpcError> {
+        match method {
+            "latestHeight" => result(self.ledger.latest_height()),
+            "latestHash" => result(self.ledger.latest_hash()),
+            "latestBlock" => result(self.ledger.latest_block()),
+            "latestStateRoot" => result(self.ledger.latest_state_root()),
+            "latestCommittee" => result(self.ledger.latest_committee()?),
+            "getBlock" => {
+                let (height_or_hash,): (JsonValue,) = self::params(params)?;
+                let block = match height_or_hash {
+                    JsonValue::Number(height) => match height.as_u64().and_then(|height| u32::try_from(height).ok()) {
+                        Some(height) => self.ledger.get_block(height)?,
+                        None => return Err(RpcError::new(INVALID_PARAMS, "Invalid block height")),
+                    },
+                    JsonValue::String(hash) => match hash.parse::<N::BlockHash>() {
+                        Ok(hash) => self.ledger.get_block_by_hash(&hash)?,
+                        Err(_) => return Err(RpcError::new(INVALID_PARAMS, "Invalid block hash")),
+                    },
+                    _ => return Err(RpcError::new(INVALID_PARAMS, "Expected a block height or a block hash")),
+                };
+                result(block)
+            }
+            "getBlocks" => {
+                let (start_height, end_height): (u32, u32) = self::params(params)?;
+                result(self.blocks(start_height, end_height)?)
+            }
+            "getHeight" => {
+                let (hash,): (N::BlockHash,) = self::params(params)?;
+                result(self.ledger.get_height(&hash)?)
+            }
+            "getBlockTransactions" => {
+                let (height,): (u32,) = self::params(params)?;
+                result(self.ledger.get_transactions(height)?)
+            }
+            "getTransaction" => {
+                let (transaction_id,): (N::TransactionID,) = self::params(params)?;
+                result(self.ledger.get_transaction(transaction_id)?)
+            }
+            "getMemoryPoolTransmissions" => {
+                result(self.consensus()?.unconfirmed_transmissions().collect::<IndexMap<_, _>>())
+            }
+            "getMemoryPoolSolutions" => result(self.consensus()?.unconfirmed_solutions().collect::<IndexMap<_, _>>()),
+            "getMemoryPoolTransactions" => {
+                result(self.consensus()?.unconfirmed_transactions().collect::<IndexMap<_, _>>())
+            }
+            "getProgram" => {
+                let (program_id,): (ProgramID<N>,) = self::params(params)?;
+                result(self.ledger.vm().transaction_store().get_program(&program_id)?)
+            }
+            "getPrograms" => {
+                let page: ProgramsParams = self::params(params)?;
+                result(self.programs(page.prefix.unwrap_or_default(), page.cursor, page.limit)?)
+            }
+            "getMappingNames" => {
+                let (program_id,): (ProgramID<N>,) = self::params(params)?;
+                result(self.ledger.vm().finalize_store().get_mapping_names_confirmed(&program_id)?)
+            }
+            "getMapping" => {
+                let page: MappingParams<N> = self::params(params)?;
+                result(self.mapping_entries(&page.program_id, &page.mapping_name, page.cursor.as_ref(), page.limit)?)
+            }
+            "getMappingValue" => {
+                let query: MappingValueParams<N> = self::params(params)?;
+                let finalize_store = self.ledger.vm().finalize_store();
+                result(finalize_store.get_value_confirmed(&query.program_id, &query.mapping_name, &query.key)?)
+            }
+            "getStatePath" => {
+                let (commitment,): (Field<N>,) = self::params(params)?;
+                result(self.ledger.get_state_path_for_commitment(&commitment)?)
+            }
+            "findBlockHash" => {
+                let (transaction_id,): (N::TransactionID,) = self::params(params)?;
+                result(self.ledger.find_block_hash(&transaction_id)?)
+            }
+            "findTransactionIdFromProgramId" => {
+                let (program_id,): (ProgramID<N>,) = self::params(params)?;
+                result(self.ledger.find_transaction_id_from_program_id(&program_id)?)
+            }
+            "findTransactionIdFromTransitionId" => {
+                let (transition_id,): (N::TransitionID,) = self::params(params)?;
+                result(self.ledger.find_transaction_id_from_transition_id(&transition_id)?)
+            }
+            "findTransitionId" => {
+                let (input_or_output_id,): (Field<N>,) = self::params(params)?;
+                result(self.ledger.find_transition_id(&input_or_output_id)?)
+            }
+            "getPeersCount" => result(self.routing.router().number_of_connected_peers()),
+            "getPeers" => result(self.routing.router().connected_peers()),
+            "broadcastTransaction" => {
+                let (transaction,): (Transaction<N>,) = self::params(params)?;
+                result(self.broadcast_transaction(transaction).await?)
+            }
+            _ => Err(RpcError::new(METHOD_NOT_FOUND, format!("Method '{method}' does not exist"))),
+        }
+    }
+}
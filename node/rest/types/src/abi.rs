@@ -0,0 +1,274 @@
+// Copyright (C) 2019-2023 Aleo Systems Inc.
+// This file is part of the snarkOS library.
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at:
+// http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use snarkvm::prelude::{Identifier, Network, Program, ProgramID};
+
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+/// The interface of a program, as returned by `GET /testnet3/program/{programID}/abi`.
+///
+/// Types are written in Aleo instructions syntax, such as `u64.public`, `token.record`,
+/// or `credits.aleo/credits.record`.
+#[derive(Clone, Debug, Deserialize, Serialize, JsonSchema)]
+#[serde(bound = "N: Network")]
+#[schemars(bound = "N: Network", rename = "ProgramAbi")]
+pub struct ProgramAbi<N: Network> {
+    /// The program ID.
+    #[schemars(with = "String")]
+    pub program: ProgramID<N>,
+    /// The imported programs.
+    #[schemars(with = "Vec<String>")]
+    pub imports: Vec<ProgramID<N>>,
+    /// The structs.
+    pub structs: Vec<StructAbi<N>>,
+    /// The records.
+    pub records: Vec<RecordAbi<N>>,
+    /// The mappings.
+    pub mappings: Vec<MappingAbi<N>>,
+    /// The functions.
+    pub functions: Vec<FunctionAbi<N>>,
+}
+
+/// A named member of a struct or an entry of a record.
+#[derive(Clone, Debug, Deserialize, Serialize, JsonSchema)]
+#[serde(bound = "N: Network")]
+#[schemars(bound = "N: Network", rename = "MemberAbi")]
+pub struct MemberAbi<N: Network> {
+    /// The member name.
+    #[schemars(with = "String")]
+    pub name: Identifier<N>,
+    /// The member type.
+    #[serde(rename = "type")]
+    pub type_: String,
+}
+
+/// The interface of a struct.
+#[derive(Clone, Debug, Deserialize, Serialize, JsonSchema)]
+#[serde(bound = "N: Network")]
+#[schemars(bound = "N: Network", rename = "StructAbi")]
+pub struct StructAbi<N: Network> {
+    /// The struct name.
+    #[schemars(with = "String")]
+    pub name: Identifier<N>,
+    /// The struct members.
+    pub members: Vec<MemberAbi<N>>,
+}
+
+/// The interface of a record.
+#[derive(Clone, Debug, Deserialize, Serialize, JsonSchema)]
+#[serde(bound = "N: Network")]
+#[schemars(bound = "N: Network", rename = "RecordAbi")]
+pub struct RecordAbi<N: Network> {
+    /// The record name.
+    #[schemars(with = "String")]
+    pub name: Identifier<N>,
+    /// The type of the record owner, which is `address.public` or `address.private`.
+    pub owner: String,
+    /// The record entries.
+    pub entries: Vec<MemberAbi<N>>,
+}
+
+/// The interface of a mapping.
+#[derive(Clone, Debug, Deserialize, Serialize, JsonSchema)]
+#[serde(bound = "N: Network")]
+#[schemars(bound = "N: Network", rename = "MappingAbi")]
+pub struct MappingAbi<N: Network> {
+    /// The mapping name.
+    #[schemars(with = "String")]
+    pub name: Identifier<N>,
+    /// The key type.
+    pub key: String,
+    /// The value type.
+    pub value: String,
+}
+
+/// The interface of a function.
+#[derive(Clone, Debug, Deserialize, Serialize, JsonSchema)]
+#[serde(bound = "N: Network")]
+#[schemars(bound = "N: Network", rename = "FunctionAbi")]
+pub struct FunctionAbi<N: Network> {
+    /// The function name.
+    #[schemars(with = "String")]
+    pub name: Identifier<N>,
+    /// The input types.
+    pub inputs: Vec<String>,
+    /// The output types.
+    pub outputs: Vec<String>,
+    /// The input types of the finalize logic, if the function has one.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub finalize: Option<Vec<String>>,
+}
+
+impl<N: Network> From<&Program<N>> for ProgramAbi<N> {
+    /// Returns the interface of the given program.
+    fn from(program: &Program<N>) -> Self {
+        let structs = program
+            .structs()
+            .values()
+            .map(|struct_| StructAbi {
+                name: *struct_.name(),
+                members: struct_
+                    .members()
+                    .iter()
+                    .map(|(name, type_)| MemberAbi { name: *name, type_: type_.to_string() })
+                    .collect(),
+            })
+            .collect();
+
+        let records = program
+            .records()
+            .values()
+            .map(|record| RecordAbi {
+                name: *record.name(),
+                owner: format!("address.{}", record.owner()),
+                entries: record
+                    .entries()
+                    .iter()
+                    .map(|(name, type_)| MemberAbi { name: *name, type_: type_.to_string() })
+                    .collect(),
+            })
+            .collect();
+
+        let mappings = program
+            .mappings()
+            .values()
+            .map(|mapping| MappingAbi {
+                name: *mapping.name(),
+                key: mapping.key().plaintext_type().to_string(),
+                value: mapping.value().plaintext_type().to_string(),
+            })
+            .collect();
+
+        let functions = program
+            .functions()
+            .values()
+            .map(|function| FunctionAbi {
+                name: *function.name(),
+                inputs: function.input_types().iter().map(ToString::to_string).collect(),
+                outputs: function.output_types().iter().map(ToString::to_string).collect(),
+                finalize: function
+                    .finalize_logic()
+                    .map(|finalize| finalize.input_types().iter().map(ToString::to_string).collect()),
+            })
+            .collect();
+
+        Self {
+            program: *program.id(),
+            imports: program.imports().keys().copied().collect(),
+            structs,
+            records,
+            mappings,
+            functions,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use snarkvm::prelude::Testnet3;
+
+    use std::str::FromStr;
+
+    type CurrentNetwork = Testnet3;
+
+    /// Returns the members in Aleo instructions syntax, such as `amount as u64.private`.
+    fn members(members: &[MemberAbi<CurrentNetwork>]) -> Vec<String> {
+        members.iter().map(|member| format!("{} as {}", member.name, member.type_)).collect()
+    }
+
+    #[test]
+    fn test_program_abi() {
+        let program = Program::<CurrentNetwork>::from_str(
+            r"
+program token.aleo;
+
+struct point:
+    x as u32;
+    y as u32;
+
+record token:
+    owner as address.private;
+    amount as u64.private;
+
+mapping account:
+    key as address.public;
+    value as u64.public;
+
+mapping points:
+    key as u32.public;
+    value as point.public;
+
+function mint_public:
+    input r0 as address.public;
+    input r1 as u64.public;
+    async mint_public r0 r1 into r2;
+    output r2 as token.aleo/mint_public.future;
+
+finalize mint_public:
+    input r0 as address.public;
+    input r1 as u64.public;
+    get.or_use account[r0] 0u64 into r2;
+    add r2 r1 into r3;
+    set r3 into account[r0];
+
+function transfer_private:
+    input r0 as token.record;
+    input r1 as address.private;
+    input r2 as u64.private;
+    sub r0.amount r2 into r3;
+    cast r1 r2 into r4 as token.record;
+    cast r0.owner r3 into r5 as token.record;
+    output r4 as token.record;
+    output r5 as token.record;",
+        )
+        .unwrap();
+
+        let abi = ProgramAbi::from(&program);
+        assert_eq!(abi.program.to_string(), "token.aleo");
+        assert!(abi.imports.is_empty());
+
+        // Check the structs.
+        assert_eq!(abi.structs.len(), 1);
+        assert_eq!(abi.structs[0].name.to_string(), "point");
+        assert_eq!(members(&abi.structs[0].members), ["x as u32", "y as u32"]);
+
+        // Check the records.
+        assert_eq!(abi.records.len(), 1);
+        assert_eq!(abi.records[0].name.to_string(), "token");
+        assert_eq!(abi.records[0].owner, "address.private");
+        assert_eq!(members(&abi.records[0].entries), ["amount as u64.private"]);
+
+        // Check the mappings.
+        let mappings = abi.mappings.iter().map(|mapping| (mapping.name.to_string(), &*mapping.key, &*mapping.value));
+        assert_eq!(mappings.collect::<Vec<_>>(), [
+            ("account".to_string(), "address", "u64"),
+            ("points".to_string(), "u32", "point")
+        ]);
+
+        // Check the functions.
+        assert_eq!(abi.functions.len(), 2);
+        let mint_public = &abi.functions[0];
+        assert_eq!(mint_public.name.to_string(), "mint_public");
+        assert_eq!(mint_public.inputs, ["address.public", "u64.public"]);
+        assert_eq!(mint_public.outputs, ["token.aleo/mint_public.future"]);
+        assert_eq!(mint_public.finalize, Some(vec!["address.public".to_string(), "u64.public".to_string()]));
+        let transfer_private = &abi.functions[1];
+        assert_eq!(transfer_private.name.to_string(), "transfer_private");
+        assert_eq!(transfer_private.inputs, ["token.record", "address.private", "u64.private"]);
+        assert_eq!(transfer_private.outputs, ["token.record", "token.record"]);
+        assert!(transfer_private.finalize.is_none());
+    }
+}
@@ -0,0 +1,37 @@
+// Copyright (C) 2019-2023 Aleo Systems Inc.
+// This file is part of the snarkOS library.
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at:
+// http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use snarkvm::prelude::{FromBytes, ToBytes};
+
+use anyhow::{ensure, Result};
+
+/// The media type of the binary encoding, which is the `ToBytes` encoding of the response object.
+pub const OCTET_STREAM: &str = "application/octet-stream";
+
+/// Returns the binary encoding of the given list, which is the number of objects as a `u32`, followed by the objects.
+pub fn list_to_bytes_le<T: ToBytes>(objects: &[T]) -> Result<Vec<u8>> {
+    let mut bytes = u32::try_from(objects.len())?.to_bytes_le()?;
+    for object in objects {
+        object.write_le(&mut bytes)?;
+    }
+    Ok(bytes)
+}
+
+/// Returns the list of the given binary encoding, as written by `list_to_bytes_le`.
+pub fn list_from_bytes_le<T: FromBytes>(mut bytes: &[u8]) -> Result<Vec<T>> {
+    let num_objects = u32::read_le(&mut bytes)?;
+    let objects = (0..num_objects).map(|_| T::read_le(&mut bytes)).collect::<Result<Vec<_>, _>>()?;
+    ensure!(bytes.is_empty(), "Found {} trailing bytes after the list", bytes.len());
+    Ok(objects)
+}
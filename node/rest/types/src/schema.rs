@@ -0,0 +1,88 @@
+// Copyright (C) 2019-2023 Aleo Systems Inc.
+// This file is part of the snarkOS library.
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at:
+// http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! The JSON schemas of the snarkVM objects in the REST API.
+//!
+//! The snarkVM objects implement their own JSON encoding, so they are documented by the types below,
+//! which are named after the objects. Objects in their string encoding, such as IDs, are documented as strings.
+
+use schemars::{
+    gen::SchemaGenerator,
+    schema::{InstanceType, Metadata, Schema, SchemaObject},
+    JsonSchema,
+};
+
+macro_rules! object_schema {
+    ($($name:ident => $description:literal,)*) => {
+        $(
+            #[doc = $description]
+            pub struct $name;
+
+            impl JsonSchema for $name {
+                fn schema_name() -> String {
+                    stringify!($name).to_string()
+                }
+
+                fn json_schema(_: &mut SchemaGenerator) -> Schema {
+                    SchemaObject {
+                        instance_type: Some(InstanceType::Object.into()),
+                        metadata: Some(Box::new(Metadata {
+                            description: Some($description.to_string()),
+                            ..Default::default()
+                        })),
+                        ..Default::default()
+                    }
+                    .into()
+                }
+            }
+        )*
+    };
+}
+
+object_schema! {
+    Block => "A block, in the JSON encoding of snarkVM.",
+    Committee => "A committee, in the JSON encoding of snarkVM.",
+    Deployment => "A deployment, in the JSON encoding of snarkVM.",
+    Execution => "An execution, in the JSON encoding of snarkVM.",
+    ProverSolution => "A prover solution, in the JSON encoding of snarkVM.",
+    Transaction => "A transaction, in the JSON encoding of snarkVM.",
+    Transactions => "The confirmed transactions of a block, in the JSON encoding of snarkVM.",
+    Transmission => "A transmission, in the JSON encoding of snarkVM.",
+}
+
+/// A transmitted object, in the JSON encoding of snarkVM.
+#[derive(JsonSchema)]
+pub struct Data<T> {
+    /// The encoding of the object, which is `object`, or `buffer` for its `ToBytes` encoding.
+    #[schemars(rename = "type")]
+    pub type_: String,
+    /// The object, or its `ToBytes` encoding.
+    pub data: T,
+}
+
+/// A finalize operation, in the JSON encoding of snarkVM.
+#[derive(JsonSchema)]
+pub struct FinalizeOperation {
+    /// The operation, such as `insert_key_value` or `remove_key_value`.
+    #[schemars(rename = "type")]
+    pub type_: String,
+    /// The mapping ID.
+    pub mapping_id: String,
+    /// The index of the key in the mapping, for updates and removals.
+    pub index: Option<u64>,
+    /// The key ID, for insertions and updates.
+    pub key_id: Option<String>,
+    /// The value ID, for insertions and updates.
+    pub value_id: Option<String>,
+}
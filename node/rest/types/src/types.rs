@@ -0,0 +1,173 @@
+// Copyright (C) 2019-2023 Aleo Systems Inc.
+// This file is part of the snarkOS library.
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at:
+// http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use snarkvm::{
+    prelude::{
+        block::{Deployment, Execution},
+        Identifier,
+        Network,
+        Plaintext,
+        ProgramID,
+        Value,
+    },
+    synthesizer::program::FinalizeOperation,
+};
+
+use crate::schema;
+
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+/// The `GET /testnet3/programs` response object.
+#[derive(Clone, Debug, Deserialize, Serialize, JsonSchema)]
+#[serde(bound = "N: Network")]
+#[schemars(bound = "N: Network", rename = "ProgramList")]
+pub struct ProgramList<N: Network> {
+    /// The deployed program IDs, in alphabetical order.
+    #[schemars(with = "Vec<String>")]
+    pub programs: Vec<ProgramID<N>>,
+    /// The cursor of the next page, if there are more programs.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub next_cursor: Option<usize>,
+}
+
+/// The `GET /testnet3/program/{programID}/mapping/{mappingName}` response object.
+#[derive(Clone, Debug, Deserialize, Serialize, JsonSchema)]
+#[serde(bound = "N: Network")]
+#[schemars(bound = "N: Network", rename = "MappingEntries")]
+pub struct MappingEntries<N: Network> {
+    /// The entries of the mapping, as (key, value).
+    #[schemars(with = "Vec<(String, String)>")]
+    pub entries: Vec<(Plaintext<N>, Value<N>)>,
+    /// The cursor of the next page, which is the last key of this page, if there are more entries.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[schemars(with = "Option<String>")]
+    pub next_cursor: Option<Plaintext<N>>,
+}
+
+/// A transaction in the `GET /testnet3/address/{address}/transactions` and
+/// `GET /testnet3/program/{programID}/transactions` response objects.
+#[derive(Clone, Debug, Deserialize, Serialize, JsonSchema)]
+#[serde(bound = "N: Network")]
+#[schemars(bound = "N: Network", rename = "IndexedTransaction")]
+pub struct IndexedTransaction<N: Network> {
+    /// The transaction ID.
+    #[schemars(with = "String")]
+    pub transaction_id: N::TransactionID,
+    /// The height of the block containing the transaction.
+    pub height: u32,
+}
+
+/// The `GET /testnet3/address/{address}/transactions` and `GET /testnet3/program/{programID}/transactions`
+/// response object, with the most recent transactions first.
+#[derive(Clone, Debug, Deserialize, Serialize, JsonSchema)]
+#[serde(bound = "N: Network")]
+#[schemars(bound = "N: Network", rename = "TransactionPage")]
+pub struct TransactionPage<N: Network> {
+    /// The transactions.
+    pub transactions: Vec<IndexedTransaction<N>>,
+    /// The cursor of the next page, if there are more transactions.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub next_cursor: Option<u64>,
+}
+
+/// The `POST /testnet3/transaction/estimateFee` request object, containing a deployment or an execution.
+#[derive(Clone, Debug, Deserialize, Serialize, JsonSchema)]
+#[serde(bound = "N: Network", rename_all = "lowercase")]
+#[schemars(bound = "N: Network", rename = "FeeRequest")]
+pub enum FeeRequest<N: Network> {
+    /// A deployment, which is priced by its size and program name.
+    Deployment(#[schemars(with = "schema::Deployment")] Deployment<N>),
+    /// An execution, which is priced by its size and finalize logic.
+    /// Note: The execution may be unproven, in which case the storage cost does not include the proof.
+    Execution(#[schemars(with = "schema::Execution")] Execution<N>),
+}
+
+/// The `POST /testnet3/transaction/estimateFee` response object, in microcredits.
+#[derive(Clone, Debug, Deserialize, Serialize, JsonSchema)]
+pub struct FeeEstimate {
+    /// The base fee, which is the minimum fee to accept the transaction.
+    pub base_fee: u64,
+    /// The storage cost of the transaction.
+    pub storage_cost: u64,
+    /// The namespace cost of a deployment.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub namespace_cost: Option<u64>,
+    /// The finalize cost of an execution.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub finalize_cost: Option<u64>,
+    /// The suggested priority fee, which is the median priority fee in the memory pool.
+    pub priority_fee: u64,
+}
+
+/// The outcome of finalizing a simulated transaction.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Deserialize, Serialize, JsonSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum SimulationStatus {
+    /// The transaction would be accepted.
+    Accepted,
+    /// The transaction would be rejected, and only its fee would be confirmed.
+    Rejected,
+    /// The transaction would be aborted.
+    Aborted,
+}
+
+/// The `POST /testnet3/transaction/simulate` response object.
+#[derive(Clone, Debug, Deserialize, Serialize, JsonSchema)]
+#[serde(bound = "N: Network")]
+#[schemars(bound = "N: Network", rename = "Simulation")]
+pub struct Simulation<N: Network> {
+    /// Whether the transaction passes the checks of the memory pool.
+    pub valid: bool,
+    /// The outcome of finalizing the transaction.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub status: Option<SimulationStatus>,
+    /// The reason the transaction is invalid.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+    /// The fee paid by the transaction, in microcredits.
+    pub fee: u64,
+    /// The mapping changes caused by finalizing the transaction.
+    pub changes: Vec<MappingChange<N>>,
+}
+
+/// A mapping change in the `POST /testnet3/transaction/simulate` response object.
+///
+/// The finalize operation only commits to the keys and values, so the mapping and key are resolved
+/// from the programs and public inputs and outputs of the transaction, where possible.
+#[derive(Clone, Debug, Deserialize, Serialize, JsonSchema)]
+#[serde(bound = "N: Network")]
+#[schemars(bound = "N: Network", rename = "MappingChange")]
+pub struct MappingChange<N: Network> {
+    /// The finalize operation.
+    #[serde(flatten)]
+    #[schemars(with = "schema::FinalizeOperation")]
+    pub operation: FinalizeOperation<N>,
+    /// The program ID of the mapping.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[schemars(with = "Option<String>")]
+    pub program_id: Option<ProgramID<N>>,
+    /// The name of the mapping.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[schemars(with = "Option<String>")]
+    pub mapping: Option<Identifier<N>>,
+    /// The key in the mapping.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[schemars(with = "Option<String>")]
+    pub key: Option<Plaintext<N>>,
+    /// The value of the key in the latest state.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[schemars(with = "Option<String>")]
+    pub previous_value: Option<Value<N>>,
+}
@@ -183,6 +183,10 @@ impl<N: Network> Router<N> {
         stream: &'a mut TcpStream,
         genesis_header: Header<N>,
     ) -> io::Result<(SocketAddr, Framed<&mut TcpStream, MessageCodec<N>>)> {
+        // Ensure the peer is not banned, before anything is exchanged with it.
+        if self.is_banned(peer_addr.ip()) {
+            return Err(error(format!("Dropping connection request from '{peer_addr}' (banned)")));
+        }
         // Construct the stream.
         let mut framed = Framed::new(stream, MessageCodec::<N>::handshake());
 
@@ -37,6 +37,7 @@ use snarkvm::prelude::{
 use anyhow::{anyhow, bail, Result};
 use std::{net::SocketAddr, time::Instant};
 use tokio::task::spawn_blocking;
+use tracing::instrument;
 
 #[async_trait]
 pub trait Inbound<N: Network>: Reading + Outbound<N> {
@@ -46,6 +47,7 @@ pub trait Inbound<N: Network>: Reading + Outbound<N> {
     const PING_SLEEP_IN_SECS: u64 = 9; // 9 seconds
 
     /// Handles the inbound message from the peer.
+    #[instrument(level = "trace", skip_all, fields(peer = %peer_addr, message = %message.name()))]
     async fn inbound(&self, peer_addr: SocketAddr, message: Message<N>) -> Result<()> {
         // Retrieve the listener IP for the peer.
         let peer_ip = match self.router().resolve_to_listener(&peer_addr) {
@@ -47,7 +47,15 @@ use snarkvm::prelude::{Address, Network, PrivateKey, ViewKey};
 use anyhow::{bail, Result};
 use indexmap::{IndexMap, IndexSet};
 use parking_lot::{Mutex, RwLock};
-use std::{collections::HashSet, future::Future, net::SocketAddr, ops::Deref, str::FromStr, sync::Arc, time::Instant};
+use std::{
+    collections::HashSet,
+    future::Future,
+    net::{IpAddr, SocketAddr},
+    ops::Deref,
+    str::FromStr,
+    sync::Arc,
+    time::Instant,
+};
 use tokio::task::JoinHandle;
 
 #[derive(Clone)]
@@ -85,6 +93,8 @@ pub struct InnerRouter<N: Network> {
     candidate_peers: RwLock<IndexSet<SocketAddr>>,
     /// The set of restricted peer IPs.
     restricted_peers: RwLock<IndexMap<SocketAddr, Instant>>,
+    /// The set of banned IP addresses, which are never connected to, until an operator unbans them.
+    banned_ips: RwLock<IndexSet<IpAddr>>,
     /// The spawned handles.
     handles: Mutex<Vec<JoinHandle<()>>>,
     /// The boolean flag for the development mode.
@@ -125,6 +135,7 @@ impl<N: Network> Router<N> {
             connecting_peers: Default::default(),
             candidate_peers: Default::default(),
             restricted_peers: Default::default(),
+            banned_ips: Default::default(),
             handles: Default::default(),
             is_dev,
         })))
@@ -177,6 +188,10 @@ impl<N: Network> Router<N> {
         if self.is_restricted(&peer_ip) {
             bail!("Dropping connection attempt to '{peer_ip}' (restricted)")
         }
+        // Ensure the peer is not banned.
+        if self.is_banned(peer_ip.ip()) {
+            bail!("Dropping connection attempt to '{peer_ip}' (banned)")
+        }
         // Ensure the node is not already connecting to this peer.
         if !self.connecting_peers.lock().insert(peer_ip) {
             bail!("Dropping connection attempt to '{peer_ip}' (already shaking hands as the initiator)")
@@ -284,6 +299,11 @@ impl<N: Network> Router<N> {
             .unwrap_or(false)
     }
 
+    /// Returns `true` if the given IP address is banned.
+    pub fn is_banned(&self, ip: IpAddr) -> bool {
+        self.banned_ips.read().contains(&ip)
+    }
+
     /// Returns the maximum number of connected peers.
     pub fn max_connected_peers(&self) -> usize {
         self.tcp.config().max_connections as usize
@@ -359,6 +379,11 @@ impl<N: Network> Router<N> {
         self.restricted_peers.read().keys().copied().collect()
     }
 
+    /// Returns the list of banned IP addresses.
+    pub fn banned_ips(&self) -> Vec<IpAddr> {
+        self.banned_ips.read().iter().copied().collect()
+    }
+
     /// Returns the list of trusted peers.
     pub fn trusted_peers(&self) -> &IndexSet<SocketAddr> {
         &self.trusted_peers
@@ -405,8 +430,11 @@ impl<N: Network> Router<N> {
         let eligible_peers = peers
             .iter()
             .filter(|peer_ip| {
-                // Ensure the peer is not itself, is not already connected, and is not restricted.
-                !self.is_local_ip(peer_ip) && !self.is_connected(peer_ip) && !self.is_restricted(peer_ip)
+                // Ensure the peer is not itself, is not already connected, and is not restricted or banned.
+                !self.is_local_ip(peer_ip)
+                    && !self.is_connected(peer_ip)
+                    && !self.is_restricted(peer_ip)
+                    && !self.is_banned(peer_ip.ip())
             })
             .take(max_candidate_peers);
 
@@ -422,6 +450,26 @@ impl<N: Network> Router<N> {
         self.restricted_peers.write().insert(peer_ip, Instant::now());
     }
 
+    /// Removes the given peer from the restricted peers, and returns `true` if it was restricted.
+    pub fn remove_restricted_peer(&self, peer_ip: SocketAddr) -> bool {
+        self.restricted_peers.write().remove(&peer_ip).is_some()
+    }
+
+    /// Bans the given IP address, and returns the connected peers with the IP address, which are to be disconnected.
+    /// Note: Unlike a restriction, the ban does not expire, and is only lifted by `remove_banned_ip`.
+    pub fn insert_banned_ip(&self, ip: IpAddr) -> Vec<SocketAddr> {
+        // Remove the peers with this IP address from the candidate peers.
+        self.candidate_peers.write().retain(|peer_ip| peer_ip.ip() != ip);
+        // Add the IP address to the banned IP addresses.
+        self.banned_ips.write().insert(ip);
+        self.connected_peers().into_iter().filter(|peer_ip| peer_ip.ip() == ip).collect()
+    }
+
+    /// Lifts the ban of the given IP address, and returns `true` if it was banned.
+    pub fn remove_banned_ip(&self, ip: IpAddr) -> bool {
+        self.banned_ips.write().shift_remove(&ip)
+    }
+
     /// Updates the connected peer with the given function.
     pub fn update_connected_peer<Fn: FnMut(&mut Peer<N>)>(
         &self,
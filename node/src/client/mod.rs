@@ -16,8 +16,9 @@ mod router;
 
 use crate::traits::NodeInterface;
 use snarkos_account::Account;
+use snarkos_node_indexer::Indexer;
 use snarkos_node_narwhal::ledger_service::CoreLedgerService;
-use snarkos_node_rest::Rest;
+use snarkos_node_rest::{RateLimits, Rest, TlsConfig};
 use snarkos_node_router::{
     messages::{Message, NodeType, UnconfirmedSolution},
     Heartbeat,
@@ -76,10 +77,13 @@ impl<N: Network, C: ConsensusStorage<N>> Client<N, C> {
     pub async fn new(
         node_ip: SocketAddr,
         rest_ip: Option<SocketAddr>,
+        rate_limits: RateLimits,
+        rest_tls: Option<TlsConfig>,
         account: Account<N>,
         trusted_peers: &[SocketAddr],
         genesis: Block<N>,
         cdn: Option<String>,
+        indexer: bool,
         dev: Option<u16>,
     ) -> Result<Self> {
         // Initialize the signal handler.
@@ -96,6 +100,12 @@ impl<N: Network, C: ConsensusStorage<N>> Client<N, C> {
             }
         }
 
+        // Initialize the indexer.
+        let indexer = match indexer {
+            true => Some(Indexer::open(dev)?),
+            false => None,
+        };
+
         // Initialize the ledger service.
         let ledger_service = Arc::new(CoreLedgerService::<N, C>::new(ledger.clone()));
         // Initialize the sync module.
@@ -127,10 +137,26 @@ impl<N: Network, C: ConsensusStorage<N>> Client<N, C> {
 
         // Initialize the REST server.
         if let Some(rest_ip) = rest_ip {
-            node.rest = Some(Rest::start(rest_ip, None, ledger.clone(), Arc::new(node.clone()))?);
+            node.rest = Some(
+                Rest::start(
+                    rest_ip,
+                    rate_limits,
+                    rest_tls,
+                    None,
+                    ledger.clone(),
+                    ledger_service.clone(),
+                    indexer.clone(),
+                    Arc::new(node.clone()),
+                )
+                .await?,
+            );
         }
         // Initialize the routing.
         node.initialize_routing().await;
+        // Initialize the indexer.
+        if let Some(indexer) = indexer {
+            node.handles.lock().push(tokio::spawn(indexer.run(ledger.clone())));
+        }
         // Initialize the sync module.
         node.initialize_sync();
         // Pass the node to the signal handler.
@@ -148,6 +174,11 @@ impl<N: Network, C: ConsensusStorage<N>> Client<N, C> {
     pub fn rest(&self) -> &Option<Rest<N, C, Self>> {
         &self.rest
     }
+
+    /// Returns `true` if the node is synced up to the latest block.
+    pub fn is_block_synced(&self) -> bool {
+        self.sync.is_block_synced()
+    }
 }
 
 impl<N: Network, C: ConsensusStorage<N>> Client<N, C> {
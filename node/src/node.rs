@@ -14,7 +14,9 @@
 
 use crate::{traits::NodeInterface, Client, Prover, Validator};
 use snarkos_account::Account;
-use snarkos_node_router::messages::NodeType;
+use snarkos_node_consensus::Consensus;
+use snarkos_node_rest::{RateLimits, TlsConfig};
+use snarkos_node_router::{messages::NodeType, Outbound, Router};
 use snarkvm::prelude::{
     block::Block,
     store::helpers::{memory::ConsensusMemory, rocksdb::ConsensusDB},
@@ -41,15 +43,31 @@ impl<N: Network> Node<N> {
     pub async fn new_validator(
         node_ip: SocketAddr,
         rest_ip: Option<SocketAddr>,
+        rate_limits: RateLimits,
+        rest_tls: Option<TlsConfig>,
         account: Account<N>,
         trusted_peers: &[SocketAddr],
         trusted_validators: &[SocketAddr],
         genesis: Block<N>,
         cdn: Option<String>,
+        indexer: bool,
         dev: Option<u16>,
     ) -> Result<Self> {
         Ok(Self::Validator(Arc::new(
-            Validator::new(node_ip, rest_ip, account, trusted_peers, trusted_validators, genesis, cdn, dev).await?,
+            Validator::new(
+                node_ip,
+                rest_ip,
+                rate_limits,
+                rest_tls,
+                account,
+                trusted_peers,
+                trusted_validators,
+                genesis,
+                cdn,
+                indexer,
+                dev,
+            )
+            .await?,
         )))
     }
 
@@ -68,13 +86,19 @@ impl<N: Network> Node<N> {
     pub async fn new_client(
         node_ip: SocketAddr,
         rest_ip: Option<SocketAddr>,
+        rate_limits: RateLimits,
+        rest_tls: Option<TlsConfig>,
         account: Account<N>,
         trusted_peers: &[SocketAddr],
         genesis: Block<N>,
         cdn: Option<String>,
+        indexer: bool,
         dev: Option<u16>,
     ) -> Result<Self> {
-        Ok(Self::Client(Arc::new(Client::new(node_ip, rest_ip, account, trusted_peers, genesis, cdn, dev).await?)))
+        Ok(Self::Client(Arc::new(
+            Client::new(node_ip, rest_ip, rate_limits, rest_tls, account, trusted_peers, genesis, cdn, indexer, dev)
+                .await?,
+        )))
     }
 
     /// Returns the node type.
@@ -121,4 +145,39 @@ impl<N: Network> Node<N> {
             Self::Client(node) => node.is_dev(),
         }
     }
+
+    /// Returns the router of the node.
+    pub fn router(&self) -> &Router<N> {
+        match self {
+            Self::Validator(node) => node.router(),
+            Self::Prover(node) => node.router(),
+            Self::Client(node) => node.router(),
+        }
+    }
+
+    /// Returns the consensus module of the node, if it is a validator.
+    pub fn consensus(&self) -> Option<&Consensus<N>> {
+        match self {
+            Self::Validator(node) => Some(node.consensus()),
+            Self::Prover(_) | Self::Client(_) => None,
+        }
+    }
+
+    /// Returns `true` if the node is synced up to the latest block.
+    pub fn is_block_synced(&self) -> bool {
+        match self {
+            Self::Validator(node) => node.is_block_synced(),
+            Self::Prover(node) => node.is_block_synced(),
+            Self::Client(node) => node.is_block_synced(),
+        }
+    }
+
+    /// Returns the height of the latest block, and its hash if the node has a ledger.
+    pub fn latest_block(&self) -> Option<(u32, Option<N::BlockHash>)> {
+        match self {
+            Self::Validator(node) => Some((node.ledger().latest_height(), Some(node.ledger().latest_hash()))),
+            Self::Prover(node) => node.latest_block_header().map(|header| (header.height(), None)),
+            Self::Client(node) => Some((node.ledger().latest_height(), Some(node.ledger().latest_hash()))),
+        }
+    }
 }
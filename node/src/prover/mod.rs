@@ -132,6 +132,16 @@ impl<N: Network, C: ConsensusStorage<N>> Prover<N, C> {
         // Return the node.
         Ok(node)
     }
+
+    /// Returns `true` if the node is synced up to the latest block.
+    pub fn is_block_synced(&self) -> bool {
+        self.sync.is_block_synced()
+    }
+
+    /// Returns the latest block header received from the peers, if any.
+    pub fn latest_block_header(&self) -> Option<Header<N>> {
+        self.latest_block_header.read().clone()
+    }
 }
 
 #[async_trait]
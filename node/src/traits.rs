@@ -59,6 +59,9 @@ pub trait NodeInterface<N: Network>: Routing<N> {
                     if let Some(node) = node_clone.get() {
                         node.shut_down().await;
                     }
+                    // Export the remaining tracing spans, if an exporter is installed.
+                    // Note: The tracer provider blocks while it is flushed, so it is shut down on a blocking thread.
+                    let _ = tokio::task::spawn_blocking(opentelemetry::global::shutdown_tracer_provider).await;
                     std::process::exit(0);
                 }
                 Err(error) => error!("tokio::signal::ctrl_c encountered an error: {}", error),
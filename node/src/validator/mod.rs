@@ -17,8 +17,9 @@ mod router;
 use crate::traits::NodeInterface;
 use snarkos_account::Account;
 use snarkos_node_consensus::Consensus;
+use snarkos_node_indexer::Indexer;
 use snarkos_node_narwhal::{helpers::init_primary_channels, ledger_service::CoreLedgerService};
-use snarkos_node_rest::Rest;
+use snarkos_node_rest::{RateLimits, Rest, TlsConfig};
 use snarkos_node_router::{
     messages::{NodeType, PuzzleResponse, UnconfirmedSolution, UnconfirmedTransaction},
     Heartbeat,
@@ -74,11 +75,14 @@ impl<N: Network, C: ConsensusStorage<N>> Validator<N, C> {
     pub async fn new(
         node_ip: SocketAddr,
         rest_ip: Option<SocketAddr>,
+        rate_limits: RateLimits,
+        rest_tls: Option<TlsConfig>,
         account: Account<N>,
         trusted_peers: &[SocketAddr],
         trusted_validators: &[SocketAddr],
         genesis: Block<N>,
         cdn: Option<String>,
+        indexer: bool,
         dev: Option<u16>,
     ) -> Result<Self> {
         // Initialize the signal handler.
@@ -95,13 +99,19 @@ impl<N: Network, C: ConsensusStorage<N>> Validator<N, C> {
             }
         }
 
+        // Initialize the indexer.
+        let indexer = match indexer {
+            true => Some(Indexer::open(dev)?),
+            false => None,
+        };
+
         // Initialize the ledger service.
         let ledger_service = Arc::new(CoreLedgerService::new(ledger.clone()));
         // Initialize the sync module.
         let sync = BlockSync::new(BlockSyncMode::Gateway, ledger_service.clone());
 
         // Initialize the consensus.
-        let mut consensus = Consensus::new(account.clone(), ledger_service, None, trusted_validators, dev)?;
+        let mut consensus = Consensus::new(account.clone(), ledger_service.clone(), None, trusted_validators, dev)?;
         // Initialize the primary channels.
         let (primary_sender, primary_receiver) = init_primary_channels::<N>();
         // Start the consensus.
@@ -133,10 +143,26 @@ impl<N: Network, C: ConsensusStorage<N>> Validator<N, C> {
 
         // Initialize the REST server.
         if let Some(rest_ip) = rest_ip {
-            node.rest = Some(Rest::start(rest_ip, Some(consensus), ledger.clone(), Arc::new(node.clone()))?);
+            node.rest = Some(
+                Rest::start(
+                    rest_ip,
+                    rate_limits,
+                    rest_tls,
+                    Some(consensus),
+                    ledger.clone(),
+                    ledger_service.clone(),
+                    indexer.clone(),
+                    Arc::new(node.clone()),
+                )
+                .await?,
+            );
         }
         // Initialize the routing.
         node.initialize_routing().await;
+        // Initialize the indexer.
+        if let Some(indexer) = indexer {
+            node.handles.lock().push(tokio::spawn(indexer.run(ledger.clone())));
+        }
         // Pass the node to the signal handler.
         let _ = signal_node.set(node.clone());
         // Return the node.
@@ -148,10 +174,20 @@ impl<N: Network, C: ConsensusStorage<N>> Validator<N, C> {
         &self.ledger
     }
 
+    /// Returns the consensus module.
+    pub fn consensus(&self) -> &Consensus<N> {
+        &self.consensus
+    }
+
     /// Returns the REST server.
     pub fn rest(&self) -> &Option<Rest<N, C, Self>> {
         &self.rest
     }
+
+    /// Returns `true` if the node is synced up to the latest block.
+    pub fn is_block_synced(&self) -> bool {
+        self.sync.is_block_synced()
+    }
 }
 
 impl<N: Network, C: ConsensusStorage<N>> Validator<N, C> {
@@ -474,11 +510,14 @@ mod tests {
         let validator = Validator::<CurrentNetwork, ConsensusMemory<CurrentNetwork>>::new(
             node,
             Some(rest),
+            RateLimits::default(),
+            None,
             account,
             &[],
             &[],
             genesis,
             None,
+            false,
             dev,
         )
         .await
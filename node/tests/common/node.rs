@@ -14,25 +14,64 @@
 
 use crate::common::test_peer::sample_genesis_block;
 use snarkos_account::Account;
-use snarkos_node::{Client, Prover, Validator};
+use snarkos_node::{
+    rest::{RateLimits, TlsConfig},
+    Client,
+    Prover,
+    Validator,
+};
 use snarkvm::prelude::{store::helpers::memory::ConsensusMemory, Testnet3 as CurrentNetwork};
 
-use std::str::FromStr;
+use std::{net::SocketAddr, str::FromStr};
 
 pub async fn client() -> Client<CurrentNetwork, ConsensusMemory<CurrentNetwork>> {
     Client::new(
         "127.0.0.1:0".parse().unwrap(),
         None,
+        RateLimits::default(),
+        None,
         Account::<CurrentNetwork>::from_str("APrivateKey1zkp2oVPTci9kKcUprnbzMwq95Di1MQERpYBhEeqvkrDirK1").unwrap(),
         &[],
         sample_genesis_block(),
-        None, // No CDN.
+        None,  // No CDN.
+        false, // No indexer.
         None,
     )
     .await
     .expect("couldn't create client instance")
 }
 
+pub async fn client_with_rest(rest_ip: SocketAddr) -> Client<CurrentNetwork, ConsensusMemory<CurrentNetwork>> {
+    client_with_rest_limits(rest_ip, RateLimits::default()).await
+}
+
+pub async fn client_with_rest_limits(
+    rest_ip: SocketAddr,
+    rate_limits: RateLimits,
+) -> Client<CurrentNetwork, ConsensusMemory<CurrentNetwork>> {
+    client_with_rest_tls(rest_ip, rate_limits, None).await.expect("couldn't create client instance")
+}
+
+pub async fn client_with_rest_tls(
+    rest_ip: SocketAddr,
+    rate_limits: RateLimits,
+    tls: Option<TlsConfig>,
+) -> anyhow::Result<Client<CurrentNetwork, ConsensusMemory<CurrentNetwork>>> {
+    Client::new(
+        "127.0.0.1:0".parse().unwrap(),
+        Some(rest_ip),
+        rate_limits,
+        tls,
+        Account::<CurrentNetwork>::from_str("APrivateKey1zkp2oVPTci9kKcUprnbzMwq95Di1MQERpYBhEeqvkrDirK1").unwrap(),
+        &[],
+        sample_genesis_block(),
+        None,  // No CDN.
+        false, // No indexer.
+        None,
+    )
+    .await
+}
+
 pub async fn prover() -> Prover<CurrentNetwork, ConsensusMemory<CurrentNetwork>> {
     Prover::new(
         "127.0.0.1:0".parse().unwrap(),
@@ -49,11 +88,14 @@ pub async fn validator() -> Validator<CurrentNetwork, ConsensusMemory<CurrentNet
     Validator::new(
         "127.0.0.1:0".parse().unwrap(),
         None,
+        RateLimits::default(),
+        None,
         Account::<CurrentNetwork>::from_str("APrivateKey1zkp2oVPTci9kKcUprnbzMwq95Di1MQERpYBhEeqvkrDirK1").unwrap(),
         &[],
         &[],
         sample_genesis_block(), // Should load the current network's genesis block.
         None,                   // No CDN.
+        false,                  // No indexer.
         None,
     )
     .await
@@ -0,0 +1,85 @@
+// Copyright (C) 2019-2023 Aleo Systems Inc.
+// This file is part of the snarkOS library.
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at:
+// http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+#![recursion_limit = "256"]
+
+#[allow(dead_code)]
+mod common;
+use common::node::client_with_rest;
+
+use reqwest::StatusCode;
+use std::{net::TcpListener, time::Duration};
+use tokio::time::sleep;
+
+#[tokio::test]
+async fn test_openapi_covers_routes() {
+    // Start a node with the REST server.
+    let rest_ip = TcpListener::bind("127.0.0.1:0").unwrap().local_addr().unwrap();
+    let _node = client_with_rest(rest_ip).await;
+
+    // Retrieve the OpenAPI document, once the REST server is started.
+    let client = reqwest::Client::new();
+    let mut document = None;
+    for _ in 0..50 {
+        if let Ok(response) = client.get(format!("http://{rest_ip}/testnet3/openapi.json")).send().await {
+            document = Some(response.json::<serde_json::Value>().await.unwrap());
+            break;
+        }
+        sleep(Duration::from_millis(100)).await;
+    }
+    let document = document.expect("The REST server did not start");
+    assert_eq!(document["openapi"], "3.0.3");
+
+    let paths = document["paths"].as_object().unwrap();
+    assert!(paths.contains_key("/testnet3/openapi.json"));
+    assert!(paths["/testnet3/program/{id}/mapping/{name}"]["get"]["parameters"].as_array().unwrap().len() >= 4);
+
+    // Ensure the schemas are derived from the types, and collected in the components.
+    let schemas = document["components"]["schemas"].as_object().unwrap();
+    let estimate_fee = &paths["/testnet3/transaction/estimateFee"]["post"];
+    assert_eq!(
+        estimate_fee["responses"]["200"]["content"]["application/json"]["schema"]["$ref"],
+        "#/components/schemas/FeeEstimate"
+    );
+    assert_eq!(
+        estimate_fee["requestBody"]["content"]["application/json"]["schema"]["$ref"],
+        "#/components/schemas/FeeRequest"
+    );
+    assert!(schemas["FeeEstimate"]["properties"]["base_fee"].is_object());
+    assert!(schemas["ProgramList"]["properties"]["programs"].is_object());
+    assert!(schemas.contains_key("Block"));
+
+    // Ensure the response schemas are inferred from the outputs of the handlers.
+    let response = |path: &str| &paths[path]["get"]["responses"]["200"]["content"]["application/json"]["schema"];
+    assert_eq!(response("/testnet3/latest/height")["type"], "integer");
+    assert_eq!(response("/testnet3/latest/block")["$ref"], "#/components/schemas/Block");
+    assert_eq!(response("/testnet3/blocks")["items"]["$ref"], "#/components/schemas/Block");
+    assert_eq!(response("/testnet3/program/{id}/abi")["$ref"], "#/components/schemas/ProgramAbi");
+
+    // Ensure every documented operation is a registered route, with placeholder parameters.
+    for (path, methods) in paths {
+        let url = format!("http://{rest_ip}{}", path.replace(['{', '}'], "").replace("height_or_hash", "0"));
+        for (method, operation) in methods.as_object().unwrap() {
+            assert!(operation["operationId"].is_string(), "{method} {path} is missing an operation ID");
+            let request = match method.as_str() {
+                "get" => client.get(&url),
+                "post" => client.post(&url).json(&serde_json::json!({})),
+                _ => panic!("Unexpected method {method} for {path}"),
+            };
+            let status = request.send().await.unwrap().status();
+            assert_ne!(status, StatusCode::NOT_FOUND, "{method} {path} is not a registered route");
+            assert_ne!(status, StatusCode::METHOD_NOT_ALLOWED, "{method} {path} is not a registered route");
+        }
+    }
+}
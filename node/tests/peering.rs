@@ -186,3 +186,24 @@ async fn duplicate_disconnect_attempts() {
     assert_eq!(node1.router().number_of_connected_peers(), 0);
     assert_eq!(node2.router().number_of_connected_peers(), 0);
 }
+
+#[tokio::test(flavor = "multi_thread")]
+async fn banned_ip_cannot_connect() {
+    // Spin up 2 full nodes.
+    let node1 = client().await;
+    let node2 = client().await;
+    let addr1 = node1.tcp().listening_addr().unwrap();
+    let addr2 = node2.tcp().listening_addr().unwrap();
+
+    // Ban the IP address of node1 on node2, which rejects the inbound connection.
+    assert!(node2.router().insert_banned_ip(addr1.ip()).is_empty());
+    assert!(!node1.router().connect(addr2).unwrap().await.unwrap());
+    assert_eq!(node2.router().number_of_connected_peers(), 0);
+    // Node2 does not connect to the banned IP address either.
+    assert!(node2.router().connect(addr1).is_none());
+
+    // Once the ban is lifted, the nodes connect.
+    assert!(node2.router().remove_banned_ip(addr1.ip()));
+    assert!(node1.router().connect(addr2).unwrap().await.unwrap());
+    assert_eq!(node1.router().number_of_connected_peers(), 1);
+}
@@ -0,0 +1,126 @@
+// Copyright (C) 2019-2023 Aleo Systems Inc.
+// This file is part of the snarkOS library.
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at:
+// http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+#![recursion_limit = "256"]
+
+#[allow(dead_code)]
+mod common;
+use common::node::client_with_rest_limits;
+
+use snarkos_node::rest::{Quota, RateLimits};
+use snarkos_node_rest_client::RestClient;
+use snarkvm::prelude::Testnet3 as CurrentNetwork;
+
+use reqwest::{header::RETRY_AFTER, StatusCode};
+use std::{
+    net::{SocketAddr, TcpListener},
+    time::Duration,
+};
+use tokio::time::sleep;
+
+/// Returns an unused local address for the REST server.
+fn sample_rest_ip() -> SocketAddr {
+    TcpListener::bind("127.0.0.1:0").unwrap().local_addr().unwrap()
+}
+
+/// Returns rate limits which allow a burst of 3 standard requests, at 1 request per second.
+fn sample_rate_limits() -> RateLimits {
+    RateLimits { standard: Quota { per_second: 1, burst: 3 }, ..Default::default() }
+}
+
+/// Returns rate limits which allow a burst of 1 heavy request, at 1 request per second.
+fn sample_heavy_rate_limits() -> RateLimits {
+    RateLimits { heavy: Quota { per_second: 1, burst: 1 }, ..Default::default() }
+}
+
+/// Sends requests to the given route until the REST server responds, and returns the statuses of the next requests.
+async fn statuses(rest_ip: SocketAddr, route: &str, num_requests: usize) -> Vec<(StatusCode, Option<u64>)> {
+    let client = reqwest::Client::new();
+    let url = format!("http://{rest_ip}{route}");
+    for _ in 0..50 {
+        if client.get(&url).send().await.is_ok() {
+            break;
+        }
+        sleep(Duration::from_millis(100)).await;
+    }
+
+    let mut statuses = Vec::with_capacity(num_requests);
+    for _ in 0..num_requests {
+        let response = client.get(&url).send().await.unwrap();
+        let retry_after = response.headers().get(RETRY_AFTER).map(|value| value.to_str().unwrap().parse().unwrap());
+        statuses.push((response.status(), retry_after));
+    }
+    statuses
+}
+
+#[tokio::test]
+async fn test_rate_limit_per_ip() {
+    let rest_ip = sample_rest_ip();
+    let _node = client_with_rest_limits(rest_ip, sample_rate_limits()).await;
+
+    // The burst is exhausted, so the following requests are rejected, with the time to wait.
+    let statuses = statuses(rest_ip, "/testnet3/latest/height", 5).await;
+    let (status, retry_after) = statuses.last().unwrap();
+    assert_eq!(*status, StatusCode::TOO_MANY_REQUESTS);
+    assert!(retry_after.unwrap() >= 1);
+
+    // The heavy routes have a separate quota.
+    let response = reqwest::get(format!("http://{rest_ip}/testnet3/blocks?start=0&end=1")).await.unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+
+    // The quota is refilled over time.
+    sleep(Duration::from_secs(2)).await;
+    let response = reqwest::get(format!("http://{rest_ip}/testnet3/latest/height")).await.unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+}
+
+#[tokio::test]
+async fn test_rate_limit_allowlist() {
+    let rest_ip = sample_rest_ip();
+    let rate_limits = RateLimits { allowlist: vec![rest_ip.ip()], ..sample_rate_limits() };
+    let _node = client_with_rest_limits(rest_ip, rate_limits).await;
+
+    // The trusted IP is not limited.
+    let statuses = statuses(rest_ip, "/testnet3/latest/height", 10).await;
+    assert!(statuses.iter().all(|(status, _)| *status == StatusCode::OK));
+}
+
+#[tokio::test]
+async fn test_rate_limit_heavy_routes() {
+    let rest_ip = sample_rest_ip();
+    let _node = client_with_rest_limits(rest_ip, sample_heavy_rate_limits()).await;
+
+    // The routes are classified by their route, whatever their path parameters.
+    let statuses = statuses(rest_ip, "/testnet3/program/credits.aleo/mapping/account", 1).await;
+    assert_eq!(statuses[0].0, StatusCode::TOO_MANY_REQUESTS);
+    let response = reqwest::get(format!("http://{rest_ip}/testnet3/memoryPool/transactions")).await.unwrap();
+    assert_eq!(response.status(), StatusCode::TOO_MANY_REQUESTS);
+
+    // The standard routes have a separate quota.
+    let response = reqwest::get(format!("http://{rest_ip}/testnet3/program/credits.aleo")).await.unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+}
+
+#[tokio::test]
+async fn test_rest_client_retries_rate_limited() {
+    let rest_ip = sample_rest_ip();
+    let _node = client_with_rest_limits(rest_ip, sample_heavy_rate_limits()).await;
+
+    // Exhaust the heavy quota.
+    statuses(rest_ip, "/testnet3/blocks?start=0&end=1", 0).await;
+
+    // The client waits for the time given by the node, and retries the request.
+    let client = RestClient::<CurrentNetwork>::new(&format!("http://{rest_ip}")).unwrap();
+    assert_eq!(client.get_blocks(0, 1).await.unwrap().len(), 1);
+}
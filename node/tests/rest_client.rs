@@ -0,0 +1,425 @@
+// Copyright (C) 2019-2023 Aleo Systems Inc.
+// This file is part of the snarkOS library.
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at:
+// http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+#![recursion_limit = "256"]
+
+#[allow(dead_code)]
+mod common;
+use common::{node::client_with_rest, sample_account, sample_genesis_block};
+
+use snarkos_node::rest::{Claims, LogFilter};
+use snarkos_node_rest_client::{blocking, list_from_bytes_le, FeeRequest, RestClient, StatusError, OCTET_STREAM};
+use snarkvm::{
+    circuit::AleoV0,
+    prelude::{
+        block::{Block, Execution},
+        store::{helpers::memory::ConsensusMemory, ConsensusStore},
+        Field,
+        FromBytes,
+        Program,
+        Testnet3 as CurrentNetwork,
+        VM,
+    },
+};
+
+use anyhow::ensure;
+use reqwest::header::{ACCEPT, ACCEPT_ENCODING, CONTENT_ENCODING, CONTENT_TYPE};
+use std::{
+    net::{SocketAddr, TcpListener},
+    sync::{Arc, Mutex},
+    time::Duration,
+};
+use tokio::time::sleep;
+
+/// Returns an unused local address for the REST server.
+fn sample_rest_ip() -> SocketAddr {
+    TcpListener::bind("127.0.0.1:0").unwrap().local_addr().unwrap()
+}
+
+/// Waits for the REST server at the given address to accept requests.
+async fn wait_for_rest(rest_ip: SocketAddr) -> RestClient<CurrentNetwork> {
+    let client = RestClient::new(&format!("http://{rest_ip}")).unwrap();
+    for _ in 0..50 {
+        if client.latest_height().await.is_ok() {
+            return client;
+        }
+        sleep(Duration::from_millis(100)).await;
+    }
+    panic!("The REST server at {rest_ip} did not start");
+}
+
+#[tokio::test]
+async fn test_latest_and_block_routes() {
+    let rest_ip = sample_rest_ip();
+    let _node = client_with_rest(rest_ip).await;
+    let client = wait_for_rest(rest_ip).await;
+
+    let genesis = sample_genesis_block();
+    assert_eq!(client.latest_height().await.unwrap(), 0);
+    assert_eq!(client.latest_hash().await.unwrap(), genesis.hash());
+    assert_eq!(client.latest_block().await.unwrap(), genesis);
+    assert_eq!(client.get_block(0).await.unwrap(), genesis);
+    assert_eq!(client.get_block_by_hash(&genesis.hash()).await.unwrap(), genesis);
+    assert_eq!(client.get_blocks(0, 1).await.unwrap(), vec![genesis.clone()]);
+    assert_eq!(client.get_height(&genesis.hash()).await.unwrap(), 0);
+
+    // A missing block is returned as an error.
+    assert!(client.get_block(1).await.is_err());
+}
+
+#[tokio::test]
+async fn test_block_encodings() {
+    let rest_ip = sample_rest_ip();
+    let _node = client_with_rest(rest_ip).await;
+    wait_for_rest(rest_ip).await;
+
+    let genesis = sample_genesis_block();
+    let client = reqwest::Client::builder().no_gzip().build().unwrap();
+
+    // The binary encoding is negotiated from the `Accept` header.
+    let response =
+        client.get(format!("http://{rest_ip}/testnet3/block/0")).header(ACCEPT, OCTET_STREAM).send().await.unwrap();
+    assert_eq!(response.headers()[CONTENT_TYPE], OCTET_STREAM);
+    assert_eq!(Block::from_bytes_le(&response.bytes().await.unwrap()).unwrap(), genesis);
+
+    let response = client
+        .get(format!("http://{rest_ip}/testnet3/blocks?start=0&end=1"))
+        .header(ACCEPT, OCTET_STREAM)
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(list_from_bytes_le::<Block<CurrentNetwork>>(&response.bytes().await.unwrap()).unwrap(), vec![genesis]);
+
+    // JSON remains the default.
+    let response = client.get(format!("http://{rest_ip}/testnet3/block/0")).send().await.unwrap();
+    assert_eq!(response.headers()[CONTENT_TYPE], "application/json");
+
+    // The responses are compressed, as accepted by the client.
+    let response = client
+        .get(format!("http://{rest_ip}/testnet3/blocks?start=0&end=1"))
+        .header(ACCEPT_ENCODING, "gzip")
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(response.headers()[CONTENT_ENCODING], "gzip");
+}
+
+#[tokio::test]
+async fn test_transaction_routes() {
+    let rest_ip = sample_rest_ip();
+    let _node = client_with_rest(rest_ip).await;
+    let client = wait_for_rest(rest_ip).await;
+
+    let genesis = sample_genesis_block();
+    let transactions = client.get_block_transactions(0).await.unwrap();
+    assert_eq!(transactions, *genesis.transactions());
+
+    let transaction = genesis.transactions().iter().next().unwrap();
+    assert_eq!(client.get_transaction(&transaction.id()).await.unwrap(), **transaction);
+    assert_eq!(client.find_block_hash(&transaction.id()).await.unwrap(), Some(genesis.hash()));
+
+    // The transition is found by its output ID, and a missing ID is not found.
+    let transition = transaction.transitions().next().unwrap();
+    let output_id = transition.outputs()[0].id();
+    assert_eq!(client.find_transition_id(output_id).await.unwrap(), *transition.id());
+    let error = client.find_transition_id(&Field::from_u64(1)).await.unwrap_err();
+    assert!(error.downcast_ref::<StatusError>().unwrap().is_not_found());
+}
+
+#[tokio::test]
+async fn test_simulate_transaction() {
+    let rest_ip = sample_rest_ip();
+    let _node = client_with_rest(rest_ip).await;
+    let client = wait_for_rest(rest_ip).await;
+
+    // A transaction which is already in the ledger fails the checks of the memory pool.
+    let genesis = sample_genesis_block();
+    let transaction = genesis.transactions().iter().next().unwrap();
+    let simulation = client.simulate_transaction(transaction).await.unwrap();
+    assert!(!simulation.valid);
+    assert!(simulation.error.is_some());
+    assert_eq!(simulation.status, None);
+    assert_eq!(simulation.fee, *transaction.fee_amount().unwrap());
+    assert!(simulation.changes.is_empty());
+}
+
+#[tokio::test]
+async fn test_program_routes() {
+    let rest_ip = sample_rest_ip();
+    let _node = client_with_rest(rest_ip).await;
+    let client = wait_for_rest(rest_ip).await;
+
+    let credits = "credits.aleo".try_into().unwrap();
+    assert_eq!(client.get_program(&credits).await.unwrap(), Program::credits().unwrap());
+    assert_eq!(client.find_program(&credits).await.unwrap(), Some(Program::credits().unwrap()));
+
+    // A missing program is returned as a 404, which is not an error when finding the program.
+    let missing = "missing.aleo".try_into().unwrap();
+    let error = client.get_program(&missing).await.unwrap_err();
+    assert!(error.downcast_ref::<StatusError>().unwrap().is_not_found());
+    assert_eq!(client.find_program(&missing).await.unwrap(), None);
+
+    // The entries of a mapping are paged, with the last key of each page as the cursor of the next page.
+    let committee = "committee".try_into().unwrap();
+    let entries = client.get_mapping(&credits, &committee, None, None).await.unwrap().entries;
+    let (mut paged, mut cursor) = (Vec::new(), None);
+    loop {
+        let page = client.get_mapping(&credits, &committee, cursor.as_ref(), Some(1)).await.unwrap();
+        assert!(page.entries.len() <= 1);
+        paged.extend(page.entries);
+        match page.next_cursor {
+            Some(next_cursor) => cursor = Some(next_cursor),
+            None => break,
+        }
+    }
+    assert_eq!(paged, entries);
+}
+
+#[tokio::test]
+async fn test_program_abi_route() {
+    let rest_ip = sample_rest_ip();
+    let _node = client_with_rest(rest_ip).await;
+    let client = wait_for_rest(rest_ip).await;
+
+    let response = reqwest::get(format!("http://{rest_ip}/testnet3/program/credits.aleo/abi")).await.unwrap();
+    assert!(response.status().is_success());
+    let abi = response.json::<serde_json::Value>().await.unwrap();
+    assert_eq!(abi["program"], "credits.aleo");
+    assert_eq!(abi["imports"], serde_json::json!([]));
+
+    // Returns the item of the given name in the given list of the interface.
+    let item = |list: &str, name: &str| {
+        abi[list].as_array().unwrap().iter().find(|item| item["name"] == name).cloned().unwrap()
+    };
+
+    // Check a struct, a record, a mapping, and a function with a finalize logic.
+    assert_eq!(
+        item("structs", "committee_state")["members"],
+        serde_json::json!([{ "name": "microcredits", "type": "u64" }, { "name": "is_open", "type": "boolean" }])
+    );
+    let record = item("records", "credits");
+    assert_eq!(record["owner"], "address.private");
+    assert_eq!(record["entries"], serde_json::json!([{ "name": "microcredits", "type": "u64.private" }]));
+    let mapping = item("mappings", "account");
+    assert_eq!(mapping["key"], "address");
+    assert_eq!(mapping["value"], "u64");
+    let function = item("functions", "transfer_public");
+    assert_eq!(function["inputs"], serde_json::json!(["address.public", "u64.public"]));
+    assert_eq!(function["outputs"], serde_json::json!(["credits.aleo/transfer_public.future"]));
+    assert_eq!(function["finalize"], serde_json::json!(["address.public", "address.public", "u64.public"]));
+
+    // The client returns the same interface.
+    let credits = "credits.aleo".try_into().unwrap();
+    assert_eq!(serde_json::to_value(client.get_program_abi(&credits).await.unwrap()).unwrap(), abi);
+}
+
+#[tokio::test]
+async fn test_estimate_fee_of_unproven_execution() {
+    let rest_ip = sample_rest_ip();
+    let _node = client_with_rest(rest_ip).await;
+    let client = wait_for_rest(rest_ip).await;
+
+    // Run a public transfer, without proving it.
+    let account = sample_account();
+    let vm = VM::from(ConsensusStore::<CurrentNetwork, ConsensusMemory<CurrentNetwork>>::open(None).unwrap()).unwrap();
+    let inputs = [account.address().to_string(), "1u64".to_string()];
+    let authorization = vm
+        .authorize(account.private_key(), "credits.aleo", "transfer_public", inputs.iter(), &mut rand::thread_rng())
+        .unwrap();
+    let (_, trace) = vm.process().read().execute::<AleoV0>(authorization).unwrap();
+    let execution = Execution::from(trace.transitions().iter().cloned(), Default::default(), None).unwrap();
+
+    // The node prices the execution, including its finalize logic.
+    let request = FeeRequest::Execution(execution);
+    let estimate = client.estimate_fee(&request).await.unwrap();
+    assert!(estimate.base_fee > 0);
+    assert!(estimate.finalize_cost.unwrap() > 0);
+    // The memory pool is empty, so no priority fee is suggested.
+    assert_eq!(estimate.priority_fee, 0);
+}
+
+#[tokio::test]
+async fn test_node_routes() {
+    let rest_ip = sample_rest_ip();
+    let _node = client_with_rest(rest_ip).await;
+    let client = wait_for_rest(rest_ip).await;
+
+    assert_eq!(client.get_node_address().await.unwrap(), sample_account().address());
+    assert_eq!(client.get_peers_count().await.unwrap(), 0);
+    assert!(client.get_peers_all().await.unwrap().is_empty());
+
+    // The memory pool is only available on validators.
+    assert!(client.get_memory_pool_transactions().await.is_err());
+
+    // The indexer is not enabled.
+    assert!(client.get_address_transactions(&sample_account().address(), None, None).await.is_err());
+
+    // The JSON-RPC endpoint returns the same results.
+    let response = client
+        .rpc(&serde_json::json!({ "jsonrpc": "2.0", "method": "latestHeight", "params": [], "id": 1 }))
+        .await
+        .unwrap();
+    assert_eq!(response, serde_json::json!({ "jsonrpc": "2.0", "result": 0, "id": 1 }));
+}
+
+#[tokio::test]
+async fn test_rpc_methods() {
+    let rest_ip = sample_rest_ip();
+    let _node = client_with_rest(rest_ip).await;
+    let client = wait_for_rest(rest_ip).await;
+
+    // Calls the given method, and returns its response.
+    let call = |method: &str, params: serde_json::Value| {
+        let request = serde_json::json!({ "jsonrpc": "2.0", "method": method, "params": params, "id": 1 });
+        let client = client.clone();
+        async move { client.rpc(&request).await.unwrap() }
+    };
+
+    // The block methods return the same results as the REST routes.
+    let genesis = sample_genesis_block();
+    let hash = genesis.hash().to_string();
+    assert_eq!(call("getBlock", serde_json::json!([0])).await["result"], serde_json::to_value(&genesis).unwrap());
+    assert_eq!(call("getBlock", serde_json::json!([hash])).await["result"], serde_json::to_value(&genesis).unwrap());
+    assert_eq!(call("getBlocks", serde_json::json!([0, 1])).await["result"], serde_json::json!([genesis]));
+    assert_eq!(call("getHeight", serde_json::json!([hash])).await["result"], 0);
+    assert_eq!(call("getBlocks", serde_json::json!([0, 100])).await["error"]["code"], -32000);
+
+    // The transaction ID of the genesis block is found from its transitions.
+    let transaction = genesis.transactions().iter().next().unwrap();
+    let transition_id = transaction.transitions().next().unwrap().id().to_string();
+    let response = call("findBlockHash", serde_json::json!([transaction.id()])).await;
+    assert_eq!(response["result"], serde_json::json!(hash));
+    let response = call("findTransactionIdFromTransitionId", serde_json::json!([transition_id])).await;
+    assert_eq!(response["result"], serde_json::json!(transaction.id()));
+
+    // The program methods accept parameters by position or by name.
+    let response = call("getProgram", serde_json::json!(["credits.aleo"])).await;
+    assert_eq!(response["result"], serde_json::to_value(Program::<CurrentNetwork>::credits().unwrap()).unwrap());
+    assert_eq!(call("getProgram", serde_json::json!(["missing.aleo"])).await["result"], serde_json::Value::Null);
+    let response = call("getPrograms", serde_json::json!({ "prefix": "credits" })).await;
+    assert_eq!(response["result"]["programs"], serde_json::json!(["credits.aleo"]));
+    let credits = "credits.aleo".try_into().unwrap();
+    let mapping_names = client.get_mapping_names(&credits).await.unwrap();
+    let response = call("getMappingNames", serde_json::json!(["credits.aleo"])).await;
+    assert_eq!(response["result"], serde_json::to_value(&mapping_names).unwrap());
+    let response = call("getMapping", serde_json::json!(["credits.aleo", "committee", null, 1])).await;
+    assert_eq!(response["result"]["entries"].as_array().unwrap().len(), 1);
+    let address = sample_account().address();
+    let committee = "committee".try_into().unwrap();
+    let expected = client.get_mapping_value(&credits, &committee, &address.to_string().parse().unwrap()).await;
+    let response = call(
+        "getMappingValue",
+        serde_json::json!({ "program_id": "credits.aleo", "mapping_name": "committee", "key": address }),
+    )
+    .await;
+    assert_eq!(response["result"], serde_json::to_value(expected.unwrap()).unwrap());
+
+    // The memory pool is only available on validators, and unknown methods and parameters are rejected.
+    assert_eq!(call("getMemoryPoolTransactions", serde_json::json!([])).await["error"]["code"], -32000);
+    assert_eq!(call("getBlock", serde_json::json!(["invalid"])).await["error"]["code"], -32602);
+    assert_eq!(call("unknownMethod", serde_json::json!([])).await["error"]["code"], -32601);
+
+    // A batch returns a response per request, except for notifications.
+    let batch = serde_json::json!([
+        { "jsonrpc": "2.0", "method": "latestHeight", "id": 1 },
+        { "jsonrpc": "2.0", "method": "latestHash", "id": 2 },
+        { "jsonrpc": "2.0", "method": "latestHeight" },
+    ]);
+    let responses = client.rpc(&batch).await.unwrap();
+    assert_eq!(
+        responses,
+        serde_json::json!([
+            { "jsonrpc": "2.0", "result": 0, "id": 1 },
+            { "jsonrpc": "2.0", "result": hash, "id": 2 },
+        ])
+    );
+
+    // A batch is bounded.
+    let request = serde_json::json!({ "jsonrpc": "2.0", "method": "latestHeight", "id": 1 });
+    let batch = serde_json::Value::Array(vec![request; 21]);
+    assert_eq!(client.rpc(&batch).await.unwrap()["error"]["code"], -32600);
+}
+
+#[tokio::test]
+async fn test_peer_admin_routes() {
+    let rest_ip = sample_rest_ip();
+    let _node = client_with_rest(rest_ip).await;
+    let client = wait_for_rest(rest_ip).await;
+    let peer_ip = SocketAddr::from(([127, 0, 0, 1], 4140));
+
+    // The admin routes require the JSON Web Token of the node.
+    assert!(client.get_peers_bans().await.is_err());
+    assert!(client.ban_peer(peer_ip.ip()).await.is_err());
+
+    let token = Claims::new(sample_account().address()).to_jwt_string().unwrap();
+    let client = client.with_token(&token);
+    assert!(client.get_peers_bans().await.unwrap().is_empty());
+
+    // A banned IP address is listed, and none of its ports can be connected to.
+    assert!(!client.ban_peer(peer_ip.ip()).await.unwrap());
+    assert_eq!(client.get_peers_bans().await.unwrap(), vec![peer_ip.ip()]);
+    assert!(client.connect_peer(peer_ip).await.is_err());
+    assert!(client.connect_peer(SocketAddr::from(([127, 0, 0, 1], 4141))).await.is_err());
+
+    // The ban does not expire, and is only lifted by an operator.
+    assert!(client.unban_peer(peer_ip.ip()).await.unwrap());
+    assert!(!client.unban_peer(peer_ip.ip()).await.unwrap());
+    assert!(client.get_peers_bans().await.unwrap().is_empty());
+
+    // The memory pool is only available on validators.
+    assert!(client.connect_validator(peer_ip).await.is_err());
+}
+
+#[tokio::test]
+async fn test_log_filter_routes() {
+    let rest_ip = sample_rest_ip();
+    let _node = client_with_rest(rest_ip).await;
+    let token = Claims::new(sample_account().address()).to_jwt_string().unwrap();
+    let client = wait_for_rest(rest_ip).await.with_token(&token);
+
+    // The log filter cannot be changed until it is registered.
+    assert!(client.get_log_filter().await.is_err());
+
+    let applied = Arc::new(Mutex::new(Vec::new()));
+    let applied_clone = applied.clone();
+    let log_filter = LogFilter::new("info".to_string(), move |directives| {
+        ensure!(!directives.contains("loud"), "Invalid directives");
+        applied_clone.lock().unwrap().push(directives.to_string());
+        Ok(())
+    });
+    log_filter.register().unwrap();
+
+    assert_eq!(client.get_log_filter().await.unwrap(), "info");
+    let directives = "info,snarkos_node_narwhal::gateway=trace";
+    assert_eq!(client.set_log_filter(directives).await.unwrap(), directives);
+    assert_eq!(client.get_log_filter().await.unwrap(), directives);
+    assert_eq!(*applied.lock().unwrap(), vec![directives.to_string()]);
+
+    // Invalid directives are not applied.
+    assert!(client.set_log_filter("info,snarkos_node_narwhal=loud").await.is_err());
+    assert_eq!(client.get_log_filter().await.unwrap(), directives);
+}
+
+#[test]
+fn test_blocking_client() {
+    let runtime = tokio::runtime::Runtime::new().unwrap();
+    let rest_ip = sample_rest_ip();
+    let _node = runtime.block_on(client_with_rest(rest_ip));
+    runtime.block_on(wait_for_rest(rest_ip));
+
+    // The blocking client must be used outside of the runtime.
+    let client = blocking::RestClient::<CurrentNetwork>::new(&format!("http://{rest_ip}")).unwrap();
+    assert_eq!(client.latest_height().unwrap(), 0);
+    assert_eq!(client.latest_block().unwrap(), sample_genesis_block());
+}
@@ -0,0 +1,111 @@
+// Copyright (C) 2019-2023 Aleo Systems Inc.
+// This file is part of the snarkOS library.
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at:
+// http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+#![recursion_limit = "256"]
+
+#[allow(dead_code)]
+mod common;
+use common::node::client_with_rest_tls;
+
+use snarkos_node::rest::{RateLimits, TlsConfig};
+
+use reqwest::{Certificate, StatusCode};
+use std::{
+    net::{SocketAddr, TcpListener},
+    path::PathBuf,
+    time::Duration,
+};
+use tokio::time::sleep;
+
+/// Returns an unused local address for the REST server.
+fn sample_rest_ip() -> SocketAddr {
+    TcpListener::bind("127.0.0.1:0").unwrap().local_addr().unwrap()
+}
+
+/// Writes a new self-signed certificate for `localhost` and its private key to the given files,
+/// and returns the certificate.
+fn write_sample_certificate(tls: &TlsConfig) -> Certificate {
+    let certificate = rcgen::generate_simple_self_signed(vec!["localhost".to_string()]).unwrap();
+    let cert_pem = certificate.serialize_pem().unwrap();
+    std::fs::write(&tls.cert_path, &cert_pem).unwrap();
+    std::fs::write(&tls.key_path, certificate.serialize_private_key_pem()).unwrap();
+    Certificate::from_pem(cert_pem.as_bytes()).unwrap()
+}
+
+/// Returns a TLS configuration with unique file paths for the given test.
+fn sample_tls_config(name: &str) -> TlsConfig {
+    let path = |file: &str| -> PathBuf {
+        std::env::temp_dir().join(format!("snarkos_test_{name}_{file}_{}.pem", std::process::id()))
+    };
+    TlsConfig::new(path("cert"), path("key"))
+}
+
+/// Returns the status of `GET /testnet3/latest/height` over HTTPS, trusting only the given certificate.
+async fn latest_height_status(rest_ip: SocketAddr, certificate: Certificate) -> reqwest::Result<StatusCode> {
+    let client = reqwest::Client::builder().add_root_certificate(certificate).tls_built_in_root_certs(false).build()?;
+    let url = format!("https://localhost:{}/testnet3/latest/height", rest_ip.port());
+    Ok(client.get(url).send().await?.status())
+}
+
+#[tokio::test]
+async fn test_rest_tls_invalid_certificate() {
+    let tls = sample_tls_config("invalid");
+    std::fs::write(&tls.cert_path, "").unwrap();
+    std::fs::write(&tls.key_path, "").unwrap();
+
+    // The node fails to start, instead of the REST server failing in the background.
+    assert!(client_with_rest_tls(sample_rest_ip(), RateLimits::default(), Some(tls.clone())).await.is_err());
+
+    std::fs::remove_file(tls.cert_path).unwrap();
+    std::fs::remove_file(tls.key_path).unwrap();
+}
+
+#[tokio::test]
+async fn test_rest_tls_reload() {
+    let tls = sample_tls_config("reload");
+    let first = write_sample_certificate(&tls);
+
+    let rest_ip = sample_rest_ip();
+    let _node = client_with_rest_tls(rest_ip, RateLimits::default(), Some(tls.clone())).await.unwrap();
+
+    // The server is served over HTTPS with the first certificate.
+    let mut status = None;
+    for _ in 0..50 {
+        if let Ok(response) = latest_height_status(rest_ip, first.clone()).await {
+            status = Some(response);
+            break;
+        }
+        sleep(Duration::from_millis(100)).await;
+    }
+    assert_eq!(status, Some(StatusCode::OK));
+
+    // Renew the certificate, where the modification times must differ from the first files.
+    sleep(Duration::from_secs(1)).await;
+    let second = write_sample_certificate(&tls);
+
+    // The server reloads the second certificate, and no longer serves the first one.
+    let mut status = None;
+    for _ in 0..30 {
+        if let Ok(response) = latest_height_status(rest_ip, second.clone()).await {
+            status = Some(response);
+            break;
+        }
+        sleep(Duration::from_secs(1)).await;
+    }
+    assert_eq!(status, Some(StatusCode::OK));
+    assert!(latest_height_status(rest_ip, first).await.is_err());
+
+    std::fs::remove_file(tls.cert_path).unwrap();
+    std::fs::remove_file(tls.key_path).unwrap();
+}
@@ -12,7 +12,10 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
-use snarkos_cli::{commands::CLI, helpers::Updater};
+use snarkos_cli::{
+    commands::{WaitError, CLI},
+    helpers::Updater,
+};
 
 use clap::Parser;
 #[cfg(feature = "jemalloc")]
@@ -30,7 +33,14 @@ fn main() -> anyhow::Result<()> {
     // Run the CLI.
     match cli.command.parse() {
         Ok(output) => println!("{output}\n"),
-        Err(error) => println!("⚠️  {error}\n"),
+        Err(error) => {
+            println!("⚠️  {error}\n");
+            // Exit with a non-zero status code if a transaction broadcast with `--wait` was not accepted,
+            // so that scripts can detect the failure.
+            if error.is::<WaitError>() {
+                std::process::exit(1);
+            }
+        }
     }
     Ok(())
 }